@@ -0,0 +1,116 @@
+//! Measures how many instructions per second the core runs, with the bus
+//! passed as a trait object and as a concrete type, and how many times faster
+//! than real time it runs a 48K Spectrum.
+//!
+//! Run with `cargo run --release --example benchmark`, or for WebAssembly
+//! with `cargo build --release --example benchmark --target wasm32-wasip1`
+//! and a runtime such as `wasmtime`.
+
+use std::time::{Duration, Instant};
+
+use spectrum::{
+    z80::{assemble, Z80Io, Z80Memory, Z80},
+    zx_spectrum::ZxSpectrum,
+};
+
+const INSTRUCTIONS: u64 = 20_000_000;
+const FRAMES: u64 = 500;
+
+/// A mix of loads, arithmetic, jumps, stack operations and block transfers,
+/// run forever.
+const PROGRAM: &str = "
+        ORG 0
+start:  LD SP, 0
+        LD HL, 8000h
+        LD DE, 9000h
+        LD BC, 100h
+        LDIR
+        LD HL, 8000h
+        LD B, 0
+.sum:   LD A, (HL)
+        ADD A, B
+        LD (HL), A
+        INC HL
+        PUSH BC
+        POP BC
+        CALL .next
+        DJNZ .sum
+        LD IX, 8000h
+        LD B, 40h
+.index: LD A, (IX+1)
+        XOR (IX+2)
+        LD (IX+0), A
+        INC IX
+        SRL A
+        OUT (0FEh), A
+        DJNZ .index
+        JP start
+.next:  RET
+";
+
+struct Ram {
+    bytes: [u8; 0x10000],
+}
+
+impl Z80Memory for Ram {
+    fn read(&self, address: u16) -> u8 {
+        self.bytes[address as usize]
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        self.bytes[address as usize] = data;
+    }
+}
+
+struct Ports;
+
+impl Z80Io for Ports {
+    fn read_port(&mut self, _port: u16) -> u8 {
+        0xFF
+    }
+
+    fn write_port(&mut self, _port: u16, _data: u8) {}
+}
+
+fn run<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(mem: &mut M, io: &mut I) -> Duration {
+    let z80 = &mut Z80::new();
+    let start = Instant::now();
+    for _ in 0..INSTRUCTIONS {
+        z80.process_next_instruction(mem, io);
+    }
+    start.elapsed()
+}
+
+fn report(name: &str, elapsed: Duration) {
+    println!(
+        "{name}: {:.1} million instructions per second",
+        INSTRUCTIONS as f64 / elapsed.as_secs_f64() / 1e6
+    );
+}
+
+fn main() {
+    let program = assemble(PROGRAM).expect("valid program");
+    let load = || {
+        let mut mem = Ram {
+            bytes: [0; 0x10000],
+        };
+        program.write_to(&mut mem);
+        mem
+    };
+
+    let mem: &mut dyn Z80Memory = &mut load();
+    let io: &mut dyn Z80Io = &mut Ports;
+    report("&mut dyn Z80Memory", run(mem, io));
+    report("concrete Z80Memory", run(&mut load(), &mut Ports));
+
+    let spectrum = &mut ZxSpectrum::new();
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        spectrum.run_frame();
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "ZX Spectrum 48K: {:.1} times real time",
+        FRAMES as f64 / 50.0 / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,7 @@
+// The tests compare flags with `assert_eq!(false, ...)` for symmetry with
+// their `assert_eq!(true, ...)` counterparts, and describe their scenarios
+// with tuples of function pointers.
+#![cfg_attr(test, allow(clippy::bool_assert_comparison, clippy::type_complexity))]
+
+pub mod z80;
+pub mod zx_spectrum;
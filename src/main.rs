@@ -1,8 +1,5 @@
-mod z80;
-mod zx_spectrum;
-
-use zx_spectrum::ZxSpectrum;
+use spectrum::zx_spectrum::ZxSpectrum;
 
 fn main() {
-    let spectrum = ZxSpectrum::new();
+    let _spectrum = ZxSpectrum::new();
 }
@@ -0,0 +1,1399 @@
+use std::{collections::HashMap, error::Error, fmt, sync::OnceLock};
+
+use super::{
+    decode,
+    instruction::{Condition, Operand, Operation, Prefix, Register, RegisterPair, Shift},
+    Z80Memory,
+};
+
+/// Machine code assembled from source, starting at `origin`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assembly {
+    pub origin: u16,
+    pub bytes: Vec<u8>,
+    symbols: HashMap<String, u16>,
+}
+
+impl Assembly {
+    /// The value of a label or EQU. Local symbols are looked up with the
+    /// global label they belong to, as in `main.loop`.
+    pub fn symbol(&self, name: &str) -> Option<u16> {
+        self.symbols.get(&name.to_ascii_uppercase()).copied()
+    }
+
+    /// Writes the machine code into memory at the address it was assembled
+    /// for, such as to patch a ROM.
+    pub fn write_to(&self, mem: &mut (impl Z80Memory + ?Sized)) {
+        for (offset, data) in self.bytes.iter().enumerate() {
+            mem.write(self.origin.wrapping_add(offset as u16), *data);
+        }
+    }
+}
+
+/// An error in the source, with the line it was found on, counting from 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssemblyError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for AssemblyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for AssemblyError {}
+
+/// Assembles Zilog-syntax source into machine code.
+///
+/// Each line can hold a label followed by a colon, an instruction or
+/// directive, and a comment after a semicolon. Labels starting with a dot are
+/// local to the last global label, so each routine can have its own `.loop`.
+/// Instructions may use any expression built from numbers, symbols, `$` for
+/// the address of the current line, parentheses and the operators
+/// `+ - * / % & | ^ << >> ~`. Numbers can be decimal, hexadecimal as `0FFh`,
+/// `$FF` or `0xFF`, binary as `1010b` or `%1010`, or a character as `'A'`.
+///
+/// The directives are:
+/// * `ORG address` to continue assembling at a later address.
+/// * `DB` and `DW` to emit lists of bytes or words, where DB also takes
+///   strings in double quotes.
+/// * `DS count[, fill]` to reserve a number of bytes.
+/// * `name EQU value` to define a symbol.
+///
+/// An operand in parentheses is an address in memory or a port, so `(2+3)*4`
+/// has to be written as `4*(2+3)` to be read as an expression.
+pub fn assemble(source: &str) -> Result<Assembly, AssemblyError> {
+    let encodings = encodings();
+
+    // The first pass finds the address of every label, which the second pass
+    // then uses to emit the machine code
+    let mut symbols = HashMap::new();
+    for pass in [Pass::First, Pass::Final] {
+        let assembler = &mut Assembler {
+            encodings,
+            pass,
+            symbols,
+            scope: String::new(),
+            address: 0,
+            origin: None,
+            bytes: Vec::new(),
+        };
+        for (index, line) in source.lines().enumerate() {
+            assembler
+                .assemble_line(line)
+                .map_err(|message| AssemblyError {
+                    line: index + 1,
+                    message,
+                })?;
+        }
+
+        if pass == Pass::Final {
+            return Ok(Assembly {
+                origin: assembler.origin.unwrap_or(0),
+                bytes: std::mem::take(&mut assembler.bytes),
+                symbols: std::mem::take(&mut assembler.symbols),
+            });
+        }
+        symbols = std::mem::take(&mut assembler.symbols);
+    }
+
+    unreachable!("the final pass returns")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    /// Symbols may not be defined yet, so they read as 0 and values are not
+    /// range checked.
+    First,
+    Final,
+}
+
+/// The prefix bytes and opcode of every operation, keyed by the operation
+/// with the values of its operands cleared.
+type Encodings = HashMap<Operation, (Prefix, u8)>;
+
+/// The encodings, built the first time they are needed.
+fn encodings() -> &'static Encodings {
+    static ENCODINGS: OnceLock<Encodings> = OnceLock::new();
+    ENCODINGS.get_or_init(build_encodings)
+}
+
+/// Builds the encodings by decoding every opcode on every page, keeping the
+/// documented one where several opcodes decode to the same operation.
+fn build_encodings() -> Encodings {
+    let pages: [&[u8]; 7] = [
+        &[],
+        &[0xCB],
+        &[0xED],
+        &[0xDD],
+        &[0xFD],
+        &[0xDD, 0xCB, 0x00],
+        &[0xFD, 0xCB, 0x00],
+    ];
+
+    // BIT b, (IX+d) repeats on every opcode of its row, but is documented
+    // only for the one that would otherwise address (HL)
+    let opcodes = (0x00..=0xFF)
+        .filter(|opcode| opcode & 0b111 == 6)
+        .chain((0x00..=0xFF).filter(|opcode| opcode & 0b111 != 6));
+
+    let mut encodings = HashMap::new();
+    for page in pages {
+        for opcode in opcodes.clone() {
+            let bytes = [page, &[opcode, 0x00, 0x00]].concat();
+            let (instruction, _) = decode(&bytes);
+            encodings
+                .entry(instruction.operation)
+                .or_insert((instruction.prefix, instruction.opcode));
+        }
+    }
+
+    encodings
+}
+
+/// The operation with the values of its operands, but not those that are part
+/// of the opcode such as the bit of BIT, set to 0.
+fn without_values(operation: Operation) -> Operation {
+    let clear = |operand| match operand {
+        Operand::Immediate(_) => Operand::Immediate(0),
+        Operand::ImmediateExtended(_) => Operand::ImmediateExtended(0),
+        Operand::Indexed(register_pair, _) => Operand::Indexed(register_pair, 0),
+        Operand::Extended(_) => Operand::Extended(0),
+        Operand::Relative(_) => Operand::Relative(0),
+        Operand::Port(_) => Operand::Port(0),
+        _ => operand,
+    };
+
+    match operation {
+        Operation::Ld(destination, source) => Operation::Ld(clear(destination), clear(source)),
+        Operation::Add(destination, source) => Operation::Add(destination, clear(source)),
+        Operation::Adc(destination, source) => Operation::Adc(destination, clear(source)),
+        Operation::Sbc(destination, source) => Operation::Sbc(destination, clear(source)),
+        Operation::Sub(operand) => Operation::Sub(clear(operand)),
+        Operation::And(operand) => Operation::And(clear(operand)),
+        Operation::Or(operand) => Operation::Or(clear(operand)),
+        Operation::Xor(operand) => Operation::Xor(clear(operand)),
+        Operation::Cp(operand) => Operation::Cp(clear(operand)),
+        Operation::Inc(operand) => Operation::Inc(clear(operand)),
+        Operation::Dec(operand) => Operation::Dec(clear(operand)),
+        Operation::Shift(shift, operand) => Operation::Shift(shift, clear(operand)),
+        Operation::LdShift(register, shift, operand) => {
+            Operation::LdShift(register, shift, clear(operand))
+        }
+        Operation::Bit(bit, operand) => Operation::Bit(bit, clear(operand)),
+        Operation::Set(bit, operand) => Operation::Set(bit, clear(operand)),
+        Operation::LdSet(register, bit, operand) => Operation::LdSet(register, bit, clear(operand)),
+        Operation::Res(bit, operand) => Operation::Res(bit, clear(operand)),
+        Operation::LdRes(register, bit, operand) => Operation::LdRes(register, bit, clear(operand)),
+        Operation::Jp(condition, target) => Operation::Jp(condition, clear(target)),
+        Operation::Jr(condition, target) => Operation::Jr(condition, clear(target)),
+        Operation::Djnz(target) => Operation::Djnz(clear(target)),
+        Operation::Call(condition, target) => Operation::Call(condition, clear(target)),
+        Operation::In(register, port) => Operation::In(register, clear(port)),
+        Operation::Out(port, source) => Operation::Out(clear(port), source),
+        _ => operation,
+    }
+}
+
+/// The operands of an operation in the order their values are encoded.
+fn operands(operation: Operation) -> Vec<Operand> {
+    match operation {
+        Operation::Ld(first, second)
+        | Operation::Add(first, second)
+        | Operation::Adc(first, second)
+        | Operation::Sbc(first, second) => vec![first, second],
+        // The 0 of OUT (C), 0 is part of the opcode
+        Operation::Out(port, _) => vec![port],
+        Operation::Sub(operand)
+        | Operation::And(operand)
+        | Operation::Or(operand)
+        | Operation::Xor(operand)
+        | Operation::Cp(operand)
+        | Operation::Inc(operand)
+        | Operation::Dec(operand)
+        | Operation::Shift(_, operand)
+        | Operation::LdShift(_, _, operand)
+        | Operation::Bit(_, operand)
+        | Operation::Set(_, operand)
+        | Operation::LdSet(_, _, operand)
+        | Operation::Res(_, operand)
+        | Operation::LdRes(_, _, operand)
+        | Operation::Jp(_, operand)
+        | Operation::Jr(_, operand)
+        | Operation::Djnz(operand)
+        | Operation::Call(_, operand)
+        | Operation::In(_, operand) => vec![operand],
+        _ => vec![],
+    }
+}
+
+/// Encodes an operation, or returns None if there is no opcode for it.
+fn encode(encodings: &Encodings, operation: Operation) -> Option<Vec<u8>> {
+    let (prefix, opcode) = *encodings.get(&without_values(operation))?;
+
+    let displacement = operands(operation)
+        .into_iter()
+        .find_map(|operand| match operand {
+            Operand::Indexed(_, displacement) => Some(displacement as u8),
+            _ => None,
+        });
+    let mut bytes = match prefix {
+        Prefix::None => vec![opcode],
+        Prefix::Cb => vec![0xCB, opcode],
+        Prefix::Ed => vec![0xED, opcode],
+        Prefix::Dd => vec![0xDD, opcode],
+        Prefix::Fd => vec![0xFD, opcode],
+        // The displacement comes before the opcode
+        Prefix::DdCb => return Some(vec![0xDD, 0xCB, displacement?, opcode]),
+        Prefix::FdCb => return Some(vec![0xFD, 0xCB, displacement?, opcode]),
+    };
+
+    bytes.extend(displacement);
+    for operand in operands(operation) {
+        match operand {
+            Operand::Immediate(data) | Operand::Port(data) => bytes.push(data),
+            Operand::Relative(displacement) => bytes.push(displacement as u8),
+            Operand::ImmediateExtended(data) | Operand::Extended(data) => {
+                bytes.extend(data.to_le_bytes())
+            }
+            _ => {}
+        }
+    }
+
+    Some(bytes)
+}
+
+/// An operand as written in the source, before it is known which addressing
+/// mode the instruction uses.
+#[derive(Clone, Copy, Debug)]
+enum Argument {
+    Register(Register),
+    RegisterPair(RegisterPair),
+    RegisterIndirect(RegisterPair),
+    Indexed(RegisterPair, i64),
+    /// (n) or (nn)
+    Memory(i64),
+    PortC,
+    Condition(Condition),
+    /// n, nn or a jump target
+    Value(i64),
+}
+
+struct Assembler {
+    encodings: &'static Encodings,
+    pass: Pass,
+    symbols: HashMap<String, u16>,
+    /// The last global label, that local labels belong to.
+    scope: String,
+    address: u32,
+    origin: Option<u16>,
+    bytes: Vec<u8>,
+}
+
+impl Assembler {
+    fn assemble_line(&mut self, line: &str) -> Result<(), String> {
+        let mut line = strip_comment(line).trim();
+
+        if let Some((label, rest)) = split_label(line) {
+            line = rest.trim();
+            let (word, value) = split_first_word(line);
+            if word.eq_ignore_ascii_case("EQU") {
+                return self.define_equ(label, value);
+            }
+            let address = self.address_u16()?;
+            self.define(label, address)?;
+        }
+
+        if line.is_empty() {
+            return Ok(());
+        }
+
+        let (mnemonic, rest) = split_first_word(line);
+        let (second_word, value) = split_first_word(rest);
+        if second_word.eq_ignore_ascii_case("EQU") {
+            return self.define_equ(mnemonic, value);
+        }
+
+        let mnemonic = mnemonic.to_ascii_uppercase();
+        let arguments = split_arguments(rest);
+        match mnemonic.as_str() {
+            "ORG" => self.org(&arguments),
+            "DB" | "DEFB" => self.db(&arguments),
+            "DW" | "DEFW" => self.dw(&arguments),
+            "DS" | "DEFS" => self.ds(&arguments),
+            _ => self.instruction(&mnemonic, &arguments, line),
+        }
+    }
+
+    fn address_u16(&self) -> Result<u16, String> {
+        u16::try_from(self.address).map_err(|_| "the program runs past FFFFh".to_string())
+    }
+
+    fn define(&mut self, label: &str, value: u16) -> Result<(), String> {
+        let name = self.symbol_name(label);
+        if !label.starts_with('.') {
+            self.scope = name.clone();
+        }
+
+        if self.pass == Pass::First && self.symbols.contains_key(&name) {
+            return Err(format!("{label} is already defined"));
+        }
+        self.symbols.insert(name, value);
+
+        Ok(())
+    }
+
+    fn define_equ(&mut self, name: &str, value: &str) -> Result<(), String> {
+        let value = self.evaluate(value)?;
+        let name = self.symbol_name(name);
+        if self.pass == Pass::First && self.symbols.contains_key(&name) {
+            return Err(format!("{name} is already defined"));
+        }
+        self.symbols.insert(name, value as u16);
+
+        Ok(())
+    }
+
+    /// The full name of a symbol, with local symbols prefixed by their scope.
+    fn symbol_name(&self, name: &str) -> String {
+        let name = name.to_ascii_uppercase();
+        if name.starts_with('.') {
+            format!("{}{}", self.scope, name)
+        } else {
+            name
+        }
+    }
+
+    fn emit(&mut self, bytes: &[u8]) -> Result<(), String> {
+        let address = self.address_u16()?;
+        let origin = *self.origin.get_or_insert(address);
+        let start = (self.address - origin as u32) as usize;
+        if self.bytes.len() < start + bytes.len() {
+            self.bytes.resize(start + bytes.len(), 0x00);
+        }
+        self.bytes[start..start + bytes.len()].copy_from_slice(bytes);
+        self.address += bytes.len() as u32;
+
+        if self.address > 0x10000 {
+            return Err("the program runs past FFFFh".to_string());
+        }
+
+        Ok(())
+    }
+
+    fn org(&mut self, arguments: &[&str]) -> Result<(), String> {
+        let [address] = arguments else {
+            return Err("ORG takes an address".to_string());
+        };
+        let address = self.evaluate_now(address)?;
+        if !(0..=0xFFFF).contains(&address) {
+            return Err(format!("ORG address {address} is out of range"));
+        }
+        if self.origin.is_some() && (address as u32) < self.address {
+            return Err("ORG cannot move back over code already assembled".to_string());
+        }
+
+        self.address = address as u32;
+        Ok(())
+    }
+
+    fn db(&mut self, arguments: &[&str]) -> Result<(), String> {
+        for argument in arguments {
+            if let Some(text) = argument
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"'))
+            {
+                self.emit(text.as_bytes())?;
+            } else {
+                let value = self.evaluate(argument)?;
+                let data = self.byte(value)?;
+                self.emit(&[data])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn dw(&mut self, arguments: &[&str]) -> Result<(), String> {
+        for argument in arguments {
+            let value = self.evaluate(argument)?;
+            let data = self.word(value)?;
+            self.emit(&data.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    fn ds(&mut self, arguments: &[&str]) -> Result<(), String> {
+        let (count, fill) = match arguments {
+            [count] => (count, 0x00),
+            [count, fill] => {
+                let fill = self.evaluate(fill)?;
+                (count, self.byte(fill)?)
+            }
+            _ => return Err("DS takes a count and an optional fill byte".to_string()),
+        };
+        let count = self.evaluate_now(count)?;
+        if !(0..=0x10000).contains(&count) {
+            return Err(format!("DS count {count} is out of range"));
+        }
+
+        self.emit(&vec![fill; count as usize])
+    }
+
+    fn instruction(
+        &mut self,
+        mnemonic: &str,
+        arguments: &[&str],
+        line: &str,
+    ) -> Result<(), String> {
+        let operations = self.operations(mnemonic, arguments)?;
+        let invalid = || format!("invalid instruction {line}");
+
+        for operation in operations {
+            if let Some(bytes) = encode(self.encodings, operation) {
+                // The bytes have to decode back to what was written
+                if decode(&bytes).0.operation != operation {
+                    return Err(invalid());
+                }
+                return self.emit(&bytes);
+            }
+        }
+
+        Err(invalid())
+    }
+
+    /// The operations the mnemonic and arguments could mean, trying each
+    /// addressing mode an argument could be written for.
+    fn operations(&self, mnemonic: &str, arguments: &[&str]) -> Result<Vec<Operation>, String> {
+        let arguments: Vec<Argument> = match mnemonic {
+            // The undocumented LD r, RLC (IX+d) and LD r, SET b, (IX+d)
+            "LD" if arguments.len() > 1 => {
+                let (word, rest) = split_first_word(arguments[1]);
+                let word = word.to_ascii_uppercase();
+                if let Some(shift) = shift_from_mnemonic(&word) {
+                    let register = self.register_argument(arguments[0])?;
+                    let operand = self.parse_argument(rest)?;
+                    return Ok(self
+                        .candidates(operand)
+                        .into_iter()
+                        .map(|operand| Operation::LdShift(register, shift, operand))
+                        .collect());
+                }
+                if (word == "SET" || word == "RES") && arguments.len() == 3 {
+                    let register = self.register_argument(arguments[0])?;
+                    let bit = self.bit(rest)?;
+                    let operand = self.parse_argument(arguments[2])?;
+                    return Ok(self
+                        .candidates(operand)
+                        .into_iter()
+                        .map(|operand| {
+                            if word == "SET" {
+                                Operation::LdSet(register, bit, operand)
+                            } else {
+                                Operation::LdRes(register, bit, operand)
+                            }
+                        })
+                        .collect());
+                }
+                self.parse_arguments(arguments)?
+            }
+            // IN F, (C) is another name for the undocumented IN (C), which
+            // only sets the flags
+            "IN" if arguments.len() == 2 && arguments[0].trim().eq_ignore_ascii_case("F") => {
+                match self.parse_argument(arguments[1])? {
+                    Argument::PortC => vec![Argument::PortC],
+                    _ => return Err("IN F only takes (C)".to_string()),
+                }
+            }
+            _ => self.parse_arguments(arguments)?,
+        };
+
+        let a = Argument::Register(Register::A);
+        let operations = match (mnemonic, arguments.as_slice()) {
+            ("NOP", []) => vec![Operation::Nop],
+            ("HALT", []) => vec![Operation::Halt],
+            ("DI", []) => vec![Operation::Di],
+            ("EI", []) => vec![Operation::Ei],
+            ("EXX", []) => vec![Operation::Exx],
+            ("LDI", []) => vec![Operation::Ldi],
+            ("LDIR", []) => vec![Operation::Ldir],
+            ("LDD", []) => vec![Operation::Ldd],
+            ("LDDR", []) => vec![Operation::Lddr],
+            ("CPI", []) => vec![Operation::Cpi],
+            ("CPIR", []) => vec![Operation::Cpir],
+            ("CPD", []) => vec![Operation::Cpd],
+            ("CPDR", []) => vec![Operation::Cpdr],
+            ("DAA", []) => vec![Operation::Daa],
+            ("CPL", []) => vec![Operation::Cpl],
+            ("NEG", []) => vec![Operation::Neg],
+            ("CCF", []) => vec![Operation::Ccf],
+            ("SCF", []) => vec![Operation::Scf],
+            ("RLCA", []) => vec![Operation::Rlca],
+            ("RLA", []) => vec![Operation::Rla],
+            ("RRCA", []) => vec![Operation::Rrca],
+            ("RRA", []) => vec![Operation::Rra],
+            ("RLD", []) => vec![Operation::Rld],
+            ("RRD", []) => vec![Operation::Rrd],
+            ("RETI", []) => vec![Operation::Reti],
+            ("RETN", []) => vec![Operation::Retn],
+            ("INI", []) => vec![Operation::Ini],
+            ("INIR", []) => vec![Operation::Inir],
+            ("IND", []) => vec![Operation::Ind],
+            ("INDR", []) => vec![Operation::Indr],
+            ("OUTI", []) => vec![Operation::Outi],
+            ("OTIR", []) => vec![Operation::Otir],
+            ("OUTD", []) => vec![Operation::Outd],
+            ("OTDR", []) => vec![Operation::Otdr],
+            ("IM", [Argument::Value(mode @ 0..=2)]) => vec![Operation::Im(*mode as u8)],
+            ("RST", [Argument::Value(address)])
+                if address % 8 == 0 && (0..=0x38).contains(address) =>
+            {
+                vec![Operation::Rst(*address as u8)]
+            }
+            ("LD", [destination, source]) => self.pairs(*destination, *source, Operation::Ld),
+            ("EX", [first, second]) => self.pairs(*first, *second, Operation::Ex),
+            ("ADD", [source]) => self.pairs(a, *source, Operation::Add),
+            ("ADD", [destination, source]) => self.pairs(*destination, *source, Operation::Add),
+            ("ADC", [source]) => self.pairs(a, *source, Operation::Adc),
+            ("ADC", [destination, source]) => self.pairs(*destination, *source, Operation::Adc),
+            ("SBC", [source]) => self.pairs(a, *source, Operation::Sbc),
+            ("SBC", [destination, source]) => self.pairs(*destination, *source, Operation::Sbc),
+            ("SUB" | "AND" | "OR" | "XOR" | "CP", [Argument::Register(Register::A), source])
+            | ("SUB" | "AND" | "OR" | "XOR" | "CP", [source]) => {
+                let operation = match mnemonic {
+                    "SUB" => Operation::Sub,
+                    "AND" => Operation::And,
+                    "OR" => Operation::Or,
+                    "XOR" => Operation::Xor,
+                    _ => Operation::Cp,
+                };
+                self.singles(*source, operation)
+            }
+            ("PUSH", [operand]) => self.singles(*operand, Operation::Push),
+            ("POP", [operand]) => self.singles(*operand, Operation::Pop),
+            ("INC", [operand]) => self.singles(*operand, Operation::Inc),
+            ("DEC", [operand]) => self.singles(*operand, Operation::Dec),
+            ("BIT" | "SET" | "RES", [Argument::Value(bit @ 0..=7), operand]) => {
+                let bit = *bit as u8;
+                self.candidates(*operand)
+                    .into_iter()
+                    .map(|operand| match mnemonic {
+                        "BIT" => Operation::Bit(bit, operand),
+                        "SET" => Operation::Set(bit, operand),
+                        _ => Operation::Res(bit, operand),
+                    })
+                    .collect()
+            }
+            ("JP" | "CALL", [target]) => self.jumps(mnemonic, None, *target),
+            ("JP" | "CALL", [condition, target]) => {
+                self.jumps(mnemonic, Some(as_condition(*condition)?), *target)
+            }
+            ("JR", [Argument::Value(target)]) => vec![Operation::Jr(None, self.relative(*target)?)],
+            ("JR", [condition, Argument::Value(target)]) => {
+                let condition = as_condition(*condition)?;
+                if !matches!(
+                    condition,
+                    Condition::Nz | Condition::Z | Condition::Nc | Condition::C
+                ) {
+                    return Err("JR only takes NZ, Z, NC or C".to_string());
+                }
+                vec![Operation::Jr(Some(condition), self.relative(*target)?)]
+            }
+            ("DJNZ", [Argument::Value(target)]) => vec![Operation::Djnz(self.relative(*target)?)],
+            ("RET", []) => vec![Operation::Ret(None)],
+            ("RET", [condition]) => vec![Operation::Ret(Some(as_condition(*condition)?))],
+            ("IN", [Argument::PortC]) => vec![Operation::In(None, Operand::PortC)],
+            ("IN", [Argument::Register(register), port]) => self
+                .candidates(*port)
+                .into_iter()
+                .map(|port| Operation::In(Some(*register), port))
+                .collect(),
+            ("OUT", [port, source]) => self.pairs(*port, *source, Operation::Out),
+            _ => {
+                if let Some(shift) = shift_from_mnemonic(mnemonic) {
+                    if let [operand] = arguments.as_slice() {
+                        return Ok(self
+                            .candidates(*operand)
+                            .into_iter()
+                            .map(|operand| Operation::Shift(shift, operand))
+                            .collect());
+                    }
+                }
+                return Err(format!("unknown instruction {mnemonic}"));
+            }
+        };
+
+        Ok(operations)
+    }
+
+    fn singles(&self, operand: Argument, operation: fn(Operand) -> Operation) -> Vec<Operation> {
+        self.candidates(operand)
+            .into_iter()
+            .map(operation)
+            .collect()
+    }
+
+    fn pairs(
+        &self,
+        first: Argument,
+        second: Argument,
+        operation: fn(Operand, Operand) -> Operation,
+    ) -> Vec<Operation> {
+        let mut operations = Vec::new();
+        for first in self.candidates(first) {
+            for second in self.candidates(second) {
+                operations.push(operation(first, second));
+            }
+        }
+
+        operations
+    }
+
+    fn jumps(
+        &self,
+        mnemonic: &str,
+        condition: Option<Condition>,
+        target: Argument,
+    ) -> Vec<Operation> {
+        self.candidates(target)
+            .into_iter()
+            .map(|target| {
+                if mnemonic == "JP" {
+                    Operation::Jp(condition, target)
+                } else {
+                    Operation::Call(condition, target)
+                }
+            })
+            .collect()
+    }
+
+    /// The operands an argument could be, depending on the instruction.
+    fn candidates(&self, argument: Argument) -> Vec<Operand> {
+        let fits_byte = |value: i64| self.pass == Pass::First || (-0x80..=0xFF).contains(&value);
+        let fits_word =
+            |value: i64| self.pass == Pass::First || (-0x8000..=0xFFFF).contains(&value);
+
+        let mut candidates = Vec::new();
+        match argument {
+            Argument::Register(register) => candidates.push(Operand::Register(register)),
+            Argument::RegisterPair(register_pair) => {
+                candidates.push(Operand::RegisterPair(register_pair))
+            }
+            Argument::RegisterIndirect(register_pair) => {
+                candidates.push(Operand::RegisterIndirect(register_pair));
+                // (IX) is (IX+0) except for JP (IX)
+                if matches!(register_pair, RegisterPair::Ix | RegisterPair::Iy) {
+                    candidates.push(Operand::Indexed(register_pair, 0));
+                }
+            }
+            Argument::Indexed(register_pair, displacement) => {
+                if self.pass == Pass::First || (-0x80..=0x7F).contains(&displacement) {
+                    candidates.push(Operand::Indexed(register_pair, displacement as i8));
+                }
+            }
+            Argument::Memory(address) => {
+                if fits_word(address) {
+                    candidates.push(Operand::Extended(address as u16));
+                }
+                if fits_byte(address) {
+                    candidates.push(Operand::Port(address as u8));
+                }
+            }
+            Argument::PortC => candidates.push(Operand::PortC),
+            Argument::Condition(_) => {}
+            Argument::Value(value) => {
+                if fits_byte(value) {
+                    candidates.push(Operand::Immediate(value as u8));
+                }
+                if fits_word(value) {
+                    candidates.push(Operand::ImmediateExtended(value as u16));
+                }
+            }
+        }
+
+        candidates
+    }
+
+    /// The displacement of a relative jump to `target` from this line.
+    fn relative(&self, target: i64) -> Result<Operand, String> {
+        let displacement = target - (self.address as i64 + 2);
+        if self.pass == Pass::Final && !(-0x80..=0x7F).contains(&displacement) {
+            return Err(format!("relative jump to {target:04X}h is out of range"));
+        }
+
+        Ok(Operand::Relative(displacement as i8))
+    }
+
+    fn bit(&self, text: &str) -> Result<u8, String> {
+        match self.evaluate(text)? {
+            bit @ 0..=7 => Ok(bit as u8),
+            bit => Err(format!("bit {bit} is out of range")),
+        }
+    }
+
+    fn register_argument(&self, text: &str) -> Result<Register, String> {
+        match self.parse_argument(text)? {
+            Argument::Register(register) => Ok(register),
+            _ => Err(format!("{text} is not a register")),
+        }
+    }
+
+    fn parse_arguments(&self, arguments: &[&str]) -> Result<Vec<Argument>, String> {
+        arguments
+            .iter()
+            .map(|argument| self.parse_argument(argument))
+            .collect()
+    }
+
+    fn parse_argument(&self, text: &str) -> Result<Argument, String> {
+        let text = text.trim();
+        let upper = text.to_ascii_uppercase();
+
+        if let Some(register) = register_from_name(&upper) {
+            return Ok(Argument::Register(register));
+        }
+        if let Some(register_pair) = register_pair_from_name(&upper) {
+            return Ok(Argument::RegisterPair(register_pair));
+        }
+        if let Some(condition) = condition_from_name(&upper) {
+            return Ok(Argument::Condition(condition));
+        }
+
+        if let Some(inner) = enclosed_in_parentheses(text) {
+            let inner = inner.trim();
+            let upper_inner = inner.to_ascii_uppercase();
+            if upper_inner == "C" {
+                return Ok(Argument::PortC);
+            }
+            if let Some(register_pair) = register_pair_from_name(&upper_inner) {
+                return Ok(Argument::RegisterIndirect(register_pair));
+            }
+            for (name, register_pair) in [("IX", RegisterPair::Ix), ("IY", RegisterPair::Iy)] {
+                if let Some(displacement) = upper_inner.strip_prefix(name) {
+                    let displacement = displacement.trim_start();
+                    if displacement.starts_with(['+', '-']) {
+                        let offset = inner.len() - displacement.len();
+                        return Ok(Argument::Indexed(
+                            register_pair,
+                            self.evaluate(&inner[offset..])?,
+                        ));
+                    }
+                }
+            }
+            return Ok(Argument::Memory(self.evaluate(inner)?));
+        }
+
+        Ok(Argument::Value(self.evaluate(text)?))
+    }
+
+    /// Evaluates an expression, treating symbols that are not defined yet as
+    /// 0 on the first pass.
+    fn evaluate(&self, text: &str) -> Result<i64, String> {
+        let parser = &mut ExpressionParser {
+            assembler: self,
+            text: text.as_bytes(),
+            position: 0,
+            require_defined: self.pass == Pass::Final,
+        };
+        parser.parse()
+    }
+
+    /// Evaluates an expression that the layout of the program depends on, so
+    /// its symbols have to be defined before it.
+    fn evaluate_now(&self, text: &str) -> Result<i64, String> {
+        let parser = &mut ExpressionParser {
+            assembler: self,
+            text: text.as_bytes(),
+            position: 0,
+            require_defined: true,
+        };
+        parser.parse()
+    }
+
+    fn byte(&self, value: i64) -> Result<u8, String> {
+        if self.pass == Pass::Final && !(-0x80..=0xFF).contains(&value) {
+            return Err(format!("{value} does not fit in a byte"));
+        }
+
+        Ok(value as u8)
+    }
+
+    fn word(&self, value: i64) -> Result<u16, String> {
+        if self.pass == Pass::Final && !(-0x8000..=0xFFFF).contains(&value) {
+            return Err(format!("{value} does not fit in a word"));
+        }
+
+        Ok(value as u16)
+    }
+}
+
+/// A recursive descent parser that evaluates expressions as it reads them.
+struct ExpressionParser<'a> {
+    assembler: &'a Assembler,
+    text: &'a [u8],
+    position: usize,
+    require_defined: bool,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn parse(&mut self) -> Result<i64, String> {
+        let value = self.binary(0)?;
+        self.skip_whitespace();
+        if self.position < self.text.len() {
+            return Err(format!(
+                "unexpected {} in expression",
+                self.text[self.position] as char
+            ));
+        }
+
+        Ok(value)
+    }
+
+    /// Parses binary operators from the given level of precedence upwards.
+    fn binary(&mut self, level: usize) -> Result<i64, String> {
+        const LEVELS: [&[&str]; 6] = [
+            &["|"],
+            &["^"],
+            &["&"],
+            &["<<", ">>"],
+            &["+", "-"],
+            &["*", "/", "%"],
+        ];
+
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut value = self.binary(level + 1)?;
+        loop {
+            self.skip_whitespace();
+            let Some(operator) = LEVELS[level]
+                .iter()
+                .find(|operator| self.text[self.position..].starts_with(operator.as_bytes()))
+            else {
+                return Ok(value);
+            };
+            self.position += operator.len();
+            let right = self.binary(level + 1)?;
+            value = match *operator {
+                "|" => value | right,
+                "^" => value ^ right,
+                "&" => value & right,
+                "<<" => value.checked_shl(right as u32).unwrap_or(0),
+                ">>" => value.checked_shr(right as u32).unwrap_or(0),
+                "+" => value.wrapping_add(right),
+                "-" => value.wrapping_sub(right),
+                "*" => value.wrapping_mul(right),
+                "/" => value.checked_div(right).ok_or("division by zero")?,
+                _ => value.checked_rem(right).ok_or("division by zero")?,
+            };
+        }
+    }
+
+    fn unary(&mut self) -> Result<i64, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'-') => {
+                self.position += 1;
+                Ok(self.unary()?.wrapping_neg())
+            }
+            Some(b'+') => {
+                self.position += 1;
+                self.unary()
+            }
+            Some(b'~') => {
+                self.position += 1;
+                Ok(!self.unary()?)
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<i64, String> {
+        self.skip_whitespace();
+        let start = self.position;
+        match self.peek() {
+            Some(b'(') => {
+                self.position += 1;
+                let value = self.binary(0)?;
+                self.skip_whitespace();
+                if self.peek() != Some(b')') {
+                    return Err("missing ) in expression".to_string());
+                }
+                self.position += 1;
+                Ok(value)
+            }
+            Some(b'\'') => match self.text.get(start..start + 3) {
+                Some([b'\'', character, b'\'']) => {
+                    self.position += 3;
+                    Ok(*character as i64)
+                }
+                _ => Err("a character has to be written as 'c'".to_string()),
+            },
+            Some(b'$') => {
+                self.position += 1;
+                let digits = self.take_while(|byte| byte.is_ascii_hexdigit());
+                if digits.is_empty() {
+                    Ok(self.assembler.address as i64)
+                } else {
+                    parse_number(digits, 16)
+                }
+            }
+            Some(b'%') => {
+                self.position += 1;
+                parse_number(self.take_while(|byte| byte == b'0' || byte == b'1'), 2)
+            }
+            Some(byte) if byte.is_ascii_digit() => {
+                let number = self.take_while(|byte| byte.is_ascii_alphanumeric());
+                parse_literal(number)
+            }
+            Some(byte) if is_symbol_start(byte) => {
+                let name = self.take_while(is_symbol_byte);
+                self.symbol(name)
+            }
+            Some(byte) => Err(format!("unexpected {} in expression", byte as char)),
+            None => Err("missing value in expression".to_string()),
+        }
+    }
+
+    fn symbol(&self, name: &str) -> Result<i64, String> {
+        let full_name = self.assembler.symbol_name(name);
+        match self.assembler.symbols.get(&full_name) {
+            Some(value) => Ok(*value as i64),
+            None if self.require_defined => Err(format!("{name} is not defined")),
+            None => Ok(0),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(|byte| byte.is_ascii_whitespace());
+    }
+
+    fn take_while(&mut self, predicate: fn(u8) -> bool) -> &'a str {
+        let start = self.position;
+        while self.peek().is_some_and(predicate) {
+            self.position += 1;
+        }
+        let text: &'a [u8] = self.text;
+        std::str::from_utf8(&text[start..self.position]).unwrap_or_default()
+    }
+}
+
+fn parse_number(digits: &str, radix: u32) -> Result<i64, String> {
+    i64::from_str_radix(digits, radix).map_err(|_| format!("invalid number {digits}"))
+}
+
+/// Parses a number that starts with a digit, such as `255`, `0FFh`, `0xFF`,
+/// `0b1010` or `1010b`.
+fn parse_literal(number: &str) -> Result<i64, String> {
+    let lower = number.to_ascii_lowercase();
+    if let Some(digits) = lower.strip_prefix("0x") {
+        parse_number(digits, 16)
+    } else if let Some(digits) = lower.strip_suffix('h') {
+        parse_number(digits, 16)
+    } else if let Some(digits) = lower.strip_prefix("0b") {
+        parse_number(digits, 2)
+    } else if let Some(digits) = lower
+        .strip_suffix('b')
+        .filter(|digits| digits.bytes().all(|byte| byte == b'0' || byte == b'1'))
+    {
+        parse_number(digits, 2)
+    } else {
+        parse_number(&lower, 10)
+    }
+}
+
+fn is_symbol_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'.'
+}
+
+fn is_symbol_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'.'
+}
+
+/// Removes a comment, leaving semicolons inside strings and characters.
+fn strip_comment(line: &str) -> &str {
+    let bytes = line.as_bytes();
+    let mut in_string = false;
+    let mut position = 0;
+    while position < bytes.len() {
+        match bytes[position] {
+            b'"' => in_string = !in_string,
+            // The quote of AF' is not the start of a character
+            b'\'' if !in_string && bytes.get(position + 2) == Some(&b'\'') => position += 2,
+            b';' if !in_string => return &line[..position],
+            _ => {}
+        }
+        position += 1;
+    }
+
+    line
+}
+
+/// Splits off a label, written as a symbol followed by a colon, or as a symbol
+/// followed by EQU.
+fn split_label(line: &str) -> Option<(&str, &str)> {
+    let end = line
+        .bytes()
+        .position(|byte| !is_symbol_byte(byte))
+        .unwrap_or(line.len());
+    let (label, rest) = line.split_at(end);
+    if label.is_empty() || !is_symbol_start(label.as_bytes()[0]) {
+        return None;
+    }
+
+    rest.strip_prefix(':').map(|rest| (label, rest))
+}
+
+fn split_first_word(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    match text.find(char::is_whitespace) {
+        Some(end) => (&text[..end], text[end..].trim()),
+        None => (text, ""),
+    }
+}
+
+/// Splits the arguments at commas that are not inside parentheses, strings or
+/// characters.
+fn split_arguments(text: &str) -> Vec<&str> {
+    if text.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let bytes = text.as_bytes();
+    let mut arguments = Vec::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut start = 0;
+    let mut position = 0;
+    while position < bytes.len() {
+        match bytes[position] {
+            b'"' => in_string = !in_string,
+            b'\'' if !in_string && bytes.get(position + 2) == Some(&b'\'') => position += 2,
+            b'(' if !in_string => depth += 1,
+            b')' if !in_string => depth -= 1,
+            b',' if !in_string && depth == 0 => {
+                arguments.push(text[start..position].trim());
+                start = position + 1;
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+    arguments.push(text[start..].trim());
+
+    arguments
+}
+
+/// The text inside the parentheses if the whole of `text` is enclosed in one
+/// pair of them.
+fn enclosed_in_parentheses(text: &str) -> Option<&str> {
+    let inner = text.strip_prefix('(')?.strip_suffix(')')?;
+    let mut depth = 0;
+    for byte in inner.bytes() {
+        match byte {
+            b'(' => depth += 1,
+            b')' if depth == 0 => return None,
+            b')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    Some(inner)
+}
+
+fn as_condition(argument: Argument) -> Result<Condition, String> {
+    match argument {
+        Argument::Condition(condition) => Ok(condition),
+        // C is a register everywhere but in front of a jump target
+        Argument::Register(Register::C) => Ok(Condition::C),
+        _ => Err("expected a condition".to_string()),
+    }
+}
+
+fn register_from_name(name: &str) -> Option<Register> {
+    Some(match name {
+        "A" => Register::A,
+        "B" => Register::B,
+        "C" => Register::C,
+        "D" => Register::D,
+        "E" => Register::E,
+        "H" => Register::H,
+        "L" => Register::L,
+        "I" => Register::I,
+        "R" => Register::R,
+        "IXH" => Register::Ixh,
+        "IXL" => Register::Ixl,
+        "IYH" => Register::Iyh,
+        "IYL" => Register::Iyl,
+        _ => return None,
+    })
+}
+
+fn register_pair_from_name(name: &str) -> Option<RegisterPair> {
+    Some(match name {
+        "AF" => RegisterPair::Af,
+        "AF'" => RegisterPair::AfPrime,
+        "BC" => RegisterPair::Bc,
+        "DE" => RegisterPair::De,
+        "HL" => RegisterPair::Hl,
+        "SP" => RegisterPair::Sp,
+        "IX" => RegisterPair::Ix,
+        "IY" => RegisterPair::Iy,
+        _ => return None,
+    })
+}
+
+fn condition_from_name(name: &str) -> Option<Condition> {
+    Some(match name {
+        "NZ" => Condition::Nz,
+        "Z" => Condition::Z,
+        "NC" => Condition::Nc,
+        "PO" => Condition::Po,
+        "PE" => Condition::Pe,
+        "P" => Condition::P,
+        "M" => Condition::M,
+        _ => return None,
+    })
+}
+
+fn shift_from_mnemonic(mnemonic: &str) -> Option<Shift> {
+    Some(match mnemonic {
+        "RLC" => Shift::Rlc,
+        "RRC" => Shift::Rrc,
+        "RL" => Shift::Rl,
+        "RR" => Shift::Rr,
+        "SLA" => Shift::Sla,
+        "SRA" => Shift::Sra,
+        "SLL" => Shift::Sll,
+        "SRL" => Shift::Srl,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        tests::{Ports, Ram},
+        DisassembledInstruction, Z80,
+    };
+
+    use super::*;
+
+    fn assemble_bytes(source: &str) -> Vec<u8> {
+        assemble(source).unwrap().bytes
+    }
+
+    #[test]
+    fn test_assemble_instructions() {
+        let scenarios = [
+            ("NOP", vec![0x00]),
+            ("ld h, 0DDh", vec![0x26, 0xDD]),
+            ("LD BC, 1234h", vec![0x01, 0x34, 0x12]),
+            ("LD A, (BC)", vec![0x0A]),
+            ("LD HL, (5C5Dh)", vec![0x2A, 0x5D, 0x5C]),
+            ("LD (4000h), BC", vec![0xED, 0x43, 0x00, 0x40]),
+            ("LD (IX+5), 22h", vec![0xDD, 0x36, 0x05, 0x22]),
+            ("LD H, (IX-2)", vec![0xDD, 0x66, 0xFE]),
+            ("LD A, (IY)", vec![0xFD, 0x7E, 0x00]),
+            ("LD IXH, B", vec![0xDD, 0x60]),
+            ("LD A, I", vec![0xED, 0x57]),
+            ("ADD A, B", vec![0x80]),
+            ("ADD B", vec![0x80]),
+            ("ADD IX, SP", vec![0xDD, 0x39]),
+            ("SBC HL, DE", vec![0xED, 0x52]),
+            ("SUB 10", vec![0xD6, 0x0A]),
+            ("CP -1", vec![0xFE, 0xFF]),
+            ("EX AF, AF'", vec![0x08]),
+            ("EX (SP), IY", vec![0xFD, 0xE3]),
+            ("PUSH AF", vec![0xF5]),
+            ("JP (HL)", vec![0xE9]),
+            ("JP PE, 8000h", vec![0xEA, 0x00, 0x80]),
+            ("CALL C, 0", vec![0xDC, 0x00, 0x00]),
+            ("RET NC", vec![0xD0]),
+            ("RST 38h", vec![0xFF]),
+            ("JR $", vec![0x18, 0xFE]),
+            ("DJNZ $+2", vec![0x10, 0x00]),
+            ("IN A, (0FEh)", vec![0xDB, 0xFE]),
+            ("IN E, (C)", vec![0xED, 0x58]),
+            ("IN (C)", vec![0xED, 0x70]),
+            ("IN F, (C)", vec![0xED, 0x70]),
+            ("in f,(c)", vec![0xED, 0x70]),
+            ("OUT (C), 0", vec![0xED, 0x71]),
+            ("OUT (254), A", vec![0xD3, 0xFE]),
+            ("IM 2", vec![0xED, 0x5E]),
+            ("SLL (HL)", vec![0xCB, 0x36]),
+            ("BIT 7, (IY+1)", vec![0xFD, 0xCB, 0x01, 0x7E]),
+            ("LD B, RLC (IX+3)", vec![0xDD, 0xCB, 0x03, 0x00]),
+            ("LD A, RES 7, (IX+3)", vec![0xDD, 0xCB, 0x03, 0xBF]),
+            ("CPIR", vec![0xED, 0xB1]),
+        ];
+
+        for (source, bytes) in scenarios {
+            assert_eq!(bytes, assemble_bytes(source), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_assemble_disassembled_instructions() {
+        let pages: [&[u8]; 7] = [
+            &[],
+            &[0xCB],
+            &[0xED],
+            &[0xDD],
+            &[0xFD],
+            &[0xDD, 0xCB, 0x85],
+            &[0xFD, 0xCB, 0x85],
+        ];
+
+        for page in pages {
+            for opcode in 0x00..=0xFF {
+                let mut bytes = vec![0x00; 0x200];
+                let start = 0x0100;
+                let instruction_bytes = [page, &[opcode, 0x34, 0xF2]].concat();
+                bytes[start..start + instruction_bytes.len()].copy_from_slice(&instruction_bytes);
+                let ram = &Ram::new(&mut bytes);
+                let disassembled = DisassembledInstruction::read(ram, start as u16);
+
+                let source = format!(
+                    "ORG {start}\n{} {}",
+                    disassembled.mnemonic(),
+                    disassembled.operands()
+                );
+                let assembly = assemble(&source).unwrap();
+                let mut bytes = vec![0x00; 0x200];
+                let ram = &mut Ram::new(&mut bytes);
+                assembly.write_to(ram);
+                let reassembled = DisassembledInstruction::read(ram, start as u16);
+
+                // Opcodes that repeat others assemble to the documented one,
+                // so it is the meaning that has to match
+                assert_eq!(
+                    (disassembled.mnemonic(), disassembled.operands()),
+                    (reassembled.mnemonic(), reassembled.operands()),
+                    "{source}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_labels_and_directives() {
+        let assembly = assemble(
+            "
+            ; Copies the message to the screen
+                    ORG 8000h
+            length  EQU end - message
+            start:  LD HL, message
+                    LD B, length
+            .loop:  LD A, (HL)
+                    INC HL
+                    DJNZ .loop
+            other:  JR .loop        ; a different .loop
+            .loop:  JP start.loop
+            message:
+                    DB \"Hi;\", 0, 'x'
+            end:    DW start, -1
+                    DS 2, 0AAh
+                    ORG $+1
+                    DB %101, 1010b, 0x0F, $10, ~0 & 7, 1 << 4 | 1
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(0x8000, assembly.origin);
+        assert_eq!(Some(0x8000), assembly.symbol("start"));
+        assert_eq!(Some(0x8005), assembly.symbol("start.loop"));
+        assert_eq!(Some(0x800B), assembly.symbol("other.loop"));
+        assert_eq!(Some(0x800E), assembly.symbol("message"));
+        assert_eq!(Some(5), assembly.symbol("LENGTH"));
+        assert_eq!(
+            vec![
+                0x21, 0x0E, 0x80, // LD HL, message
+                0x06, 0x05, // LD B, length
+                0x7E, // LD A, (HL)
+                0x23, // INC HL
+                0x10, 0xFC, // DJNZ .loop
+                0x18, 0x00, // JR .loop
+                0xC3, 0x05, 0x80, // JP start.loop
+                b'H', b'i', b';', 0x00, b'x', // DB
+                0x00, 0x80, 0xFF, 0xFF, // DW
+                0xAA, 0xAA, // DS
+                0x00, // ORG $+1
+                0x05, 0x0A, 0x0F, 0x10, 0x07, 0x11, // DB
+            ],
+            assembly.bytes
+        );
+    }
+
+    #[test]
+    fn test_assemble_errors() {
+        let scenarios = [
+            ("LD A, B\nLD A, missing", 2, "missing is not defined"),
+            (
+                "JR far\nDS 200\nfar:",
+                1,
+                "relative jump to 00CAh is out of range",
+            ),
+            ("LD A, 300", 1, "invalid instruction LD A, 300"),
+            ("OUT (C), 1", 1, "invalid instruction OUT (C), 1"),
+            ("LD (IX+200), A", 1, "invalid instruction LD (IX+200), A"),
+            ("FOO A", 1, "unknown instruction FOO"),
+            ("IN F, (0FEh)", 1, "IN F only takes (C)"),
+            ("a:\na:", 2, "a is already defined"),
+            (
+                "ORG 10\nNOP\nORG 5",
+                3,
+                "ORG cannot move back over code already assembled",
+            ),
+            ("DS count\ncount EQU 2", 1, "count is not defined"),
+        ];
+
+        for (source, line, message) in scenarios {
+            assert_eq!(
+                Err(AssemblyError {
+                    line,
+                    message: message.to_string()
+                }),
+                assemble(source),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_assembled_program_runs() {
+        let assembly = assemble(
+            "
+                    ORG 0
+                    LD B, 4
+                    XOR A
+            .loop:  ADD A, B
+                    DJNZ .loop
+                    LD (result), A
+                    HALT
+            result: DB 0
+            ",
+        )
+        .unwrap();
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        assembly.write_to(ram);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        while !z80.is_halted() {
+            z80.process_next_instruction(ram, io);
+        }
+
+        let result = assembly.symbol("result").unwrap();
+        assert_eq!(10, ram.read(result));
+    }
+}
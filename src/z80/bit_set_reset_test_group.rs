@@ -0,0 +1,775 @@
+use super::{
+    register_flags::{
+        set_h_flag, set_p_flag_with, set_s_flag_with, set_x_flag_with, set_y_flag_with,
+        set_z_flag_with, unset_n_flag, X_FLAG_BITMASK, Y_FLAG_BITMASK,
+    },
+    Register, Z80Memory, Z80,
+};
+
+impl Z80 {
+    // Bit Set, Reset, and Test Group
+
+    /// ## BIT b, r
+    ///
+    /// ### Operation
+    ///
+    /// Z ← ~rb
+    ///
+    /// ### Op Code
+    ///
+    /// BIT: `1 1 0 0 1 0 1 1` (CB) `0 1 b b b r r r`
+    ///
+    /// ### Operands
+    ///
+    /// b, r
+    ///
+    /// ### Description
+    ///
+    /// This instruction tests bit b in register r and sets the Z flag
+    /// accordingly. In the assembled object code, operands b and r are specified
+    /// as follows:
+    ///
+    /// | Bit Tested | b   |
+    /// | ---------- | --- |
+    /// | 0          | 000 |
+    /// | 1          | 001 |
+    /// | 2          | 010 |
+    /// | 3          | 011 |
+    /// | 4          | 100 |
+    /// | 5          | 101 |
+    /// | 6          | 110 |
+    /// | 7          | 111 |
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if specified bit is 0; otherwise, it is reset.
+    ///
+    /// H is set.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If bit 2 in Register B contains 0, then upon the execution of a BIT 2, B
+    /// instruction, the Z flag in the F Register contains 1, and bit 2 in
+    /// Register B remains at 0. Bit 0 in Register B is the least-significant
+    /// bit.
+    fn bit_b_m(&mut self, b: u8, m: u8) {
+        let set = m & (1 << b) != 0;
+
+        set_z_flag_with(&mut self.f, !set);
+        set_h_flag(&mut self.f);
+        unset_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        // S is only set when testing bit 7 and it is set, and P/V is a copy of
+        // Z. Y and X are copied from the tested value.
+        set_s_flag_with(&mut self.f, b == 7 && set);
+        set_p_flag_with(&mut self.f, !set);
+        set_y_flag_with(&mut self.f, m & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, m & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn bit_b_a(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.a.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_b(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.b.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_c(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.c.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_d(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.d.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_e(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.e.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_h(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.h.value());
+
+        // T states
+        8
+    }
+
+    pub fn bit_b_l(&mut self, b: u8) -> u8 {
+        self.bit_b_m(b, self.l.value());
+
+        // T states
+        8
+    }
+
+    /// ## BIT b, (HL)
+    ///
+    /// ### Operation
+    ///
+    /// Z ← ~(HL)b
+    ///
+    /// ### Op Code
+    ///
+    /// BIT: `1 1 0 0 1 0 1 1` (CB) `0 1 b b b 1 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// b, (HL)
+    ///
+    /// ### Description
+    ///
+    /// This instruction tests bit b in the memory location specified by the
+    /// contents of HL and sets the Z flag accordingly. In the assembled object
+    /// code, operand b is specified as follows:
+    ///
+    /// | Bit Tested | b   |
+    /// | ---------- | --- |
+    /// | 0          | 000 |
+    /// | 1          | 001 |
+    /// | 2          | 010 |
+    /// | 3          | 011 |
+    /// | 4          | 100 |
+    /// | 5          | 101 |
+    /// | 6          | 110 |
+    /// | 7          | 111 |
+    ///
+    /// | M Cycles | T States     | 4 MHz E.T. |
+    /// | -------- | ------------ | ---------- |
+    /// | 3        | 12 (4, 4, 4) | 3.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if specified bit is 0; otherwise, it is reset.
+    ///
+    /// H is set.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 4444h, and bit 4 in the memory location
+    /// 444h contains 1, then upon the execution of a BIT 4, (HL) instruction, the
+    /// Z flag in the F Register contains 0, and bit 4 in memory location 4444h
+    /// remains at 1. Bit 0 in memory location 4444h is the least-significant
+    /// bit.
+    pub fn bit_b_mem_hl(&mut self, b: u8, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.internal_cycles(mem, 1);
+        self.bit_b_m(b, data);
+        self.set_xy_flags_from_memptr();
+
+        // T states
+        12
+    }
+
+    /// ## BIT b, (IX+d) / BIT b, (IY+d)
+    ///
+    /// ### Operation
+    ///
+    /// Z ← ~(IX+d)b or Z ← ~(IY+d)b
+    ///
+    /// ### Op Code
+    ///
+    /// BIT: `1 1 0 1 1 1 0 1` (DD) or `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1`
+    /// (CB) `d d d d d d d d` `0 1 b b b 1 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// b, (IX+d) or b, (IY+d)
+    ///
+    /// ### Description
+    ///
+    /// This instruction tests bit b in the memory location specified by the
+    /// contents of the index register plus the two’s-complement displacement d
+    /// and sets the Z flag accordingly. The address has already been computed
+    /// into MEMPTR when the instruction is decoded. The undocumented encodings
+    /// with a register in the low three bits behave identically.
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 20 (4, 4, 3, 5, 4)    | 5.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if specified bit is 0; otherwise, it is reset.
+    ///
+    /// H is set.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the contents of Index Register IX are 2000h and bit 6 in memory
+    /// location 2004h contains 1, then upon the execution of a BIT 6, (IX+4h)
+    /// instruction, the Z flag in the F Register contains 0 and bit 6 in
+    /// memory location 2004h still contains 1. Bit 0 in memory location 2004h
+    /// is the least-significant bit.
+    pub fn bit_b_mem_index_d(&mut self, b: u8, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.memptr);
+        self.internal_cycles(mem, 1);
+        self.bit_b_m(b, data);
+        self.set_xy_flags_from_memptr();
+
+        // T states
+        20
+    }
+
+    /// Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.21
+    /// When BIT tests a memory location, Y and X leak from the high byte of
+    /// the internal MEMPTR register rather than from the tested value.
+    fn set_xy_flags_from_memptr(&mut self) {
+        let memptr_high = (self.memptr >> 8) as u8;
+        set_y_flag_with(&mut self.f, memptr_high & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, memptr_high & X_FLAG_BITMASK != 0);
+    }
+
+    /// ## SET b, m
+    ///
+    /// ### Operation
+    ///
+    /// mb ← 1
+    ///
+    /// ### Op Code
+    ///
+    /// SET b, r: `1 1 0 0 1 0 1 1` (CB) `1 1 b b b r r r`
+    ///
+    /// SET b, (HL): `1 1 0 0 1 0 1 1` (CB) `1 1 b b b 1 1 0`
+    ///
+    /// SET b, (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `1 1 b b b 1 1 0`
+    ///
+    /// SET b, (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `1 1 b b b 1 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// b, m
+    ///
+    /// ### Description
+    ///
+    /// Bit b in operand m is set. Operand m is any of r, (HL), (IX+d), or
+    /// (IY+d). In the assembled object code, operands b and r are specified as
+    /// follows:
+    ///
+    /// | Bit        | b   |
+    /// | ---------- | --- |
+    /// | 0          | 000 |
+    /// | 1          | 001 |
+    /// | 2          | 010 |
+    /// | 3          | 011 |
+    /// | 4          | 100 |
+    /// | 5          | 101 |
+    /// | 6          | 110 |
+    /// | 7          | 111 |
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 3000h, then upon the execution of a SET
+    /// 4, (HL) instruction, bit 4 in memory location 3000h contains 1. Bit 0 in
+    /// memory location 3000h is the least-significant bit.
+    fn set_b_m(b: u8, m: u8) -> u8 {
+        m | (1 << b)
+    }
+
+    pub fn set_b_a(&mut self, b: u8) -> u8 {
+        let a = Z80::set_b_m(b, self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_b(&mut self, b: u8) -> u8 {
+        let b = Z80::set_b_m(b, self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_c(&mut self, b: u8) -> u8 {
+        let c = Z80::set_b_m(b, self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_d(&mut self, b: u8) -> u8 {
+        let d = Z80::set_b_m(b, self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_e(&mut self, b: u8) -> u8 {
+        let e = Z80::set_b_m(b, self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_h(&mut self, b: u8) -> u8 {
+        let h = Z80::set_b_m(b, self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_l(&mut self, b: u8) -> u8 {
+        let l = Z80::set_b_m(b, self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn set_b_mem_hl(&mut self, b: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = Z80::set_b_m(b, data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    pub fn set_b_mem_index_d(&mut self, b: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.memptr;
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = Z80::set_b_m(b, data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn set_b_mem_index_d_r(
+        &mut self,
+        b: u8,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let address = self.memptr;
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = Z80::set_b_m(b, data);
+        self.write_memory(mem, address, data);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    /// ## RES b, m
+    ///
+    /// ### Operation
+    ///
+    /// mb ← 0
+    ///
+    /// ### Op Code
+    ///
+    /// RES b, r: `1 1 0 0 1 0 1 1` (CB) `1 0 b b b r r r`
+    ///
+    /// RES b, (HL): `1 1 0 0 1 0 1 1` (CB) `1 0 b b b 1 1 0`
+    ///
+    /// RES b, (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `1 0 b b b 1 1 0`
+    ///
+    /// RES b, (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `1 0 b b b 1 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// b, m
+    ///
+    /// ### Description
+    ///
+    /// Bit b in operand m is reset. Operand m is any of r, (HL), (IX+d), or
+    /// (IY+d). In the assembled object code, operands b and r are specified as
+    /// follows:
+    ///
+    /// | Bit        | b   |
+    /// | ---------- | --- |
+    /// | 0          | 000 |
+    /// | 1          | 001 |
+    /// | 2          | 010 |
+    /// | 3          | 011 |
+    /// | 4          | 100 |
+    /// | 5          | 101 |
+    /// | 6          | 110 |
+    /// | 7          | 111 |
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// Upon the execution of a RES 6, D instruction, bit 6 in register D
+    /// resets. Bit 0 in register D is the least-significant bit.
+    fn res_b_m(b: u8, m: u8) -> u8 {
+        m & !(1 << b)
+    }
+
+    pub fn res_b_a(&mut self, b: u8) -> u8 {
+        let a = Z80::res_b_m(b, self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_b(&mut self, b: u8) -> u8 {
+        let b = Z80::res_b_m(b, self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_c(&mut self, b: u8) -> u8 {
+        let c = Z80::res_b_m(b, self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_d(&mut self, b: u8) -> u8 {
+        let d = Z80::res_b_m(b, self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_e(&mut self, b: u8) -> u8 {
+        let e = Z80::res_b_m(b, self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_h(&mut self, b: u8) -> u8 {
+        let h = Z80::res_b_m(b, self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_l(&mut self, b: u8) -> u8 {
+        let l = Z80::res_b_m(b, self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn res_b_mem_hl(&mut self, b: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = Z80::res_b_m(b, data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    pub fn res_b_mem_index_d(&mut self, b: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.memptr;
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = Z80::res_b_m(b, data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn res_b_mem_index_d_r(
+        &mut self,
+        b: u8,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let address = self.memptr;
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = Z80::res_b_m(b, data);
+        self.write_memory(mem, address, data);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{h_flag, n_flag, p_flag, s_flag, x_flag, y_flag, z_flag},
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_bit_b_r() {
+        let scenarios: [(fn(&mut Z80, u8) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
+            (Z80::bit_b_a, |z80: &mut Z80| &mut z80.a),
+            (Z80::bit_b_b, |z80: &mut Z80| &mut z80.b),
+            (Z80::bit_b_c, |z80: &mut Z80| &mut z80.c),
+            (Z80::bit_b_d, |z80: &mut Z80| &mut z80.d),
+            (Z80::bit_b_e, |z80: &mut Z80| &mut z80.e),
+            (Z80::bit_b_h, |z80: &mut Z80| &mut z80.h),
+            (Z80::bit_b_l, |z80: &mut Z80| &mut z80.l),
+        ];
+
+        for (opcode, r_supplier) in scenarios {
+            let z80 = &mut Z80::new();
+            r_supplier(z80).set_value(0b11111011);
+
+            let t_states = opcode(z80, 2);
+            assert_eq!(8, t_states);
+
+            assert_eq!(true, z_flag(&z80.f));
+            assert_eq!(true, p_flag(&z80.f));
+            assert_eq!(true, h_flag(&z80.f));
+            assert_eq!(false, n_flag(&z80.f));
+            assert_eq!(0b11111011, r_supplier(z80).value());
+
+            opcode(z80, 3);
+
+            assert_eq!(false, z_flag(&z80.f));
+            assert_eq!(false, p_flag(&z80.f));
+        }
+    }
+
+    #[test]
+    fn test_bit_7_sets_s() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b10000000);
+
+        z80.bit_b_a(7);
+        assert_eq!(true, s_flag(&z80.f));
+
+        z80.bit_b_a(6);
+        assert_eq!(false, s_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_bit_b_mem_hl() {
+        let bytes = &mut [0x00, 0b00010000];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+        z80.memptr = 0x2800;
+
+        let t_states = z80.bit_b_mem_hl(4, ram);
+        assert_eq!(12, t_states);
+
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+        assert_eq!(0b00010000, ram.read(1));
+    }
+
+    #[test]
+    fn test_set_b_r() {
+        let z80 = &mut Z80::new();
+
+        let t_states = z80.set_b_b(4);
+        assert_eq!(8, t_states);
+
+        assert_eq!(0b00010000, z80.b.value());
+    }
+
+    #[test]
+    fn test_set_b_mem_hl() {
+        let bytes = &mut [0x00, 0x00];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+
+        let t_states = z80.set_b_mem_hl(4, ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0b00010000, ram.read(1));
+    }
+
+    #[test]
+    fn test_res_b_r() {
+        let z80 = &mut Z80::new();
+        z80.d.set_value(0xFF);
+
+        let t_states = z80.res_b_d(6);
+        assert_eq!(8, t_states);
+
+        assert_eq!(0b10111111, z80.d.value());
+    }
+
+    #[test]
+    fn test_res_b_mem_hl() {
+        let bytes = &mut [0x00, 0xFF];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+
+        let t_states = z80.res_b_mem_hl(0, ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0b11111110, ram.read(1));
+    }
+
+    #[test]
+    fn test_bit_b_mem_index_d() {
+        let bytes = &mut [0x00; 0x2900];
+        bytes[0x2804] = 0b01000000;
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.memptr = 0x2804;
+
+        let t_states = z80.bit_b_mem_index_d(6, ram);
+        assert_eq!(20, t_states);
+
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+
+        z80.bit_b_mem_index_d(5, ram);
+
+        assert_eq!(true, z_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_set_b_mem_index_d_r() {
+        let bytes = &mut [0x00, 0x00];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.memptr = 0x0001;
+
+        let t_states = z80.set_b_mem_index_d(0, ram);
+        assert_eq!(23, t_states);
+        assert_eq!(0b00000001, ram.read(1));
+
+        let t_states = z80.set_b_mem_index_d_r(7, ram, |z80: &mut Z80| &mut z80.e);
+        assert_eq!(23, t_states);
+        assert_eq!(0b10000001, ram.read(1));
+        assert_eq!(0b10000001, z80.e.value());
+    }
+
+    #[test]
+    fn test_res_b_mem_index_d_r() {
+        let bytes = &mut [0x00, 0xFF];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.memptr = 0x0001;
+
+        let t_states = z80.res_b_mem_index_d_r(1, ram, |z80: &mut Z80| &mut z80.a);
+        assert_eq!(23, t_states);
+        assert_eq!(0b11111101, ram.read(1));
+        assert_eq!(0b11111101, z80.a.value());
+    }
+}
@@ -0,0 +1,657 @@
+use super::{
+    register_flags::{c_flag, p_flag, s_flag, z_flag},
+    Z80Memory, Z80,
+};
+
+impl Z80 {
+    // Call and Return Group
+
+    /// Pops the top two bytes of the stack into the program counter, low byte
+    /// first.
+    fn pop_program_counter(&mut self, mem: &(impl Z80Memory + ?Sized)) {
+        let low = self.read_memory(mem, self.stack_pointer);
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+
+        let high = self.read_memory(mem, self.stack_pointer);
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+
+        self.program_counter = ((high as u16) << 8) | low as u16;
+        self.memptr = self.program_counter;
+    }
+
+    /// Pushes the program counter onto the stack, high byte first.
+    pub(super) fn push_program_counter(&mut self, mem: &mut (impl Z80Memory + ?Sized)) {
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        self.write_memory(mem, self.stack_pointer, (self.program_counter >> 8) as u8);
+
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+        self.write_memory(mem, self.stack_pointer, self.program_counter as u8);
+    }
+
+    /// ## CALL nn
+    ///
+    /// ### Operation
+    ///
+    /// (SP – 1) ← PCH, (SP – 2) ← PCL, PC ← nn
+    ///
+    /// ### Op Code
+    ///
+    /// CALL: `1 1 0 0 1 1 0 1` (CD) followed by `n n n n n n n n` (low order)
+    /// and `n n n n n n n n` (high order)
+    ///
+    /// ### Operands
+    ///
+    /// nn
+    ///
+    /// ### Description
+    ///
+    /// The current contents of the Program Counter (PC) are pushed onto the top
+    /// of the external memory stack. The operands nn are then loaded to the PC
+    /// to point to the address in memory at which the first op code of a
+    /// subroutine is to be fetched. At the end of the subroutine, a RETurn
+    /// instruction can be used to return to the original program flow by
+    /// popping the top of the stack back to the PC. The push is accomplished
+    /// by first decrementing the current contents of the Stack Pointer
+    /// (register pair SP), loading the high-order byte of the PC contents to
+    /// the memory address now pointed to by the SP; then decrementing SP again,
+    /// and loading the low-order byte of the PC contents to the top of stack.
+    ///
+    /// Because this process is a 3-byte instruction, the Program Counter was
+    /// incremented by three before the push is executed.
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 17 (4, 3, 4, 3, 3)    | 4.25       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// The Program Counter contains 1A47h, the Stack Pointer contains 3002h,
+    /// and memory locations contain the following data.
+    ///
+    /// | Location | Contents |
+    /// | -------- | -------- |
+    /// | 1A47h    | CDh      |
+    /// | 1A48h    | 35h      |
+    /// | 1A49h    | 21h      |
+    ///
+    /// If an opcode fetch cycle begins, then upon the completion of the
+    /// execution of a CALL 2135h instruction, the Stack Pointer contains 3000h,
+    /// memory location 3001h contains 1Ah, memory location 3000h contains 4Ah,
+    /// and the Program Counter contains 2135h, thereby pointing to the address
+    /// of the first op code of the subroutine now to be executed.
+    pub fn call_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(true, mem)
+    }
+
+    /// ## CALL cc, nn
+    ///
+    /// ### Operation
+    ///
+    /// IF cc true: (SP – 1) ← PCH, (SP – 2) ← PCL, PC ← nn
+    ///
+    /// ### Op Code
+    ///
+    /// CALL: `1 1 c c c 1 0 0` followed by `n n n n n n n n` (low order) and
+    /// `n n n n n n n n` (high order)
+    ///
+    /// ### Operands
+    ///
+    /// cc, nn
+    ///
+    /// ### Description
+    ///
+    /// If condition cc is true, this instruction pushes the current contents of
+    /// the Program Counter (PC) onto the top of the external memory stack, then
+    /// loads the operands nn to PC to point to the address in memory at which
+    /// the first op code of a subroutine is to be fetched. At the end of the
+    /// subroutine, a RETurn instruction can be used to return to the original
+    /// program flow by popping the top of the stack back to PC. If condition cc
+    /// is false, the Program Counter is incremented as usual, and the program
+    /// continues with the next sequential instruction. The stack push is
+    /// accomplished by first decrementing the current contents of the Stack
+    /// Pointer (SP), loading the high-order byte of the PC contents to the
+    /// memory address now pointed to by SP; then decrementing SP again, and
+    /// loading the low-order byte of the PC contents to the top of the stack.
+    ///
+    /// Because this process is a 3-byte instruction, the Program Counter was
+    /// incremented by three before the push is executed.
+    ///
+    /// Condition cc is programmed as one of eight statuses that corresponds to
+    /// condition bits in the Flag Register (Register F). These eight statuses
+    /// are defined in the following table, which also specifies the
+    /// corresponding cc bit fields in the assembled object code.
+    ///
+    /// | cc  | Condition       | Relevant Flag |
+    /// | --- | --------------- | ------------- |
+    /// | 000 | Non-Zero (NZ)   | Z             |
+    /// | 001 | Zero (Z)        | Z             |
+    /// | 010 | No Carry (NC)   | C             |
+    /// | 011 | Carry (C)       | C             |
+    /// | 100 | Parity Odd (PO) | P/V           |
+    /// | 101 | Parity Even(PE) | P/V           |
+    /// | 110 | Sign Positive(P)| S             |
+    /// | 111 | Sign Negative(M)| S             |
+    ///
+    /// If cc is true:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 17 (4, 3, 4, 3, 3)    | 4.25       |
+    ///
+    /// If cc is false:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 10 (4, 3, 3)    | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    fn call_cc_nn(&mut self, condition: bool, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_next_address(mem);
+        self.memptr = address;
+
+        if !condition {
+            // T states
+            return 10;
+        }
+
+        self.internal_cycles(mem, 1);
+        self.push_program_counter(mem);
+        self.program_counter = address;
+
+        // T states
+        17
+    }
+
+    pub fn call_nz_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(!z_flag(&self.f), mem)
+    }
+
+    pub fn call_z_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(z_flag(&self.f), mem)
+    }
+
+    pub fn call_nc_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(!c_flag(&self.f), mem)
+    }
+
+    pub fn call_c_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(c_flag(&self.f), mem)
+    }
+
+    pub fn call_po_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(!p_flag(&self.f), mem)
+    }
+
+    pub fn call_pe_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(p_flag(&self.f), mem)
+    }
+
+    pub fn call_p_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(!s_flag(&self.f), mem)
+    }
+
+    pub fn call_m_nn(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.call_cc_nn(s_flag(&self.f), mem)
+    }
+
+    /// ## RET
+    ///
+    /// ### Operation
+    ///
+    /// PCL ← (SP), PCH ← (SP+1)
+    ///
+    /// ### Op Code
+    ///
+    /// RET: `1 1 0 0 1 0 0 1` (C9)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The byte at the memory location specified by the contents of the Stack
+    /// Pointer (SP) Register pair is moved to the low-order eight bits of the
+    /// Program Counter (PC). The SP is now incremented and the byte at the
+    /// memory location specified by the new contents of this instruction is
+    /// fetched from the memory location specified by the PC. This instruction
+    /// is normally used to return to the main line program at the completion
+    /// of a routine entered by a CALL instruction.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 10 (4, 3, 3)    | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// The Program Counter contains 3535h, the Stack Pointer contains 2000h,
+    /// memory location 2000h contains B5h, and memory location 2001h contains
+    /// 18h. Upon the execution of a RET instruction, the Stack Pointer
+    /// contains 2002h and the Program Counter contains 18B5h, thereby pointing
+    /// to the address of the next program op code to be fetched.
+    pub fn ret(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.pop_program_counter(mem);
+
+        // T states
+        10
+    }
+
+    /// ## RET cc
+    ///
+    /// ### Operation
+    ///
+    /// If cc true: PCL ← (SP), PCH ← (SP+1)
+    ///
+    /// ### Op Code
+    ///
+    /// RET: `1 1 c c c 0 0 0`
+    ///
+    /// ### Operands
+    ///
+    /// cc
+    ///
+    /// ### Description
+    ///
+    /// If condition cc is true, the byte at the memory location specified by
+    /// the contents of the Stack Pointer (SP) register pair is moved to the
+    /// low-order eight bits of the Program Counter (PC). The SP is incremented
+    /// and the byte at the memory location specified by the new contents of
+    /// the SP are moved to the high-order eight bits of the PC. The SP is
+    /// incremented again. The next op code following this instruction is
+    /// fetched from the memory location specified by the PC. This instruction
+    /// is normally used to return to the main line program at the completion
+    /// of a routine entered by a CALL instruction. If condition cc is false,
+    /// the PC is simply incremented as usual, and the program continues with
+    /// the next sequential instruction. Condition cc is programmed as one of
+    /// eight status that correspond to condition bits in the Flag Register
+    /// (Register F), as defined for CALL cc, nn.
+    ///
+    /// If cc is true:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (5, 3, 3)    | 2.75       |
+    ///
+    /// If cc is false:
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 5        | 1.25       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    fn ret_cc(&mut self, condition: bool, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
+        if !condition {
+            // T states
+            return 5;
+        }
+
+        self.pop_program_counter(mem);
+
+        // T states
+        11
+    }
+
+    pub fn ret_nz(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(!z_flag(&self.f), mem)
+    }
+
+    pub fn ret_z(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(z_flag(&self.f), mem)
+    }
+
+    pub fn ret_nc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(!c_flag(&self.f), mem)
+    }
+
+    pub fn ret_c(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(c_flag(&self.f), mem)
+    }
+
+    pub fn ret_po(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(!p_flag(&self.f), mem)
+    }
+
+    pub fn ret_pe(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(p_flag(&self.f), mem)
+    }
+
+    pub fn ret_p(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(!s_flag(&self.f), mem)
+    }
+
+    pub fn ret_m(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.ret_cc(s_flag(&self.f), mem)
+    }
+
+    /// ## RETI
+    ///
+    /// ### Operation
+    ///
+    /// Return from Interrupt
+    ///
+    /// ### Op Code
+    ///
+    /// RETI: `1 1 1 0 1 1 0 1` (ED) `0 1 0 0 1 1 0 1` (4D)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// This instruction is used at the end of a maskable interrupt service
+    /// routine to restore the contents of the Program Counter (analogous to
+    /// the RET instruction) and to signal an I/O device that the interrupt
+    /// routine is completed. The RETI instruction also facilitates the nesting
+    /// of interrupts, allowing higher priority devices to temporarily suspend
+    /// service of lower priority service routines. However, this instruction
+    /// does not enable interrupts that were disabled when the interrupt
+    /// routine was entered. Before doing the RETI instruction, the enable
+    /// interrupt instruction (EI) should be executed to allow recognition of
+    /// interrupts after completion of the current service routine.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 14 (4, 4, 3, 3)    | 3.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn reti(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.pop_program_counter(mem);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.20
+        // RETI copies IFF2 into IFF1 exactly as RETN does.
+        self.iff1 = self.iff2;
+
+        // T states
+        14
+    }
+
+    /// ## RETN
+    ///
+    /// ### Operation
+    ///
+    /// Return from nonmaskable interrupt
+    ///
+    /// ### Op Code
+    ///
+    /// RETN: `1 1 1 0 1 1 0 1` (ED) `0 1 0 0 0 1 0 1` (45)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// This instruction is used at the end of a nonmaskable interrupts service
+    /// routine to restore the contents of the Program Counter (analogous to
+    /// the RET instruction). The state of IFF2 is copied back to IFF1 so that
+    /// maskable interrupts are enabled immediately following the RETN if they
+    /// were enabled before the nonmaskable interrupt.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 14 (4, 4, 3, 3)    | 3.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn retn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.pop_program_counter(mem);
+        self.iff1 = self.iff2;
+
+        // T states
+        14
+    }
+
+    /// ## RST p
+    ///
+    /// ### Operation
+    ///
+    /// (SP – 1) ← PCH, (SP – 2) ← PCL, PCH ← 0, PCL ← P
+    ///
+    /// ### Op Code
+    ///
+    /// RST: `1 1 t t t 1 1 1`
+    ///
+    /// ### Operands
+    ///
+    /// p
+    ///
+    /// ### Description
+    ///
+    /// The current Program Counter (PC) contents are pushed onto the external
+    /// memory stack, and the Page 0 memory location assigned by operand p is
+    /// loaded to the PC. Program execution then begins with the op code in the
+    /// address now pointed to by PC. The push is performed by first
+    /// decrementing the contents of the Stack Pointer (SP), loading the
+    /// high-order byte of PC to the memory address now pointed to by SP,
+    /// decrementing SP again, and loading the low-order byte of PC to the
+    /// address now pointed to by SP. The Restart instruction allows for a jump
+    /// to one of eight addresses indicated in the following table. The operand
+    /// p is assembled to the object code using the corresponding T state.
+    ///
+    /// Because all addresses are stored in Page 0 of memory, the high-order
+    /// byte of PC is loaded with 00h. The number selected from the p column of
+    /// the table is loaded to the low-order byte of PC.
+    ///
+    /// | p   | t   |
+    /// | --- | --- |
+    /// | 00h | 000 |
+    /// | 08h | 001 |
+    /// | 10h | 010 |
+    /// | 18h | 011 |
+    /// | 20h | 100 |
+    /// | 28h | 101 |
+    /// | 30h | 110 |
+    /// | 38h | 111 |
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (5, 3, 3)    | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Program Counter contains 15B3h, then upon the execution of an
+    /// RST 18h (object code 11011111) instruction, the PC contains 0018h as the
+    /// address of the next fetched op code.
+    fn rst_p(&mut self, p: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
+        self.push_program_counter(mem);
+        self.program_counter = p as u16;
+        self.memptr = self.program_counter;
+
+        // T states
+        11
+    }
+
+    pub fn rst_00h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x00, mem)
+    }
+
+    pub fn rst_08h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x08, mem)
+    }
+
+    pub fn rst_10h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x10, mem)
+    }
+
+    pub fn rst_18h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x18, mem)
+    }
+
+    pub fn rst_20h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x20, mem)
+    }
+
+    pub fn rst_28h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x28, mem)
+    }
+
+    pub fn rst_30h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x30, mem)
+    }
+
+    pub fn rst_38h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rst_p(0x38, mem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{set_c_flag, set_z_flag},
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_call_nn() {
+        let bytes = &mut [0x00; 0x10];
+        bytes[0x04] = 0xCD;
+        bytes[0x05] = 0x35;
+        bytes[0x06] = 0x21;
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x0005;
+        z80.stack_pointer = 0x000E;
+
+        let t_states = z80.call_nn(ram);
+        assert_eq!(17, t_states);
+
+        assert_eq!(0x2135, z80.program_counter);
+        assert_eq!(0x000C, z80.stack_pointer);
+        assert_eq!(0x07, ram.read(0x000C));
+        assert_eq!(0x00, ram.read(0x000D));
+        assert_eq!(0x2135, z80.memptr);
+    }
+
+    #[test]
+    fn test_call_cc_nn() {
+        let bytes = &mut [0x34, 0x12, 0x00, 0x00, 0x00, 0x00];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.stack_pointer = 0x0006;
+
+        let t_states = z80.call_z_nn(ram);
+        assert_eq!(10, t_states);
+        assert_eq!(0x0002, z80.program_counter);
+        assert_eq!(0x0006, z80.stack_pointer);
+
+        z80.program_counter = 0x0000;
+        let t_states = z80.call_nz_nn(ram);
+        assert_eq!(17, t_states);
+        assert_eq!(0x1234, z80.program_counter);
+        assert_eq!(0x0004, z80.stack_pointer);
+        assert_eq!(0x02, ram.read(0x0004));
+    }
+
+    #[test]
+    fn test_ret() {
+        let bytes = &mut [0x00, 0x00, 0xB5, 0x18];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x3535;
+        z80.stack_pointer = 0x0002;
+
+        let t_states = z80.ret(ram);
+        assert_eq!(10, t_states);
+
+        assert_eq!(0x18B5, z80.program_counter);
+        assert_eq!(0x0004, z80.stack_pointer);
+        assert_eq!(0x18B5, z80.memptr);
+    }
+
+    #[test]
+    fn test_ret_cc() {
+        let bytes = &mut [0xB5, 0x18];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x3535;
+        set_c_flag(&mut z80.f);
+        set_z_flag(&mut z80.f);
+
+        let t_states = z80.ret_nc(ram);
+        assert_eq!(5, t_states);
+        assert_eq!(0x3535, z80.program_counter);
+        assert_eq!(0x0000, z80.stack_pointer);
+
+        let t_states = z80.ret_z(ram);
+        assert_eq!(11, t_states);
+        assert_eq!(0x18B5, z80.program_counter);
+        assert_eq!(0x0002, z80.stack_pointer);
+    }
+
+    #[test]
+    fn test_rst_p() {
+        let bytes = &mut [0x00; 0x04];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x15B3;
+        z80.stack_pointer = 0x0004;
+
+        let t_states = z80.rst_18h(ram);
+        assert_eq!(11, t_states);
+
+        assert_eq!(0x0018, z80.program_counter);
+        assert_eq!(0x0002, z80.stack_pointer);
+        assert_eq!(0xB3, ram.read(0x0002));
+        assert_eq!(0x15, ram.read(0x0003));
+    }
+
+    #[test]
+    fn test_retn() {
+        let bytes = &mut [0x00, 0x00, 0x34, 0x12];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.stack_pointer = 0x0002;
+        z80.iff1 = false;
+        z80.iff2 = true;
+
+        let t_states = z80.retn(ram);
+        assert_eq!(14, t_states);
+
+        assert_eq!(0x1234, z80.program_counter);
+        assert_eq!(0x0004, z80.stack_pointer);
+        assert_eq!(true, z80.iff1);
+    }
+
+    #[test]
+    fn test_reti() {
+        let bytes = &mut [0x00, 0x00, 0x34, 0x12];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.stack_pointer = 0x0002;
+        z80.iff1 = true;
+        z80.iff2 = false;
+
+        let t_states = z80.reti(ram);
+        assert_eq!(14, t_states);
+
+        assert_eq!(0x1234, z80.program_counter);
+        assert_eq!(0x0004, z80.stack_pointer);
+        assert_eq!(false, z80.iff1);
+    }
+}
@@ -0,0 +1,408 @@
+use std::{fmt, ops::RangeInclusive};
+
+use super::{
+    decode,
+    instruction::{Condition, Operand, Operation, Register, RegisterPair, Shift},
+    Instruction, Z80Memory,
+};
+
+/// The longest an instruction can be, DDCB d op.
+const MAX_INSTRUCTION_LENGTH: usize = 4;
+
+/// One line of a disassembly listing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisassembledInstruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub instruction: Instruction,
+}
+
+impl DisassembledInstruction {
+    /// Decodes the instruction at `address`.
+    pub fn read(mem: &(impl Z80Memory + ?Sized), address: u16) -> DisassembledInstruction {
+        let mut bytes: Vec<u8> = (0..MAX_INSTRUCTION_LENGTH as u16)
+            .map(|offset| mem.read(address.wrapping_add(offset)))
+            .collect();
+        let (instruction, length) = decode(&bytes);
+        bytes.truncate(length);
+
+        DisassembledInstruction {
+            address,
+            bytes,
+            instruction,
+        }
+    }
+
+    /// The Zilog mnemonic, such as `LD` or `RLC`.
+    pub fn mnemonic(&self) -> &'static str {
+        match self.instruction.operation {
+            Operation::Nop => "NOP",
+            Operation::Halt => "HALT",
+            Operation::Di => "DI",
+            Operation::Ei => "EI",
+            Operation::Im(_) => "IM",
+            Operation::Ld(..)
+            | Operation::LdShift(..)
+            | Operation::LdSet(..)
+            | Operation::LdRes(..) => "LD",
+            Operation::Push(_) => "PUSH",
+            Operation::Pop(_) => "POP",
+            Operation::Ex(..) => "EX",
+            Operation::Exx => "EXX",
+            Operation::Ldi => "LDI",
+            Operation::Ldir => "LDIR",
+            Operation::Ldd => "LDD",
+            Operation::Lddr => "LDDR",
+            Operation::Cpi => "CPI",
+            Operation::Cpir => "CPIR",
+            Operation::Cpd => "CPD",
+            Operation::Cpdr => "CPDR",
+            Operation::Add(..) => "ADD",
+            Operation::Adc(..) => "ADC",
+            Operation::Sub(_) => "SUB",
+            Operation::Sbc(..) => "SBC",
+            Operation::And(_) => "AND",
+            Operation::Or(_) => "OR",
+            Operation::Xor(_) => "XOR",
+            Operation::Cp(_) => "CP",
+            Operation::Inc(_) => "INC",
+            Operation::Dec(_) => "DEC",
+            Operation::Daa => "DAA",
+            Operation::Cpl => "CPL",
+            Operation::Neg => "NEG",
+            Operation::Ccf => "CCF",
+            Operation::Scf => "SCF",
+            Operation::Rlca => "RLCA",
+            Operation::Rla => "RLA",
+            Operation::Rrca => "RRCA",
+            Operation::Rra => "RRA",
+            Operation::Rld => "RLD",
+            Operation::Rrd => "RRD",
+            Operation::Shift(shift, _) => shift_mnemonic(shift),
+            Operation::Bit(..) => "BIT",
+            Operation::Set(..) => "SET",
+            Operation::Res(..) => "RES",
+            Operation::Jp(..) => "JP",
+            Operation::Jr(..) => "JR",
+            Operation::Djnz(_) => "DJNZ",
+            Operation::Call(..) => "CALL",
+            Operation::Ret(_) => "RET",
+            Operation::Reti => "RETI",
+            Operation::Retn => "RETN",
+            Operation::Rst(_) => "RST",
+            Operation::In(..) => "IN",
+            Operation::Out(..) => "OUT",
+            Operation::Ini => "INI",
+            Operation::Inir => "INIR",
+            Operation::Ind => "IND",
+            Operation::Indr => "INDR",
+            Operation::Outi => "OUTI",
+            Operation::Otir => "OTIR",
+            Operation::Outd => "OUTD",
+            Operation::Otdr => "OTDR",
+        }
+    }
+
+    /// The operands separated by commas, with relative jumps shown as the
+    /// address they jump to. Empty for instructions without operands.
+    pub fn operands(&self) -> String {
+        let next_address = self.address.wrapping_add(self.bytes.len() as u16);
+        let operand = |operand| format_operand(operand, next_address);
+        let condition = |condition: Option<Condition>, target| match condition {
+            Some(condition) => vec![condition_name(condition).to_string(), operand(target)],
+            None => vec![operand(target)],
+        };
+
+        let operands = match self.instruction.operation {
+            Operation::Im(mode) => vec![mode.to_string()],
+            Operation::Ld(destination, source)
+            | Operation::Ex(destination, source)
+            | Operation::Add(destination, source)
+            | Operation::Adc(destination, source)
+            | Operation::Sbc(destination, source) => vec![operand(destination), operand(source)],
+            // OUT (C), 0 has no register to write, so its data is written as
+            // it is in the mnemonic
+            Operation::Out(Operand::PortC, Operand::Immediate(0)) => {
+                vec![operand(Operand::PortC), "0".to_string()]
+            }
+            Operation::Out(port, source) => vec![operand(port), operand(source)],
+            Operation::Push(source)
+            | Operation::Pop(source)
+            | Operation::Sub(source)
+            | Operation::And(source)
+            | Operation::Or(source)
+            | Operation::Xor(source)
+            | Operation::Cp(source)
+            | Operation::Inc(source)
+            | Operation::Dec(source)
+            | Operation::Shift(_, source)
+            | Operation::Djnz(source) => vec![operand(source)],
+            Operation::LdShift(register, shift, source) => vec![
+                register_name(register).to_string(),
+                format!("{} {}", shift_mnemonic(shift), operand(source)),
+            ],
+            Operation::Bit(bit, source)
+            | Operation::Set(bit, source)
+            | Operation::Res(bit, source) => {
+                vec![bit.to_string(), operand(source)]
+            }
+            Operation::LdSet(register, bit, source) => vec![
+                register_name(register).to_string(),
+                format!("SET {}, {}", bit, operand(source)),
+            ],
+            Operation::LdRes(register, bit, source) => vec![
+                register_name(register).to_string(),
+                format!("RES {}, {}", bit, operand(source)),
+            ],
+            Operation::Jp(condition_code, target)
+            | Operation::Jr(condition_code, target)
+            | Operation::Call(condition_code, target) => condition(condition_code, target),
+            Operation::Ret(Some(condition_code)) => {
+                vec![condition_name(condition_code).to_string()]
+            }
+            Operation::Rst(address) => vec![format_byte(address)],
+            Operation::In(Some(register), port) => {
+                vec![register_name(register).to_string(), operand(port)]
+            }
+            Operation::In(None, port) => vec![operand(port)],
+            _ => vec![],
+        };
+
+        operands.join(", ")
+    }
+}
+
+impl fmt::Display for DisassembledInstruction {
+    /// Formats the line as the address, the bytes and the instruction, for
+    /// example `0005  C3 CB 11     JP 11CBh`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect();
+        write!(
+            f,
+            "{:04X}  {:<11}  {}",
+            self.address,
+            bytes.join(" "),
+            self.mnemonic()
+        )?;
+
+        let operands = self.operands();
+        if !operands.is_empty() {
+            write!(f, " {operands}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Disassembles the instructions that start within `range`. The last one may
+/// run past the end of the range, and addresses wrap around at FFFFh.
+pub fn disassemble(
+    mem: &(impl Z80Memory + ?Sized),
+    range: RangeInclusive<u16>,
+) -> Vec<DisassembledInstruction> {
+    let mut instructions = Vec::new();
+    let end = *range.end() as u32;
+    let mut address = *range.start() as u32;
+    while address <= end {
+        let instruction = DisassembledInstruction::read(mem, address as u16);
+        address += instruction.bytes.len() as u32;
+        instructions.push(instruction);
+    }
+
+    instructions
+}
+
+/// Formats a number as hexadecimal the way Zilog assemblers take it, with a
+/// leading 0 when it would otherwise start with a letter.
+fn format_hex(digits: String) -> String {
+    if digits.starts_with(|digit: char| digit.is_ascii_alphabetic()) {
+        format!("0{digits}h")
+    } else {
+        format!("{digits}h")
+    }
+}
+
+fn format_byte(data: u8) -> String {
+    format_hex(format!("{data:02X}"))
+}
+
+fn format_word(data: u16) -> String {
+    format_hex(format!("{data:04X}"))
+}
+
+fn format_operand(operand: Operand, next_address: u16) -> String {
+    match operand {
+        Operand::Register(register) => register_name(register).to_string(),
+        Operand::RegisterPair(register_pair) => register_pair_name(register_pair).to_string(),
+        Operand::Immediate(data) => format_byte(data),
+        Operand::ImmediateExtended(data) => format_word(data),
+        Operand::RegisterIndirect(register_pair) => {
+            format!("({})", register_pair_name(register_pair))
+        }
+        Operand::Indexed(register_pair, displacement) => {
+            let sign = if displacement < 0 { '-' } else { '+' };
+            format!(
+                "({}{}{})",
+                register_pair_name(register_pair),
+                sign,
+                format_byte(displacement.unsigned_abs())
+            )
+        }
+        Operand::Extended(address) => format!("({})", format_word(address)),
+        Operand::Relative(displacement) => {
+            format_word(next_address.wrapping_add_signed(displacement as i16))
+        }
+        Operand::Port(port) => format!("({})", format_byte(port)),
+        Operand::PortC => "(C)".to_string(),
+    }
+}
+
+fn register_name(register: Register) -> &'static str {
+    match register {
+        Register::A => "A",
+        Register::B => "B",
+        Register::C => "C",
+        Register::D => "D",
+        Register::E => "E",
+        Register::H => "H",
+        Register::L => "L",
+        Register::I => "I",
+        Register::R => "R",
+        Register::Ixh => "IXH",
+        Register::Ixl => "IXL",
+        Register::Iyh => "IYH",
+        Register::Iyl => "IYL",
+    }
+}
+
+fn register_pair_name(register_pair: RegisterPair) -> &'static str {
+    match register_pair {
+        RegisterPair::Af => "AF",
+        RegisterPair::AfPrime => "AF'",
+        RegisterPair::Bc => "BC",
+        RegisterPair::De => "DE",
+        RegisterPair::Hl => "HL",
+        RegisterPair::Sp => "SP",
+        RegisterPair::Ix => "IX",
+        RegisterPair::Iy => "IY",
+    }
+}
+
+fn condition_name(condition: Condition) -> &'static str {
+    match condition {
+        Condition::Nz => "NZ",
+        Condition::Z => "Z",
+        Condition::Nc => "NC",
+        Condition::C => "C",
+        Condition::Po => "PO",
+        Condition::Pe => "PE",
+        Condition::P => "P",
+        Condition::M => "M",
+    }
+}
+
+fn shift_mnemonic(shift: Shift) -> &'static str {
+    match shift {
+        Shift::Rlc => "RLC",
+        Shift::Rrc => "RRC",
+        Shift::Rl => "RL",
+        Shift::Rr => "RR",
+        Shift::Sla => "SLA",
+        Shift::Sra => "SRA",
+        Shift::Sll => "SLL",
+        Shift::Srl => "SRL",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::tests::Ram;
+
+    use super::*;
+
+    #[test]
+    fn test_disassemble() {
+        let mut bytes = [
+            0xF3, // DI
+            0xAF, // XOR A
+            0x11, 0xFF, 0xFF, // LD DE, 0FFFFh
+            0xC3, 0xCB, 0x11, // JP 11CBh
+            0x2A, 0x5D, 0x5C, // LD HL, (5C5Dh)
+            0x18, 0xFE, // JR 000Bh
+            0x00, 0x00, 0x00,
+        ];
+        let ram = &Ram::new(&mut bytes);
+
+        let listing: Vec<String> = disassemble(ram, 0x0000..=0x000B)
+            .iter()
+            .map(|instruction| instruction.to_string())
+            .collect();
+
+        assert_eq!(
+            vec![
+                "0000  F3           DI",
+                "0001  AF           XOR A",
+                "0002  11 FF FF     LD DE, 0FFFFh",
+                "0005  C3 CB 11     JP 11CBh",
+                "0008  2A 5D 5C     LD HL, (5C5Dh)",
+                "000B  18 FE        JR 000Bh",
+            ],
+            listing
+        );
+    }
+
+    #[test]
+    fn test_disassembled_instruction() {
+        let scenarios = [
+            (&[0x08][..], "EX", "AF, AF'"),
+            (&[0xFF], "RST", "38h"),
+            (&[0xDB, 0xFE], "IN", "A, (0FEh)"),
+            (&[0x20, 0x05], "JR", "NZ, 0007h"),
+            (&[0xC0], "RET", "NZ"),
+            (&[0xED, 0x70], "IN", "(C)"),
+            (&[0xED, 0x71], "OUT", "(C), 0"),
+            (&[0xED, 0x79], "OUT", "(C), A"),
+            (&[0xED, 0x5E], "IM", "2"),
+            (&[0xED, 0x00], "NOP", ""),
+            (&[0xCB, 0x36], "SLL", "(HL)"),
+            (&[0xDD, 0x7E, 0xFE], "LD", "A, (IX-02h)"),
+            (&[0xFD, 0x36, 0x7F, 0xA0], "LD", "(IY+7Fh), 0A0h"),
+            (&[0xDD, 0x6C], "LD", "IXL, IXH"),
+            (&[0xFD, 0xE9], "JP", "(IY)"),
+            (&[0xFD, 0xCB, 0x01, 0x7E], "BIT", "7, (IY+01h)"),
+            (&[0xDD, 0xCB, 0x03, 0x00], "LD", "B, RLC (IX+03h)"),
+            (&[0xDD, 0xCB, 0x03, 0xBF], "LD", "A, RES 7, (IX+03h)"),
+            (&[0xDD, 0xCB, 0x03, 0xC6], "SET", "0, (IX+03h)"),
+        ];
+
+        for (bytes, mnemonic, operands) in scenarios {
+            let mut bytes = bytes.to_vec();
+            bytes.resize(4, 0x00);
+            let ram = &Ram::new(&mut bytes);
+
+            let instruction = DisassembledInstruction::read(ram, 0x0000);
+
+            assert_eq!(mnemonic, instruction.mnemonic());
+            assert_eq!(operands, instruction.operands());
+        }
+    }
+
+    #[test]
+    fn test_disassemble_wraps_around() {
+        let mut bytes = vec![0x00; 0x10000];
+        bytes[0xFFFF] = 0xC3; // JP 1234h
+        bytes[0x0000] = 0x34;
+        bytes[0x0001] = 0x12;
+        let ram = &Ram::new(&mut bytes);
+
+        let listing = disassemble(ram, 0xFFFF..=0xFFFF);
+
+        assert_eq!(1, listing.len());
+        assert_eq!(vec![0xC3, 0x34, 0x12], listing[0].bytes);
+        assert_eq!("FFFF  C3 34 12     JP 1234h", listing[0].to_string());
+    }
+}
@@ -0,0 +1,2564 @@
+use super::{
+    register_flags::{
+        c_flag, parity, set_c_flag_with, set_h_flag, set_h_flag_with, set_n_flag, set_p_flag_with,
+        set_s_flag_with, set_x_flag_with, set_y_flag_with, set_z_flag_with, unset_c_flag,
+        unset_h_flag, unset_n_flag, S_FLAG_BITMASK, X_FLAG_BITMASK, Y_FLAG_BITMASK,
+    },
+    Z80Memory, Z80,
+};
+
+impl Z80 {
+    // 8-Bit Arithmetic Group
+
+    /// ## ADD A, s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A + s
+    ///
+    /// ### Op Code
+    ///
+    /// ADD A, r: `1 0 0 0 0 r r r`
+    ///
+    /// ADD A, n: `1 1 0 0 0 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// ADD A, (HL): `1 0 0 0 0 1 1 0`
+    ///
+    /// ADD A, (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 0 0 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// ADD A, (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 0 0 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The s operand is added to the contents of the Accumulator, and the result
+    /// is stored in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 3; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 7; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 44h and Register C contains 11h, then upon
+    /// the execution of an ADD A, C instruction, the Accumulator contains 55h.
+    fn add_a_s(&mut self, s: u8) {
+        self.add_a_s_with_carry(s, false);
+    }
+
+    pub fn add_a_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.add_a_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.add_a_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.add_a_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.add_a_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.add_a_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.add_a_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.add_a_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn add_a_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.add_a_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn add_a_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.add_a_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn add_a_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.add_a_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn add_a_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.add_a_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn add_a_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.add_a_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn add_a_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.add_a_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn add_a_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.add_a_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn add_a_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.add_a_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## ADC A, s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A + s + CY
+    ///
+    /// ### Op Code
+    ///
+    /// ADC A, r: `1 0 0 0 1 r r r`
+    ///
+    /// ADC A, n: `1 1 0 0 1 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// ADC A, (HL): `1 0 0 0 1 1 1 0`
+    ///
+    /// ADC A, (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 0 0 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// ADC A, (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 0 0 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The s operand, along with the Carry Flag (C in the F Register) is added to
+    /// the contents of the Accumulator, and the result is stored in the
+    /// Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 3; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 7; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contents are 16h, the Carry Flag is set, the HL
+    /// register pair contains 6666h, and address 6666h contains 10h, then upon
+    /// the execution of an ADC A, (HL) instruction, the Accumulator contains
+    /// 27h.
+    fn adc_a_s(&mut self, s: u8) {
+        let carry = c_flag(&self.f);
+        self.add_a_s_with_carry(s, carry);
+    }
+
+    fn add_a_s_with_carry(&mut self, s: u8, carry: bool) {
+        let a = self.a.value();
+        let sum = a as u16 + s as u16 + carry as u16;
+        let result = sum as u8;
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, (a ^ s ^ result) & 0x10 != 0);
+        set_p_flag_with(&mut self.f, (a ^ !s) & (a ^ result) & 0x80 != 0);
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, sum > 0xFF);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn adc_a_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.adc_a_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.adc_a_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.adc_a_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.adc_a_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.adc_a_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.adc_a_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.adc_a_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn adc_a_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.adc_a_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn adc_a_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.adc_a_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn adc_a_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.adc_a_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn adc_a_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.adc_a_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn adc_a_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.adc_a_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn adc_a_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.adc_a_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn adc_a_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.adc_a_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn adc_a_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.adc_a_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## SUB s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A – s
+    ///
+    /// ### Op Code
+    ///
+    /// SUB r: `1 0 0 1 0 r r r`
+    ///
+    /// SUB n: `1 1 0 1 0 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// SUB (HL): `1 0 0 1 0 1 1 0`
+    ///
+    /// SUB (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 0 1 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// SUB (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 0 1 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The s operand is subtracted from the contents of the Accumulator, and the
+    /// result is stored in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is set if borrow; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 29h and the D Register contains 11h, then
+    /// upon the execution of a SUB D instruction, the Accumulator contains 18h.
+    fn sub_s(&mut self, s: u8) {
+        let result = self.sub_a_s_with_carry(s, false);
+        self.a.set_value(result);
+    }
+
+    pub fn sub_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.sub_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn sub_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.sub_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn sub_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.sub_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn sub_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.sub_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn sub_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.sub_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn sub_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.sub_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn sub_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.sub_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn sub_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.sub_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn sub_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.sub_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn sub_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.sub_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn sub_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.sub_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn sub_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.sub_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn sub_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.sub_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn sub_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.sub_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn sub_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.sub_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## SBC A, s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A – s – CY
+    ///
+    /// ### Op Code
+    ///
+    /// SBC A, r: `1 0 0 1 1 r r r`
+    ///
+    /// SBC A, n: `1 1 0 1 1 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// SBC A, (HL): `1 0 0 1 1 1 1 0`
+    ///
+    /// SBC A, (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 0 1 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// SBC A, (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 0 1 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The s operand, along with the Carry flag (C in the F Register) is
+    /// subtracted from the contents of the Accumulator, and the result is stored
+    /// in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is set if borrow; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 16h, the carry flag is set, the HL register
+    /// pair contains 3433h, and address 3433h contains 05h, then upon the
+    /// execution of an SBC A, (HL) instruction, the Accumulator contains 10h.
+    fn sbc_a_s(&mut self, s: u8) {
+        let carry = c_flag(&self.f);
+        let result = self.sub_a_s_with_carry(s, carry);
+        self.a.set_value(result);
+    }
+
+    /// Subtracts s and the carry from the Accumulator, setting the flags as
+    /// SUB and SBC do, and returns the result without storing it so that CP
+    /// can share the same flag behaviour.
+    fn sub_a_s_with_carry(&mut self, s: u8, carry: bool) -> u8 {
+        let a = self.a.value();
+        let result = a.wrapping_sub(s).wrapping_sub(carry as u8);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, (a ^ s ^ result) & 0x10 != 0);
+        set_p_flag_with(&mut self.f, (a ^ s) & (a ^ result) & 0x80 != 0);
+        set_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, (a as u16) < s as u16 + carry as u16);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        result
+    }
+
+    pub fn sbc_a_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.sbc_a_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.sbc_a_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.sbc_a_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.sbc_a_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.sbc_a_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.sbc_a_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.sbc_a_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn sbc_a_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.sbc_a_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn sbc_a_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.sbc_a_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn sbc_a_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.sbc_a_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn sbc_a_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.sbc_a_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn sbc_a_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.sbc_a_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn sbc_a_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.sbc_a_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn sbc_a_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.sbc_a_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn sbc_a_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.sbc_a_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## AND s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A ∧ s
+    ///
+    /// ### Op Code
+    ///
+    /// AND r: `1 0 1 0 0 r r r`
+    ///
+    /// AND n: `1 1 1 0 0 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// AND (HL): `1 0 1 0 0 1 1 0`
+    ///
+    /// AND (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 1 0 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// AND (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 1 0 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// A logical AND operation is performed between the byte specified by the s
+    /// operand and the byte contained in the Accumulator; the result is stored
+    /// in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set.
+    ///
+    /// P/V is set if parity even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is reset.
+    ///
+    /// ### Example
+    ///
+    /// If Register B contains 7Bh (0111 1011) and the Accumulator contains C3h
+    /// (1100 0011), then upon the execution of an AND B instruction, the
+    /// Accumulator contains 43h (0100 0011).
+    fn and_s(&mut self, s: u8) {
+        let result = self.a.value() & s;
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(result));
+        unset_n_flag(&mut self.f);
+        unset_c_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn and_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.and_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn and_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.and_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn and_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.and_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn and_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.and_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn and_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.and_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn and_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.and_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn and_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.and_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn and_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.and_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn and_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.and_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn and_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.and_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn and_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.and_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn and_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.and_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn and_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.and_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn and_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.and_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn and_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.and_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## OR s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A ∨ s
+    ///
+    /// ### Op Code
+    ///
+    /// OR r: `1 0 1 1 0 r r r`
+    ///
+    /// OR n: `1 1 1 1 0 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// OR (HL): `1 0 1 1 0 1 1 0`
+    ///
+    /// OR (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 1 1 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// OR (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 1 1 0 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// A logical OR operation is performed between the byte specified by the s
+    /// operand and the byte contained in the Accumulator; the result is stored
+    /// in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the H Register contains 48h (0100 0100), and the Accumulator contains
+    /// 12h (0001 0010), then upon the execution of an OR H instruction, the
+    /// Accumulator contains 5Ah (0101 1010).
+    fn or_s(&mut self, s: u8) {
+        let result = self.a.value() | s;
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        unset_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(result));
+        unset_n_flag(&mut self.f);
+        unset_c_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn or_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.or_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn or_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.or_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn or_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.or_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn or_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.or_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn or_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.or_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn or_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.or_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn or_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.or_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn or_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.or_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn or_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.or_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn or_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.or_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn or_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.or_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn or_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.or_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn or_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.or_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn or_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.or_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn or_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.or_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## XOR s
+    ///
+    /// ### Operation
+    ///
+    /// A ← A ⊕ s
+    ///
+    /// ### Op Code
+    ///
+    /// XOR r: `1 0 1 0 1 r r r`
+    ///
+    /// XOR n: `1 1 1 0 1 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// XOR (HL): `1 0 1 0 1 1 1 0`
+    ///
+    /// XOR (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 1 0 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// XOR (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 1 0 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The logical exclusive-OR operation is performed between the byte
+    /// specified by the s operand and the byte contained in the Accumulator; the
+    /// result is stored in the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 96h (1001 0110), then upon the execution of
+    /// an XOR 5Dh (5Dh = 0101 1101) instruction, the Accumulator contains CBh
+    /// (1100 1011).
+    fn xor_s(&mut self, s: u8) {
+        let result = self.a.value() ^ s;
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        unset_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(result));
+        unset_n_flag(&mut self.f);
+        unset_c_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn xor_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.xor_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn xor_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.xor_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn xor_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.xor_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn xor_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.xor_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn xor_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.xor_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn xor_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.xor_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn xor_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.xor_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn xor_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.xor_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn xor_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.xor_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn xor_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.xor_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn xor_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.xor_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn xor_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.xor_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn xor_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.xor_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn xor_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.xor_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn xor_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.xor_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## CP s
+    ///
+    /// ### Operation
+    ///
+    /// A – s
+    ///
+    /// ### Op Code
+    ///
+    /// CP r: `1 0 1 1 1 r r r`
+    ///
+    /// CP n: `1 1 1 1 1 1 1 0` followed by `n n n n n n n n`
+    ///
+    /// CP (HL): `1 0 1 1 1 1 1 0`
+    ///
+    /// CP (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 0 1 1 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// CP (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 0 1 1 1 1 1 0` followed by `d d d d d d d d`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// s
+    ///
+    /// ### Description
+    ///
+    /// The contents of the s operand are compared with the contents of the
+    /// Accumulator. If there is a true compare, the Z flag is set. The execution
+    /// of this instruction does not affect the contents of the Accumulator.
+    ///
+    /// The s operand is any of r, n, (HL), (IX+d), or (IY+d).
+    ///
+    /// | Instruction | M Cycles | T States            | 4 MHz E.T. |
+    /// | ----------- | -------- | ------------------- | ---------- |
+    /// | r           | 1        | 4                   | 1.00       |
+    /// | n           | 2        | 7 (4, 3)            | 1.75       |
+    /// | (HL)        | 2        | 7 (4, 3)            | 1.75       |
+    /// | (IX+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    /// | (IY+d)      | 5        | 19 (4, 4, 3, 5, 3)  | 4.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is set if borrow; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 63h, the HL register pair contains 6000h,
+    /// and memory location 6000h contains 60h, the instruction CP (HL) results
+    /// in the P/V flag in the F Register resetting.
+    fn cp_s(&mut self, s: u8) {
+        self.sub_a_s_with_carry(s, false);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        // The undocumented flags are copied from the operand rather than the
+        // result.
+        set_y_flag_with(&mut self.f, s & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, s & X_FLAG_BITMASK != 0);
+    }
+
+    pub fn cp_a(&mut self) -> u8 {
+        let a = self.a.value();
+        self.cp_s(a);
+
+        // T states
+        4
+    }
+
+    pub fn cp_b(&mut self) -> u8 {
+        let b = self.b.value();
+        self.cp_s(b);
+
+        // T states
+        4
+    }
+
+    pub fn cp_c(&mut self) -> u8 {
+        let c = self.c.value();
+        self.cp_s(c);
+
+        // T states
+        4
+    }
+
+    pub fn cp_d(&mut self) -> u8 {
+        let d = self.d.value();
+        self.cp_s(d);
+
+        // T states
+        4
+    }
+
+    pub fn cp_e(&mut self) -> u8 {
+        let e = self.e.value();
+        self.cp_s(e);
+
+        // T states
+        4
+    }
+
+    pub fn cp_h(&mut self) -> u8 {
+        let h = self.h.value();
+        self.cp_s(h);
+
+        // T states
+        4
+    }
+
+    pub fn cp_l(&mut self) -> u8 {
+        let l = self.l.value();
+        self.cp_s(l);
+
+        // T states
+        4
+    }
+
+    pub fn cp_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.cp_s(n);
+
+        // T states
+        7
+    }
+
+    pub fn cp_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        self.cp_s(data);
+
+        // T states
+        7
+    }
+
+    pub fn cp_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.cp_s(data);
+
+        // T states
+        19
+    }
+
+    pub fn cp_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.cp_s(data);
+
+        // T states
+        19
+    }
+
+    /// Undocumented. With a DD or FD prefix the H and L operands are replaced
+    /// by the high and low bytes of IX or IY.
+    pub fn cp_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        self.cp_s(ixh);
+
+        // T states
+        8
+    }
+
+    pub fn cp_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        self.cp_s(ixl);
+
+        // T states
+        8
+    }
+
+    pub fn cp_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        self.cp_s(iyh);
+
+        // T states
+        8
+    }
+
+    pub fn cp_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        self.cp_s(iyl);
+
+        // T states
+        8
+    }
+
+    /// ## INC r
+    ///
+    /// ### Operation
+    ///
+    /// r ← r + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `0 0 r r r 1 0 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// r
+    ///
+    /// ### Description
+    ///
+    /// Register r is incremented and register r identifies any of the registers
+    /// A, B, C, D, E, H, or L.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 3; otherwise, it is reset.
+    ///
+    /// P/V is set if r was 7Fh before operation; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the D Register contains 28h, then upon the execution of an INC D
+    /// instruction, the D Register contains 29h.
+    fn inc_value(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_add(1);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, value & 0x0F == 0x0F);
+        set_p_flag_with(&mut self.f, value == 0x7F);
+        unset_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        result
+    }
+
+    pub fn inc_a(&mut self) -> u8 {
+        let a = self.inc_value(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        4
+    }
+
+    pub fn inc_b(&mut self) -> u8 {
+        let b = self.inc_value(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        4
+    }
+
+    pub fn inc_c(&mut self) -> u8 {
+        let c = self.inc_value(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        4
+    }
+
+    pub fn inc_d(&mut self) -> u8 {
+        let d = self.inc_value(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        4
+    }
+
+    pub fn inc_e(&mut self) -> u8 {
+        let e = self.inc_value(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        4
+    }
+
+    pub fn inc_h(&mut self) -> u8 {
+        let h = self.inc_value(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        4
+    }
+
+    pub fn inc_l(&mut self) -> u8 {
+        let l = self.inc_value(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        4
+    }
+
+    /// ## INC (HL)
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (HL) + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `0 0 1 1 0 1 0 0` (34)
+    ///
+    /// ### Operands
+    ///
+    /// (HL)
+    ///
+    /// ### Description
+    ///
+    /// The byte contained in the address specified by the contents of the HL
+    /// register pair is incremented.
+    ///
+    /// | M Cycles | T States     | 4 MHz E.T. |
+    /// | -------- | ------------ | ---------- |
+    /// | 3        | 11 (4, 4, 3) | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 3; otherwise, it is reset.
+    ///
+    /// P/V is set if (HL) was 7Fh before operation; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 3434h and address 3434h contains 82h,
+    /// then upon the execution of an INC (HL) instruction, memory location
+    /// 3434h contains 83h.
+    pub fn inc_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.inc_value(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        11
+    }
+
+    /// ## INC (IX+d)
+    ///
+    /// ### Operation
+    ///
+    /// (IX+d) ← (IX+d) + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `1 1 0 1 1 1 0 1` (DD) `0 0 1 1 0 1 0 0` (34) followed by `d d d d d d d d`
+    ///
+    /// ### Operands
+    ///
+    /// (IX+d)
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IX (register pair IX) are added to the
+    /// two's-complement displacement integer, d, to point to an address in
+    /// memory. The contents of this address are then incremented.
+    ///
+    /// The same instruction with an FD prefix uses Index Register IY.
+    ///
+    /// | M Cycles | T States                 | 4 MHz E.T. |
+    /// | -------- | ------------------------ | ---------- |
+    /// | 6        | 23 (4, 4, 3, 5, 4, 3)    | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 3; otherwise, it is reset.
+    ///
+    /// P/V is set if (IX+d) was 7Fh before operation; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    pub fn inc_mem_ixd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = self.inc_value(data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    pub fn inc_mem_iyd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = self.inc_value(data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. With a DD or FD prefix INC H and INC L operate on the
+    /// high and low bytes of IX or IY.
+    pub fn inc_ixh(&mut self) -> u8 {
+        let [high, low] = self.ix.to_be_bytes();
+        let high = self.inc_value(high);
+        self.ix = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn inc_ixl(&mut self) -> u8 {
+        let [high, low] = self.ix.to_be_bytes();
+        let low = self.inc_value(low);
+        self.ix = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn inc_iyh(&mut self) -> u8 {
+        let [high, low] = self.iy.to_be_bytes();
+        let high = self.inc_value(high);
+        self.iy = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn inc_iyl(&mut self) -> u8 {
+        let [high, low] = self.iy.to_be_bytes();
+        let low = self.inc_value(low);
+        self.iy = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    /// ## DEC r
+    ///
+    /// ### Operation
+    ///
+    /// r ← r – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `0 0 r r r 1 0 1`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// r
+    ///
+    /// ### Description
+    ///
+    /// The byte specified by the r operand is decremented.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4, otherwise, it is reset.
+    ///
+    /// P/V is set if r was 80h before operation; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the D Register contains byte 2Ah, then upon the execution of a DEC D
+    /// instruction, the D Register contains 29h.
+    fn dec_value(&mut self, value: u8) -> u8 {
+        let result = value.wrapping_sub(1);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, value & 0x0F == 0x00);
+        set_p_flag_with(&mut self.f, value == 0x80);
+        set_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        result
+    }
+
+    pub fn dec_a(&mut self) -> u8 {
+        let a = self.dec_value(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        4
+    }
+
+    pub fn dec_b(&mut self) -> u8 {
+        let b = self.dec_value(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        4
+    }
+
+    pub fn dec_c(&mut self) -> u8 {
+        let c = self.dec_value(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        4
+    }
+
+    pub fn dec_d(&mut self) -> u8 {
+        let d = self.dec_value(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        4
+    }
+
+    pub fn dec_e(&mut self) -> u8 {
+        let e = self.dec_value(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        4
+    }
+
+    pub fn dec_h(&mut self) -> u8 {
+        let h = self.dec_value(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        4
+    }
+
+    pub fn dec_l(&mut self) -> u8 {
+        let l = self.dec_value(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        4
+    }
+
+    /// ## DEC (HL)
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (HL) – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `0 0 1 1 0 1 0 1` (35)
+    ///
+    /// ### Operands
+    ///
+    /// (HL)
+    ///
+    /// ### Description
+    ///
+    /// The byte contained in the address specified by the contents of the HL
+    /// register pair is decremented.
+    ///
+    /// | M Cycles | T States     | 4 MHz E.T. |
+    /// | -------- | ------------ | ---------- |
+    /// | 3        | 11 (4, 4, 3) | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4, otherwise, it is reset.
+    ///
+    /// P/V is set if (HL) was 80h before operation; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn dec_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.dec_value(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        11
+    }
+
+    /// ## DEC (IX+d)
+    ///
+    /// ### Operation
+    ///
+    /// (IX+d) ← (IX+d) – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `1 1 0 1 1 1 0 1` (DD) `0 0 1 1 0 1 0 1` (35) followed by `d d d d d d d d`
+    ///
+    /// ### Operands
+    ///
+    /// (IX+d)
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IX (register pair IX) are added to the
+    /// two's-complement displacement integer, d, to point to an address in
+    /// memory. The contents of this address are then decremented.
+    ///
+    /// The same instruction with an FD prefix uses Index Register IY.
+    ///
+    /// | M Cycles | T States                 | 4 MHz E.T. |
+    /// | -------- | ------------------------ | ---------- |
+    /// | 6        | 23 (4, 4, 3, 5, 4, 3)    | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4, otherwise, it is reset.
+    ///
+    /// P/V is set if (IX+d) was 80h before operation; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn dec_mem_ixd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = self.dec_value(data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    pub fn dec_mem_iyd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = self.dec_value(data);
+        self.write_memory(mem, address, data);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. With a DD or FD prefix DEC H and DEC L operate on the
+    /// high and low bytes of IX or IY.
+    pub fn dec_ixh(&mut self) -> u8 {
+        let [high, low] = self.ix.to_be_bytes();
+        let high = self.dec_value(high);
+        self.ix = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn dec_ixl(&mut self) -> u8 {
+        let [high, low] = self.ix.to_be_bytes();
+        let low = self.dec_value(low);
+        self.ix = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn dec_iyh(&mut self) -> u8 {
+        let [high, low] = self.iy.to_be_bytes();
+        let high = self.dec_value(high);
+        self.iy = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+
+    pub fn dec_iyl(&mut self) -> u8 {
+        let [high, low] = self.iy.to_be_bytes();
+        let low = self.dec_value(low);
+        self.iy = u16::from_be_bytes([high, low]);
+
+        // T states
+        8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{
+            c_flag, h_flag, n_flag, p_flag, s_flag, set_c_flag, x_flag, y_flag, z_flag,
+        },
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add_a_s() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x44);
+        z80.c.set_value(0x11);
+
+        let t_states = z80.add_a_c();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0x55, z80.a.value());
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_add_a_s_overflow() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x78);
+        z80.b.set_value(0x69);
+
+        z80.add_a_b();
+
+        assert_eq!(0xE1, z80.a.value());
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_adc_a_s() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x06] = 0x10;
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x16);
+        z80.set_hl(0x0006);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.adc_a_mem_hl(ram);
+        assert_eq!(7, t_states);
+
+        assert_eq!(0x27, z80.a.value());
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_adc_a_s_carry_out() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0xFF);
+        z80.e.set_value(0x00);
+        set_c_flag(&mut z80.f);
+
+        z80.adc_a_e();
+
+        assert_eq!(0x00, z80.a.value());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sub_s() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x29);
+        z80.d.set_value(0x11);
+
+        z80.sub_d();
+
+        assert_eq!(0x18, z80.a.value());
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sub_s_overflow_and_borrow() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x80);
+        z80.l.set_value(0x01);
+
+        z80.sub_l();
+
+        assert_eq!(0x7F, z80.a.value());
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+
+        z80.a.set_value(0x00);
+        z80.sub_l();
+
+        assert_eq!(0xFF, z80.a.value());
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sbc_a_s() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x03] = 0x05;
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x16);
+        z80.set_hl(0x0003);
+        set_c_flag(&mut z80.f);
+
+        z80.sbc_a_mem_hl(ram);
+
+        assert_eq!(0x10, z80.a.value());
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_and_s() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0xC3);
+        z80.b.set_value(0x7B);
+
+        z80.and_b();
+
+        assert_eq!(0x43, z80.a.value());
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_or_s() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x12);
+        z80.h.set_value(0x48);
+
+        z80.or_h();
+
+        assert_eq!(0x5A, z80.a.value());
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_xor_s() {
+        let mut bytes = [0x5D];
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x96);
+
+        let t_states = z80.xor_n(ram);
+        assert_eq!(7, t_states);
+
+        assert_eq!(0xCB, z80.a.value());
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+
+        z80.xor_a();
+
+        assert_eq!(0x00, z80.a.value());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cp_s() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x0A] = 0x60;
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x63);
+        z80.set_hl(0x000A);
+
+        z80.cp_mem_hl(ram);
+
+        assert_eq!(0x63, z80.a.value());
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(true, n_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cp_s_undocumented_flags_from_operand() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x28);
+        z80.c.set_value(0x28);
+
+        z80.cp_c();
+
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+
+        z80.a.set_value(0x00);
+        z80.c.set_value(0x01);
+
+        z80.cp_c();
+
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_alu_mem_index_d() {
+        let mut bytes = [0x02, 0xFE, 0x00, 0x00, 0x00, 0x05];
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x10);
+        z80.ix = 0x0003;
+        z80.iy = 0x0007;
+
+        let t_states = z80.add_a_mem_ixd(ram);
+        assert_eq!(19, t_states);
+        assert_eq!(0x15, z80.a.value());
+
+        let t_states = z80.sub_mem_iyd(ram);
+        assert_eq!(19, t_states);
+        assert_eq!(0x10, z80.a.value());
+        assert_eq!(2, z80.program_counter);
+    }
+
+    #[test]
+    fn test_alu_index_half() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x0F);
+        z80.ix = 0x2834;
+        z80.iy = 0xF0AA;
+
+        let t_states = z80.and_ixl();
+        assert_eq!(8, t_states);
+        assert_eq!(0x04, z80.a.value());
+
+        z80.or_iyh();
+        assert_eq!(0xF4, z80.a.value());
+
+        z80.cp_ixh();
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_inc_r() {
+        let z80 = &mut Z80::new();
+        z80.d.set_value(0x28);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.inc_d();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0x29, z80.d.value());
+        assert_eq!(true, c_flag(&z80.f));
+
+        z80.d.set_value(0x7F);
+        z80.inc_d();
+
+        assert_eq!(0x80, z80.d.value());
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_dec_r() {
+        let z80 = &mut Z80::new();
+        z80.d.set_value(0x2A);
+
+        z80.dec_d();
+
+        assert_eq!(0x29, z80.d.value());
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+
+        z80.d.set_value(0x80);
+        z80.dec_d();
+
+        assert_eq!(0x7F, z80.d.value());
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_inc_dec_mem() {
+        let mut bytes = [0xFF, 0x00, 0x00, 0x82];
+        let ram = &mut Ram::new(&mut bytes);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0003);
+        z80.ix = 0x0004;
+
+        let t_states = z80.inc_mem_hl(ram);
+        assert_eq!(11, t_states);
+        assert_eq!(0x83, ram.read(0x0003));
+
+        let t_states = z80.dec_mem_ixd(ram);
+        assert_eq!(23, t_states);
+        assert_eq!(0x82, ram.read(0x0003));
+
+        let t_states = z80.dec_mem_hl(ram);
+        assert_eq!(11, t_states);
+        assert_eq!(0x81, ram.read(0x0003));
+    }
+
+    #[test]
+    fn test_inc_dec_index_half() {
+        let z80 = &mut Z80::new();
+        z80.ix = 0x12FF;
+        z80.iy = 0x0034;
+
+        let t_states = z80.inc_ixl();
+        assert_eq!(8, t_states);
+        assert_eq!(0x1200, z80.ix);
+        assert_eq!(true, z_flag(&z80.f));
+
+        z80.dec_iyh();
+        assert_eq!(0xFF34, z80.iy);
+        assert_eq!(true, s_flag(&z80.f));
+    }
+}
@@ -291,7 +291,6 @@ impl Z80 {
     /// ### Condition Bits Affected
     ///
     /// None.
-
     fn ld_r_n(r: &mut Register, n: u8) -> u8 {
         r.set_value(n);
 
@@ -299,38 +298,38 @@ impl Z80 {
         7
     }
 
-    pub fn ld_a_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_a_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.a, n)
     }
 
-    pub fn ld_b_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_b_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.b, n)
     }
 
-    pub fn ld_c_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_c_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.c, n)
     }
 
-    pub fn ld_d_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_d_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.d, n)
     }
 
-    pub fn ld_e_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_e_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.e, n)
     }
 
-    pub fn ld_h_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_h_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.h, n)
     }
 
-    pub fn ld_l_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
+    pub fn ld_l_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
         Z80::ld_r_n(&mut self.l, n)
     }
 
@@ -373,47 +372,46 @@ impl Z80 {
     /// If register pair HL contains the number 75A1h, and memory address 75A1h
     /// contains byte 58h, the execution of LD C, (HL) results in 58h in
     /// Register C.
-    fn ld_r_mem_hl(r: &mut Register, hl: u16, mem: &dyn Z80Memory) -> u8 {
-        let data = mem.read(hl);
+    fn ld_r_mem_hl(r: &mut Register, data: u8) -> u8 {
         r.set_value(data);
 
         // T states
         7
     }
 
-    pub fn ld_a_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.a, hl, mem)
+    pub fn ld_a_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.a, data)
     }
 
-    pub fn ld_b_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.b, hl, mem)
+    pub fn ld_b_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.b, data)
     }
 
-    pub fn ld_c_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.c, hl, mem)
+    pub fn ld_c_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.c, data)
     }
 
-    pub fn ld_d_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.d, hl, mem)
+    pub fn ld_d_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.d, data)
     }
 
-    pub fn ld_e_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.e, hl, mem)
+    pub fn ld_e_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.e, data)
     }
 
-    pub fn ld_h_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.h, hl, mem)
+    pub fn ld_h_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.h, data)
     }
 
-    pub fn ld_l_mem_hl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let hl = self.hl();
-        Z80::ld_r_mem_hl(&mut self.l, hl, mem)
+    pub fn ld_l_mem_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.hl());
+        Z80::ld_r_mem_hl(&mut self.l, data)
     }
 
     /// ## LD r, (IX+d)
@@ -464,49 +462,53 @@ impl Z80 {
     /// (IX+19h) allows the calculation of the sum 25AFh + 19h, which points to
     /// memory location 25C8h. If this address contains byte 39h, the
     /// instruction results in Register B also containing 39h.
-    fn ld_r_mem_ixd(r: &mut Register, ix: &u16, d: u8, mem: &dyn Z80Memory) -> u8 {
-        let displacement = i8::from_ne_bytes(d.to_ne_bytes());
-        let address = ix.wrapping_add_signed(displacement as i16);
-        let data = mem.read(address);
+    fn ld_r_mem_ixd(r: &mut Register, data: u8) -> u8 {
         r.set_value(data);
 
         // T states
         19
     }
 
-    pub fn ld_a_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.a, &self.ix, d, mem)
+    pub fn ld_a_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.a, data)
     }
 
-    pub fn ld_b_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.b, &self.ix, d, mem)
+    pub fn ld_b_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.b, data)
     }
 
-    pub fn ld_c_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.c, &self.ix, d, mem)
+    pub fn ld_c_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.c, data)
     }
 
-    pub fn ld_d_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.d, &self.ix, d, mem)
+    pub fn ld_d_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.d, data)
     }
 
-    pub fn ld_e_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.e, &self.ix, d, mem)
+    pub fn ld_e_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.e, data)
     }
 
-    pub fn ld_h_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.h, &self.ix, d, mem)
+    pub fn ld_h_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.h, data)
     }
 
-    pub fn ld_l_mem_ixd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_ixd(&mut self.l, &self.ix, d, mem)
+    pub fn ld_l_mem_ixd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_ixd(&mut self.l, data)
     }
 
     /// ## LD r, (IY+d)
@@ -557,49 +559,53 @@ impl Z80 {
     /// (IY+19h) allows the calculation of the sum 25AFh + 19h, which points to
     /// memory location 25C8h. If this address contains byte 39h, the
     /// instruction results in Register B also containing 39h.
-    fn ld_r_mem_iyd(r: &mut Register, iy: &u16, d: u8, mem: &dyn Z80Memory) -> u8 {
-        let displacement = i8::from_ne_bytes(d.to_ne_bytes());
-        let address = iy.wrapping_add_signed(displacement as i16);
-        let data = mem.read(address);
+    fn ld_r_mem_iyd(r: &mut Register, data: u8) -> u8 {
         r.set_value(data);
 
         // T states
         19
     }
 
-    pub fn ld_a_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.a, &self.iy, d, mem)
+    pub fn ld_a_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.a, data)
     }
 
-    pub fn ld_b_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.b, &self.iy, d, mem)
+    pub fn ld_b_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.b, data)
     }
 
-    pub fn ld_c_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.c, &self.iy, d, mem)
+    pub fn ld_c_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.c, data)
     }
 
-    pub fn ld_d_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.d, &self.iy, d, mem)
+    pub fn ld_d_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.d, data)
     }
 
-    pub fn ld_e_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.e, &self.iy, d, mem)
+    pub fn ld_e_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.e, data)
     }
 
-    pub fn ld_h_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.h, &self.iy, d, mem)
+    pub fn ld_h_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.h, data)
     }
 
-    pub fn ld_l_mem_iyd(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_r_mem_iyd(&mut self.l, &self.iy, d, mem)
+    pub fn ld_l_mem_iyd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        let data = self.read_memory(mem, address);
+        Z80::ld_r_mem_iyd(&mut self.l, data)
     }
 
     /// ## LD (HL), r
@@ -647,39 +653,39 @@ impl Z80 {
     /// If the contents of register pair HL specify memory location 2146h and
     /// Register B contains byte 29h, then upon the execution of an LD (HL), B
     /// instruction, memory address 2146h also contains 29h.
-    fn ld_mem_hl_r(hl: u16, r: &Register, mem: &mut dyn Z80Memory) -> u8 {
-        mem.write(hl, r.value());
+    fn ld_mem_hl_r(&mut self, hl: u16, data: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.write_memory(mem, hl, data);
 
         // T states
         7
     }
 
-    pub fn ld_mem_hl_a(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.a, mem)
+    pub fn ld_mem_hl_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.a.value(), mem)
     }
 
-    pub fn ld_mem_hl_b(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.b, mem)
+    pub fn ld_mem_hl_b(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.b.value(), mem)
     }
 
-    pub fn ld_mem_hl_c(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.c, mem)
+    pub fn ld_mem_hl_c(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.c.value(), mem)
     }
 
-    pub fn ld_mem_hl_d(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.d, mem)
+    pub fn ld_mem_hl_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.d.value(), mem)
     }
 
-    pub fn ld_mem_hl_e(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.e, mem)
+    pub fn ld_mem_hl_e(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.e.value(), mem)
     }
 
-    pub fn ld_mem_hl_h(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.h, mem)
+    pub fn ld_mem_hl_h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.h.value(), mem)
     }
 
-    pub fn ld_mem_hl_l(&self, mem: &mut dyn Z80Memory) -> u8 {
-        Z80::ld_mem_hl_r(self.hl(), &self.l, mem)
+    pub fn ld_mem_hl_l(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ld_mem_hl_r(self.hl(), self.l.value(), mem)
     }
 
     /// ## LD (IX+d), r
@@ -729,48 +735,46 @@ impl Z80 {
     /// If the C register contains byte 1Ch, and Index Register IX contains
     /// 3100h, then the instruction LID (IX + 6h), C performs the sum 3100h + 6h
     /// and loads 1Ch to memory location 3106h.
-    fn ld_mem_ixd_r(ix: u16, d: u8, r: &Register, mem: &mut dyn Z80Memory) -> u8 {
-        let displacement = i8::from_ne_bytes(d.to_ne_bytes());
-        let address = ix.wrapping_add_signed(displacement as i16);
-        mem.write(address, r.value());
+    fn ld_mem_ixd_r(&mut self, address: u16, data: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.write_memory(mem, address, data);
 
         // T states
         19
     }
 
-    pub fn ld_mem_ixd_a(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.a, mem)
+    pub fn ld_mem_ixd_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.a.value(), mem)
     }
 
-    pub fn ld_mem_ixd_b(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.b, mem)
+    pub fn ld_mem_ixd_b(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.b.value(), mem)
     }
 
-    pub fn ld_mem_ixd_c(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.c, mem)
+    pub fn ld_mem_ixd_c(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.c.value(), mem)
     }
 
-    pub fn ld_mem_ixd_d(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.d, mem)
+    pub fn ld_mem_ixd_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.d.value(), mem)
     }
 
-    pub fn ld_mem_ixd_e(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.e, mem)
+    pub fn ld_mem_ixd_e(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.e.value(), mem)
     }
 
-    pub fn ld_mem_ixd_h(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.h, mem)
+    pub fn ld_mem_ixd_h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.h.value(), mem)
     }
 
-    pub fn ld_mem_ixd_l(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_ixd_r(self.ix, d, &self.l, mem)
+    pub fn ld_mem_ixd_l(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.ix, mem);
+        self.ld_mem_ixd_r(address, self.l.value(), mem)
     }
 
     /// ## LD (IY+d), r
@@ -820,48 +824,46 @@ impl Z80 {
     /// If the C register contains byte 1Ch, and Index Register IY contains
     /// 3100h, then the instruction LID (IY + 6h), C performs the sum 3100h + 6h
     /// and loads 1Ch to memory location 3106h.
-    fn ld_mem_iyd_r(iy: u16, d: u8, r: &Register, mem: &mut dyn Z80Memory) -> u8 {
-        let displacement = i8::from_ne_bytes(d.to_ne_bytes());
-        let address = iy.wrapping_add_signed(displacement as i16);
-        mem.write(address, r.value());
+    fn ld_mem_iyd_r(&mut self, address: u16, data: u8, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.write_memory(mem, address, data);
 
         // T states
         19
     }
 
-    pub fn ld_mem_iyd_a(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.a, mem)
+    pub fn ld_mem_iyd_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.a.value(), mem)
     }
 
-    pub fn ld_mem_iyd_b(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.b, mem)
+    pub fn ld_mem_iyd_b(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.b.value(), mem)
     }
 
-    pub fn ld_mem_iyd_c(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.c, mem)
+    pub fn ld_mem_iyd_c(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.c.value(), mem)
     }
 
-    pub fn ld_mem_iyd_d(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.d, mem)
+    pub fn ld_mem_iyd_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.d.value(), mem)
     }
 
-    pub fn ld_mem_iyd_e(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.e, mem)
+    pub fn ld_mem_iyd_e(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.e.value(), mem)
     }
 
-    pub fn ld_mem_iyd_h(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.h, mem)
+    pub fn ld_mem_iyd_h(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.h.value(), mem)
     }
 
-    pub fn ld_mem_iyd_l(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let d = self.fetch_next_opcode(mem);
-        Z80::ld_mem_iyd_r(self.iy, d, &self.l, mem)
+    pub fn ld_mem_iyd_l(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_index_d_address(self.iy, mem);
+        self.ld_mem_iyd_r(address, self.l.value(), mem)
     }
 
     /// ## LD (HL), n
@@ -897,12 +899,12 @@ impl Z80 {
     ///
     /// If the HL register pair contains 4444h, the instruction LD (HL), 28h
     /// results in the memory location 4444h containing byte 28h.
-    pub fn ld_mem_hl_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let n = self.fetch_next_opcode(mem);
-        mem.write(self.hl(), n);
+    pub fn ld_mem_hl_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        self.write_memory(mem, self.hl(), n);
 
         // T states
-        3
+        10
     }
 
     /// ## LD (IX+d), n
@@ -941,11 +943,12 @@ impl Z80 {
     /// If Index Register IX contains the number 219Ah, then upon execution of
     /// an LD (IX+5h), 5Ah instruction, byte 5Ah is contained in memory address
     /// 219Fh.
-    pub fn ld_mem_ixd_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let displacement = self.fetch_next_opcode(mem);
-        let n = self.fetch_next_opcode(mem);
-        let address = self.ix.wrapping_add_signed(displacement as i16);
-        mem.write(address, n);
+    pub fn ld_mem_ixd_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let d = self.fetch_next_byte(mem);
+        let n = self.fetch_next_byte(mem);
+        self.internal_cycles(mem, 2);
+        let address = self.index_d_address(self.ix, d);
+        self.write_memory(mem, address, n);
 
         // T states
         19
@@ -987,11 +990,12 @@ impl Z80 {
     /// If Index Register IY contains the number 219Ah, then upon execution of
     /// an LD (IY+5h), 5Ah instruction, byte 5Ah is contained in memory address
     /// 219Fh.
-    pub fn ld_mem_iyd_n(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let displacement = self.fetch_next_opcode(mem);
-        let n = self.fetch_next_opcode(mem);
-        let address = self.iy.wrapping_add_signed(displacement as i16);
-        mem.write(address, n);
+    pub fn ld_mem_iyd_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let d = self.fetch_next_byte(mem);
+        let n = self.fetch_next_byte(mem);
+        self.internal_cycles(mem, 2);
+        let address = self.index_d_address(self.iy, d);
+        self.write_memory(mem, address, n);
 
         // T states
         19
@@ -1030,8 +1034,11 @@ impl Z80 {
     /// If the BC register pair contains the number 4747h, and memory address
     /// 4747h contains byte 12h, then the instruction LD A, (BC) results in byte
     /// 12h in Register A.
-    pub fn ld_a_mem_bc(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.a.set_value(mem.read(self.bc()));
+    pub fn ld_a_mem_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.bc();
+        let data = self.read_memory(mem, address);
+        self.a.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         7
@@ -1070,8 +1077,11 @@ impl Z80 {
     /// If the DE register pair contains the number 30A2h, and memory address
     /// 30A2h contains byte 22h, then the instruction LD A, (DE) results in byte
     /// 22h in Register A.
-    pub fn ld_a_mem_de(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.a.set_value(mem.read(self.de()));
+    pub fn ld_a_mem_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.de();
+        let data = self.read_memory(mem, address);
+        self.a.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         7
@@ -1112,12 +1122,14 @@ impl Z80 {
     /// If nn contains 8832h and memory address 8832h contains byte 04h, then upon
     /// the execution of an LD A, (nn) instruction, the 04h byte is in the
     /// Accumulator.
-    pub fn ld_a_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let nl = self.fetch_next_opcode(mem);
-        let nh = self.fetch_next_opcode(mem);
+    pub fn ld_a_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let nl = self.fetch_next_byte(mem);
+        let nh = self.fetch_next_byte(mem);
 
         let address = ((nh as u16) << 8) | nl as u16;
-        self.a.set_value(mem.read(address));
+        let data = self.read_memory(mem, address);
+        self.a.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         13
@@ -1155,8 +1167,10 @@ impl Z80 {
     ///
     /// If the Accumulator contains 7Ah and the BC register pair contains 1212h
     /// the instruction LD (BC), A results in 7Ah in memory location 1212h.
-    pub fn ld_mem_bc_a(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        mem.write(self.bc(), self.a.value());
+    pub fn ld_mem_bc_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.bc();
+        self.write_memory(mem, address, self.a.value());
+        self.set_memptr_after_store_a(address);
 
         // T states
         7
@@ -1195,8 +1209,10 @@ impl Z80 {
     /// If register pair DE contains 1128h and the Accumulator contains byte
     /// A0h, then the execution of a LD (DE), A instruction results in A0h being
     /// stored in memory location 1128h.
-    pub fn ld_mem_de_a(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        mem.write(self.de(), self.a.value());
+    pub fn ld_mem_de_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.de();
+        self.write_memory(mem, address, self.a.value());
+        self.set_memptr_after_store_a(address);
 
         // T states
         7
@@ -1237,16 +1253,24 @@ impl Z80 {
     ///
     /// If the Accumulator contains byte D7h, then executing an LD (3141h), AD7h
     /// instruction results in memory location 3141h.
-    pub fn ld_mem_nn_a(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let nl = self.fetch_next_opcode(mem);
-        let nh = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_a(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let nl = self.fetch_next_byte(mem);
+        let nh = self.fetch_next_byte(mem);
         let address = ((nh as u16) << 8) | nl as u16;
-        mem.write(address, self.a.value());
+        self.write_memory(mem, address, self.a.value());
+        self.set_memptr_after_store_a(address);
 
         // T states
         13
     }
 
+    /// Storing A leaves the low byte of the next address in the low byte of
+    /// MEMPTR, and A in the high byte.
+    fn set_memptr_after_store_a(&mut self, address: u16) {
+        let [_, low] = address.wrapping_add(1).to_be_bytes();
+        self.memptr = u16::from_be_bytes([self.a.value(), low]);
+    }
+
     /// ## LD A, I
     ///
     /// ### Operation
@@ -1280,7 +1304,8 @@ impl Z80 {
     /// N is reset.
     /// C is not affected.
     /// If an interrupt occurs during execution of this instruction, the Parity flag contains a 0.
-    pub fn ld_a_i(&mut self) -> u8 {
+    pub fn ld_a_i(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.a.set_value(self.i.value());
 
         // S is set if the I Register is negative; otherwise, it is reset.
@@ -1298,8 +1323,11 @@ impl Z80 {
         // N is reset.
         unset_n_flag(&mut self.f);
 
-        // TODO: If an interrupt occurs during execution of this instruction,
-        // the Parity flag contains a 0.
+        // If an interrupt occurs during execution of this instruction, the
+        // Parity flag contains a 0. The interrupt is only accepted at the
+        // next instruction boundary, so P/V is reset then, on the parts that
+        // have this bug.
+        self.after_ld_a_ir = true;
 
         // T states
         9
@@ -1339,7 +1367,8 @@ impl Z80 {
     /// C is not affected.
     /// If an interrupt occurs during execution of this instruction, the parity
     /// contains a 0.
-    pub fn ld_a_r(&mut self) -> u8 {
+    pub fn ld_a_r(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.a.set_value(self.r.value());
 
         // S is set if the R-Register is negative; otherwise, it is reset.
@@ -1357,8 +1386,11 @@ impl Z80 {
         // N is reset.
         unset_n_flag(&mut self.f);
 
-        // TODO: If an interrupt occurs during execution of this instruction,
-        // the Parity flag contains a 0.
+        // If an interrupt occurs during execution of this instruction, the
+        // Parity flag contains a 0. The interrupt is only accepted at the
+        // next instruction boundary, so P/V is reset then, on the parts that
+        // have this bug.
+        self.after_ld_a_ir = true;
 
         // T states
         9
@@ -1392,7 +1424,8 @@ impl Z80 {
     /// ### Condition Bits Affected
     ///
     /// None.
-    pub fn ld_i_a(&mut self) -> u8 {
+    pub fn ld_i_a(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.i.set_value(self.a.value());
 
         // T states
@@ -1427,14 +1460,352 @@ impl Z80 {
     /// ### Condition Bits Affected
     ///
     /// None.
-    pub fn ld_r_a(&mut self) -> u8 {
+    pub fn ld_r_a(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.r.set_value(self.a.value());
 
         // T states
         9
     }
+
+    /// ## LD r, IXH / LD r, IXL / LD r, IYH / LD r, IYL
+    ///
+    /// ### Operation
+    ///
+    /// r ← IXH, r ← IXL, r ← IYH or r ← IYL
+    ///
+    /// ### Op Code
+    ///
+    /// LD: `1 1 0 1 1 1 0 1` (DD) or `1 1 1 1 1 1 0 1` (FD) `0 1 r r r 1 0 x`
+    ///
+    /// ### Operands
+    ///
+    /// r, IXH, IXL, IYH or IYL
+    ///
+    /// ### Description
+    ///
+    /// Undocumented. A DD or FD prefix on an LD r, H or LD r, L instruction
+    /// replaces H with the high byte and L with the low byte of the index
+    /// register. r identifies registers B, C, D, E or A; the H and L forms are
+    /// LD IXH, IXL and friends.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    fn ld_r_index_half(r: &mut Register, half: u8) -> u8 {
+        r.set_value(half);
+
+        // T states
+        8
+    }
+
+    pub fn ld_a_ixh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.a, (self.ix >> 8) as u8)
+    }
+
+    pub fn ld_a_ixl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.a, self.ix as u8)
+    }
+
+    pub fn ld_b_ixh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.b, (self.ix >> 8) as u8)
+    }
+
+    pub fn ld_b_ixl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.b, self.ix as u8)
+    }
+
+    pub fn ld_c_ixh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.c, (self.ix >> 8) as u8)
+    }
+
+    pub fn ld_c_ixl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.c, self.ix as u8)
+    }
+
+    pub fn ld_d_ixh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.d, (self.ix >> 8) as u8)
+    }
+
+    pub fn ld_d_ixl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.d, self.ix as u8)
+    }
+
+    pub fn ld_e_ixh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.e, (self.ix >> 8) as u8)
+    }
+
+    pub fn ld_e_ixl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.e, self.ix as u8)
+    }
+
+    pub fn ld_a_iyh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.a, (self.iy >> 8) as u8)
+    }
+
+    pub fn ld_a_iyl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.a, self.iy as u8)
+    }
+
+    pub fn ld_b_iyh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.b, (self.iy >> 8) as u8)
+    }
+
+    pub fn ld_b_iyl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.b, self.iy as u8)
+    }
+
+    pub fn ld_c_iyh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.c, (self.iy >> 8) as u8)
+    }
+
+    pub fn ld_c_iyl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.c, self.iy as u8)
+    }
+
+    pub fn ld_d_iyh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.d, (self.iy >> 8) as u8)
+    }
+
+    pub fn ld_d_iyl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.d, self.iy as u8)
+    }
+
+    pub fn ld_e_iyh(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.e, (self.iy >> 8) as u8)
+    }
+
+    pub fn ld_e_iyl(&mut self) -> u8 {
+        Z80::ld_r_index_half(&mut self.e, self.iy as u8)
+    }
+
+    /// ## LD IXH, r / LD IXL, r / LD IYH, r / LD IYL, r
+    ///
+    /// ### Operation
+    ///
+    /// IXH ← r, IXL ← r, IYH ← r or IYL ← r
+    ///
+    /// ### Op Code
+    ///
+    /// LD: `1 1 0 1 1 1 0 1` (DD) or `1 1 1 1 1 1 0 1` (FD) `0 1 1 0 x r r r`
+    ///
+    /// ### Operands
+    ///
+    /// IXH, IXL, IYH or IYL, r
+    ///
+    /// ### Description
+    ///
+    /// Undocumented. A DD or FD prefix on an LD H, r or LD L, r instruction
+    /// loads the high or low byte of the index register instead. r identifies
+    /// registers B, C, D, E, A, or the high or low byte of the same index
+    /// register.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    fn ld_index_high_r(index: &mut u16, r: u8) -> u8 {
+        *index = (*index & 0x00FF) | (r as u16) << 8;
+
+        // T states
+        8
+    }
+
+    fn ld_index_low_r(index: &mut u16, r: u8) -> u8 {
+        *index = (*index & 0xFF00) | r as u16;
+
+        // T states
+        8
+    }
+
+    pub fn ld_ixh_a(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.ix, self.a.value())
+    }
+
+    pub fn ld_ixh_b(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.ix, self.b.value())
+    }
+
+    pub fn ld_ixh_c(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.ix, self.c.value())
+    }
+
+    pub fn ld_ixh_d(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.ix, self.d.value())
+    }
+
+    pub fn ld_ixh_e(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.ix, self.e.value())
+    }
+
+    pub fn ld_ixh_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        Z80::ld_index_high_r(&mut self.ix, ixh)
+    }
+
+    pub fn ld_ixh_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        Z80::ld_index_high_r(&mut self.ix, ixl)
+    }
+
+    pub fn ld_ixl_a(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.ix, self.a.value())
+    }
+
+    pub fn ld_ixl_b(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.ix, self.b.value())
+    }
+
+    pub fn ld_ixl_c(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.ix, self.c.value())
+    }
+
+    pub fn ld_ixl_d(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.ix, self.d.value())
+    }
+
+    pub fn ld_ixl_e(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.ix, self.e.value())
+    }
+
+    pub fn ld_ixl_ixh(&mut self) -> u8 {
+        let ixh = (self.ix >> 8) as u8;
+        Z80::ld_index_low_r(&mut self.ix, ixh)
+    }
+
+    pub fn ld_ixl_ixl(&mut self) -> u8 {
+        let ixl = self.ix as u8;
+        Z80::ld_index_low_r(&mut self.ix, ixl)
+    }
+
+    pub fn ld_iyh_a(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.iy, self.a.value())
+    }
+
+    pub fn ld_iyh_b(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.iy, self.b.value())
+    }
+
+    pub fn ld_iyh_c(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.iy, self.c.value())
+    }
+
+    pub fn ld_iyh_d(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.iy, self.d.value())
+    }
+
+    pub fn ld_iyh_e(&mut self) -> u8 {
+        Z80::ld_index_high_r(&mut self.iy, self.e.value())
+    }
+
+    pub fn ld_iyh_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        Z80::ld_index_high_r(&mut self.iy, iyh)
+    }
+
+    pub fn ld_iyh_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        Z80::ld_index_high_r(&mut self.iy, iyl)
+    }
+
+    pub fn ld_iyl_a(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.iy, self.a.value())
+    }
+
+    pub fn ld_iyl_b(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.iy, self.b.value())
+    }
+
+    pub fn ld_iyl_c(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.iy, self.c.value())
+    }
+
+    pub fn ld_iyl_d(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.iy, self.d.value())
+    }
+
+    pub fn ld_iyl_e(&mut self) -> u8 {
+        Z80::ld_index_low_r(&mut self.iy, self.e.value())
+    }
+
+    pub fn ld_iyl_iyh(&mut self) -> u8 {
+        let iyh = (self.iy >> 8) as u8;
+        Z80::ld_index_low_r(&mut self.iy, iyh)
+    }
+
+    pub fn ld_iyl_iyl(&mut self) -> u8 {
+        let iyl = self.iy as u8;
+        Z80::ld_index_low_r(&mut self.iy, iyl)
+    }
+
+    /// ## LD IXH, n / LD IXL, n / LD IYH, n / LD IYL, n
+    ///
+    /// ### Operation
+    ///
+    /// IXH ← n, IXL ← n, IYH ← n or IYL ← n
+    ///
+    /// ### Op Code
+    ///
+    /// LD: `1 1 0 1 1 1 0 1` (DD) or `1 1 1 1 1 1 0 1` (FD) `0 0 1 0 x 1 1 0`
+    /// `n n n n n n n n`
+    ///
+    /// ### Operands
+    ///
+    /// IXH, IXL, IYH or IYL, n
+    ///
+    /// ### Description
+    ///
+    /// Undocumented. A DD or FD prefix on an LD H, n or LD L, n instruction
+    /// loads the integer n into the high or low byte of the index register.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (4, 4, 3)    | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn ld_ixh_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        Z80::ld_index_high_r(&mut self.ix, n);
+
+        // T states
+        11
+    }
+
+    pub fn ld_ixl_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        Z80::ld_index_low_r(&mut self.ix, n);
+
+        // T states
+        11
+    }
+
+    pub fn ld_iyh_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        Z80::ld_index_high_r(&mut self.iy, n);
+
+        // T states
+        11
+    }
+
+    pub fn ld_iyl_n(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        Z80::ld_index_low_r(&mut self.iy, n);
+
+        // T states
+        11
+    }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
@@ -1517,10 +1888,7 @@ mod tests {
 
     #[test]
     fn test_ld_r_hl() {
-        let scenarios: [(
-            fn(&mut Z80, &dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 7] = [
+        let scenarios: [(fn(&mut Z80, &_) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
             (Z80::ld_a_mem_hl, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_b_mem_hl, |z80: &mut Z80| &mut z80.b),
             (Z80::ld_c_mem_hl, |z80: &mut Z80| &mut z80.c),
@@ -1547,13 +1915,10 @@ mod tests {
 
     #[test]
     fn test_ld_r_ixd() {
-        let scenarios: [(
-            fn(&mut Z80, &dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 7] = [
+        let scenarios: [(fn(&mut Z80, &_) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
             (Z80::ld_a_mem_ixd, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_b_mem_ixd, |z80: &mut Z80| &mut z80.b),
-            (Z80::ld_c_ixd, |z80: &mut Z80| &mut z80.c),
+            (Z80::ld_c_mem_ixd, |z80: &mut Z80| &mut z80.c),
             (Z80::ld_d_mem_ixd, |z80: &mut Z80| &mut z80.d),
             (Z80::ld_e_mem_ixd, |z80: &mut Z80| &mut z80.e),
             (Z80::ld_h_mem_ixd, |z80: &mut Z80| &mut z80.h),
@@ -1570,6 +1935,7 @@ mod tests {
 
             let t_states = opcode(z80, &ram);
             assert_eq!(19, t_states);
+            assert_eq!(0x0003, z80.memptr);
 
             let register = register_supplier(z80);
             assert_eq!(0xCC, register.value());
@@ -1578,10 +1944,7 @@ mod tests {
 
     #[test]
     fn test_ld_r_iyd() {
-        let scenarios: [(
-            fn(&mut Z80, &dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 7] = [
+        let scenarios: [(fn(&mut Z80, &_) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
             (Z80::ld_a_mem_iyd, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_b_mem_iyd, |z80: &mut Z80| &mut z80.b),
             (Z80::ld_c_mem_iyd, |z80: &mut Z80| &mut z80.c),
@@ -1609,10 +1972,7 @@ mod tests {
 
     #[test]
     fn test_ld_hl_r() {
-        let scenarios: [(
-            fn(&Z80, &mut dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 5] = [
+        let scenarios: [(fn(&mut Z80, &mut _) -> u8, fn(&mut Z80) -> &mut Register); 5] = [
             (Z80::ld_mem_hl_a, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_mem_hl_b, |z80: &mut Z80| &mut z80.b),
             (Z80::ld_mem_hl_c, |z80: &mut Z80| &mut z80.c),
@@ -1669,10 +2029,7 @@ mod tests {
 
     #[test]
     fn test_ld_ixd_r() {
-        let scenarios: [(
-            fn(&mut Z80, &mut dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 7] = [
+        let scenarios: [(fn(&mut Z80, &mut _) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
             (Z80::ld_mem_ixd_a, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_mem_ixd_b, |z80: &mut Z80| &mut z80.b),
             (Z80::ld_mem_ixd_c, |z80: &mut Z80| &mut z80.c),
@@ -1700,10 +2057,7 @@ mod tests {
 
     #[test]
     fn test_ld_iyd_r() {
-        let scenarios: [(
-            fn(&mut Z80, &mut dyn Z80Memory) -> u8,
-            fn(&mut Z80) -> &mut Register,
-        ); 7] = [
+        let scenarios: [(fn(&mut Z80, &mut _) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
             (Z80::ld_mem_iyd_a, |z80: &mut Z80| &mut z80.a),
             (Z80::ld_mem_iyd_b, |z80: &mut Z80| &mut z80.b),
             (Z80::ld_mem_iyd_c, |z80: &mut Z80| &mut z80.c),
@@ -1739,7 +2093,7 @@ mod tests {
         z80.set_hl(0x0002);
         let t_states = z80.ld_mem_hl_n(ram);
 
-        assert_eq!(3, t_states);
+        assert_eq!(10, t_states);
         assert_eq!(0xFF, ram.read(2));
     }
 
@@ -1785,6 +2139,7 @@ mod tests {
 
         assert_eq!(7, t_states);
         assert_eq!(0xFF, z80.a.value());
+        assert_eq!(0x0002, z80.memptr);
     }
 
     #[test]
@@ -1813,6 +2168,7 @@ mod tests {
 
         assert_eq!(13, t_states);
         assert_eq!(0xFF, z80.a.value());
+        assert_eq!(0x0004, z80.memptr);
     }
 
     #[test]
@@ -1827,6 +2183,7 @@ mod tests {
 
         assert_eq!(7, t_states);
         assert_eq!(0xFF, ram.read(1));
+        assert_eq!(0xFF02, z80.memptr);
     }
 
     #[test]
@@ -1855,13 +2212,15 @@ mod tests {
 
         assert_eq!(13, t_states);
         assert_eq!(0xFF, ram.read(3));
+        assert_eq!(0xFF04, z80.memptr);
     }
 
     #[test]
     fn test_ld_a_i_positive() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.i.set_value(0b01111111);
-        let t_states = z80.ld_a_i();
+        let t_states = z80.ld_a_i(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.i, z80.a);
@@ -1886,9 +2245,10 @@ mod tests {
 
     #[test]
     fn test_ld_a_i_zero() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.i.set_value(0);
-        let t_states = z80.ld_a_i();
+        let t_states = z80.ld_a_i(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.i, z80.a);
@@ -1913,9 +2273,10 @@ mod tests {
 
     #[test]
     fn test_ld_a_i_negative() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.i.set_value(0b11111111);
-        let t_states = z80.ld_a_i();
+        let t_states = z80.ld_a_i(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.i, z80.a);
@@ -1940,9 +2301,10 @@ mod tests {
 
     #[test]
     fn test_ld_a_r_positive() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.r.set_value(0b01111111);
-        let t_states = z80.ld_a_r();
+        let t_states = z80.ld_a_r(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.r, z80.a);
@@ -1967,9 +2329,10 @@ mod tests {
 
     #[test]
     fn test_ld_a_r_zero() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.r.set_value(0);
-        let t_states = z80.ld_a_r();
+        let t_states = z80.ld_a_r(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.r, z80.a);
@@ -1994,9 +2357,10 @@ mod tests {
 
     #[test]
     fn test_ld_a_r_negative() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.r.set_value(0b11111111);
-        let t_states = z80.ld_a_r();
+        let t_states = z80.ld_a_r(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(z80.r, z80.a);
@@ -2021,10 +2385,11 @@ mod tests {
 
     #[test]
     fn test_ld_i_a() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.a.set_value(0xFF);
 
-        let t_states = z80.ld_i_a();
+        let t_states = z80.ld_i_a(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(0xFF, z80.i.value());
@@ -2032,12 +2397,73 @@ mod tests {
 
     #[test]
     fn test_ld_r_a() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.a.set_value(0xFF);
 
-        let t_states = z80.ld_r_a();
+        let t_states = z80.ld_r_a(ram);
         assert_eq!(9, t_states);
 
         assert_eq!(0xFF, z80.r.value());
     }
+
+    #[test]
+    fn test_ld_ixd_n_negative_displacement() {
+        let bytes = &mut [0x00, 0xFE, 0xFF, 0x00];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.program_counter = 1;
+        z80.ix = 0x0002;
+
+        let t_states = z80.ld_mem_ixd_n(ram);
+
+        assert_eq!(19, t_states);
+        assert_eq!(0xFF, ram.read(0));
+    }
+
+    #[test]
+    fn test_ld_r_index_half() {
+        let z80 = &mut Z80::new();
+        z80.ix = 0x1234;
+        z80.iy = 0x5678;
+
+        assert_eq!(8, z80.ld_b_ixh());
+        assert_eq!(0x12, z80.b.value());
+        assert_eq!(8, z80.ld_c_ixl());
+        assert_eq!(0x34, z80.c.value());
+        assert_eq!(8, z80.ld_d_iyh());
+        assert_eq!(0x56, z80.d.value());
+        assert_eq!(8, z80.ld_a_iyl());
+        assert_eq!(0x78, z80.a.value());
+    }
+
+    #[test]
+    fn test_ld_index_half_r() {
+        let z80 = &mut Z80::new();
+        z80.ix = 0x1234;
+        z80.e.set_value(0xAB);
+
+        assert_eq!(8, z80.ld_ixh_e());
+        assert_eq!(0xAB34, z80.ix);
+        assert_eq!(8, z80.ld_ixl_ixh());
+        assert_eq!(0xABAB, z80.ix);
+        assert_eq!(8, z80.ld_iyl_e());
+        assert_eq!(0x00AB, z80.iy);
+    }
+
+    #[test]
+    fn test_ld_index_half_n() {
+        let bytes = &mut [0xFD, 0x2E, 0x99];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.program_counter = 2;
+        z80.iy = 0x1234;
+
+        let t_states = z80.ld_iyl_n(ram);
+
+        assert_eq!(11, t_states);
+        assert_eq!(0x1299, z80.iy);
+    }
 }
@@ -1,8 +1,8 @@
 use super::{
     register_flags::{
-        set_h_flag, set_h_flag_with, set_n_flag, set_p_flag_with, set_s_flag_with, set_x_flag,
-        set_x_flag_with, set_y_flag_with, set_z_flag_with, unset_h_flag, unset_n_flag,
-        S_FLAG_BITMASK,
+        set_h_flag_with, set_n_flag, set_p_flag_with, set_s_flag_with, set_x_flag_with,
+        set_y_flag_with, set_z_flag_with, unset_h_flag, unset_n_flag, z_flag, S_FLAG_BITMASK,
+        X_FLAG_BITMASK, Y_FLAG_BITMASK,
     },
     Z80Memory, Z80,
 };
@@ -165,14 +165,18 @@ impl Z80 {
     /// HL register pair containing number 2211h, memory location 8856h
     /// containing byte 12h, memory location 8857h containing byte 70h and Stack
     /// Pointer containing 8856h.
-    pub fn ex_mem_sp_hl(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let mem_sp = mem.read(self.stack_pointer);
-        mem.write(self.stack_pointer, self.l.value());
-        self.l.set_value(mem_sp);
+    pub fn ex_mem_sp_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let mem_spl = self.read_memory(mem, self.stack_pointer);
+        let mem_sph = self.read_memory(mem, self.stack_pointer.wrapping_add(1));
+        self.internal_cycles(mem, 1);
 
-        let mem_sp = mem.read(self.stack_pointer + 1);
-        mem.write(self.stack_pointer + 1, self.h.value());
-        self.h.set_value(mem_sp);
+        self.write_memory(mem, self.stack_pointer.wrapping_add(1), self.h.value());
+        self.write_memory(mem, self.stack_pointer, self.l.value());
+        self.internal_cycles(mem, 2);
+
+        self.h.set_value(mem_sph);
+        self.l.set_value(mem_spl);
+        self.memptr = self.hl();
 
         // T states
         19
@@ -206,17 +210,20 @@ impl Z80 {
     /// IX register pair containing number 4890h, memory location 0100h
     /// containing 88h, memory location 0101h containing 39h, and the Stack
     /// Pointer containing 0100h.
-    pub fn ex_mem_sp_ix(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn ex_mem_sp_ix(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
         let ixl = self.ix as u8;
         let ixh = (self.ix >> 8) as u8;
 
-        let mem_spl = mem.read(self.stack_pointer);
-        let mem_sph = mem.read(self.stack_pointer.wrapping_add(1));
+        let mem_spl = self.read_memory(mem, self.stack_pointer);
+        let mem_sph = self.read_memory(mem, self.stack_pointer.wrapping_add(1));
+        self.internal_cycles(mem, 1);
 
-        mem.write(self.stack_pointer, ixl);
-        mem.write(self.stack_pointer.wrapping_add(1), ixh);
+        self.write_memory(mem, self.stack_pointer.wrapping_add(1), ixh);
+        self.write_memory(mem, self.stack_pointer, ixl);
+        self.internal_cycles(mem, 2);
 
         self.ix = (mem_sph as u16) << 8 | mem_spl as u16;
+        self.memptr = self.ix;
 
         // T states
         23
@@ -250,17 +257,20 @@ impl Z80 {
     /// IY register pair containing number 4890h, memory location 0100h
     /// containing 88h, memory location 0101h containing 39h, and the Stack
     /// Pointer containing 0100h.
-    pub fn ex_mem_sp_iy(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn ex_mem_sp_iy(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
         let iyl = self.iy as u8;
         let iyh = (self.iy >> 8) as u8;
 
-        let mem_spl = mem.read(self.stack_pointer);
-        let mem_sph = mem.read(self.stack_pointer.wrapping_add(1));
+        let mem_spl = self.read_memory(mem, self.stack_pointer);
+        let mem_sph = self.read_memory(mem, self.stack_pointer.wrapping_add(1));
+        self.internal_cycles(mem, 1);
 
-        mem.write(self.stack_pointer, iyl);
-        mem.write(self.stack_pointer.wrapping_add(1), iyh);
+        self.write_memory(mem, self.stack_pointer.wrapping_add(1), iyh);
+        self.write_memory(mem, self.stack_pointer, iyl);
+        self.internal_cycles(mem, 2);
 
         self.iy = (mem_sph as u16) << 8 | mem_spl as u16;
+        self.memptr = self.iy;
 
         // T states
         23
@@ -306,12 +316,13 @@ impl Z80 {
     /// | DE      | contains | 2223h |
     /// | (2222h) | contains | 88h   |
     /// | BC      | contains | 6H    |
-    pub fn ldi(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn ldi(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
         // Transfer data from HL address to DE address
         let hl = self.hl();
-        let data = mem.read(hl);
+        let data = self.read_memory(mem, hl);
         let de = self.de();
-        mem.write(de, data);
+        self.write_memory(mem, de, data);
+        self.internal_cycles(mem, 2);
 
         // Increment HL and DE
         self.set_hl(hl.wrapping_add(1));
@@ -394,40 +405,9 @@ impl Z80 {
     /// | (1111h) | contains | 88h   | (2222h) | contains | 88h |
     /// | (1112h) | contains | 36h   | (2223h) | contains | 36h |
     /// | (1113h) | contains | A5h   | (2224h) | contains | A5h |
-    pub fn ldir(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        // Transfer data from HL address to DE address
-        let hl = self.hl();
-        let data = mem.read(hl);
-        let de = self.de();
-        mem.write(de, data);
-
-        // Increment HL and DE
-        self.set_hl(hl.wrapping_add(1));
-        self.set_de(de.wrapping_add(1));
-
-        // Read and decrement BC
-        let bc = self.bc().wrapping_sub(1);
-        self.set_bc(bc);
-
-        let t_states;
-
-        if bc != 0 {
-            self.program_counter -= 2;
-            t_states = 21;
-        } else {
-            t_states = 16;
-        }
-
-        unset_h_flag(&mut self.f);
-        set_p_flag_with(&mut self.f, bc != 0);
-        unset_n_flag(&mut self.f);
-
-        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
-        let n = self.a.value().wrapping_add(data);
-        set_y_flag_with(&mut self.f, n & 0b00000010 != 0);
-        set_x_flag_with(&mut self.f, n & 0b00001000 != 0);
-
-        t_states
+    pub fn ldir(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ldi(mem);
+        self.repeat_block_instruction(self.bc() != 0, mem)
     }
 
     /// ## LDD
@@ -471,12 +451,13 @@ impl Z80 {
     /// | DE      | contains | 2221h |
     /// | (2222h) | contains | 88h   |
     /// | BC      | contains | 6h    |
-    pub fn ldd(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn ldd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
         // Transfer data from HL address to DE address
         let hl = self.hl();
-        let data = mem.read(hl);
+        let data = self.read_memory(mem, hl);
         let de = self.de();
-        mem.write(de, data);
+        self.write_memory(mem, de, data);
+        self.internal_cycles(mem, 2);
 
         // Increment HL and DE
         self.set_hl(hl.wrapping_sub(1));
@@ -562,40 +543,9 @@ impl Z80 {
     /// | (1114h) | contains | A5h   | (2225h) | contains | A5h |
     /// | (1113h) | contains | 36h   | (2224h) | contains | 36h |
     /// | (1112h) | contains | 88h   | (2223h) | contains | 88h |
-    pub fn lddr(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        // Transfer data from HL address to DE address
-        let hl = self.hl();
-        let data = mem.read(hl);
-        let de = self.de();
-        mem.write(de, data);
-
-        // Increment HL and DE
-        self.set_hl(hl.wrapping_sub(1));
-        self.set_de(de.wrapping_sub(1));
-
-        // Read and decrement BC
-        let bc = self.bc().wrapping_sub(1);
-        self.set_bc(bc);
-
-        let t_states;
-
-        if bc != 0 {
-            self.program_counter -= 2;
-            t_states = 21;
-        } else {
-            t_states = 16;
-        }
-
-        unset_h_flag(&mut self.f);
-        set_p_flag_with(&mut self.f, bc != 0);
-        unset_n_flag(&mut self.f);
-
-        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
-        let n = self.a.value().wrapping_add(data);
-        set_y_flag_with(&mut self.f, n & 0b00000010 != 0);
-        set_x_flag_with(&mut self.f, n & 0b00001000 != 0);
-
-        t_states
+    pub fn lddr(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.ldd(mem);
+        self.repeat_block_instruction(self.bc() != 0, mem)
     }
 
     /// ## CPI
@@ -629,15 +579,183 @@ impl Z80 {
     /// 0000h, the HL register pair contains 1112h, the Z flag in the F register
     /// is set, and the P/V flag in the F Register is reset. There is no effect
     /// on the contents of the Accumulator or to address 1111h.
-    pub fn cpi(&mut self, mem: &dyn Z80Memory) -> u8 {
+    pub fn cpi(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.block_compare(mem, 1);
+
+        // T states
+        16
+    }
+
+    /// ## CPIR
+    /// ### Operation
+    /// A – (HL), HL ← HL+1, BC ← BC – 1
+    /// ### Op Code
+    /// CPIR
+    /// ### Operands
+    /// None.
+    /// `1 1 1 0 1 1 0 1` (ED)
+    /// `1 0 1 1 0 0 0 1` (B1)
+    /// ### Description
+    /// The contents of the memory location addressed by the HL register pair
+    /// is compared with the contents of the Accumulator. During a compare
+    /// operation, a condition bit is set. HL is incremented and the Byte
+    /// Counter (register pair BC) is decremented. If decrementing causes BC to
+    /// go to 0 or if A = (HL), the instruction is terminated. If BC is not 0
+    /// and A ≠ (HL), the program counter is decremented by two and the
+    /// instruction is repeated. Interrupts are recognized and two refresh
+    /// cycles are executed after each data transfer.
+    ///
+    /// If BC is set to 0 before instruction execution, the instruction loops
+    /// through 64 KB if no match is found.
+    ///
+    /// For BC ≠ 0 and A ≠ (HL):
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 5        | 21 (4, 4, 3, 5, 5) | 5.25       |
+    ///
+    /// For BC = 0 or A = (HL):
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 4        | 16 (4, 4, 3, 5) | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    /// * S is set if the result is negative; otherwise, it is reset.
+    /// * Z is set if A equals (HL); otherwise, it is reset.
+    /// * H is set if borrow from bit 4; otherwise, it is reset.
+    /// * P/V is set if BC – 1 does not equal 0; otherwise, it is reset.
+    /// * N is set.
+    /// * C is not affected.
+    /// ### Example
+    /// If the HL register pair contains 1111h, the Accumulator contains F3h,
+    /// the Byte Counter contains 0007h, and memory locations contain the
+    /// following data.
+    ///
+    /// |         |          |     |
+    /// | ------- | -------- | --- |
+    /// | (1111h) | contains | 52h |
+    /// | (1112h) | contains | 00h |
+    /// | (1113h) | contains | F3h |
+    ///
+    /// Upon the execution of a CPIR instruction, the HL register pair contains
+    /// 1114h, the Byte Counter contains 0004h, the P/V flag in the F register
+    /// is set, and the Z flag in the F register is set.
+    pub fn cpir(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.block_compare(mem, 1);
+        self.repeat_block_instruction(self.bc() != 0 && !z_flag(&self.f), mem)
+    }
+
+    /// ## CPD
+    /// ### Operation
+    /// A – (HL), HL ← HL – 1, BC ← BC – 1
+    /// ### Op Code
+    /// CPD
+    /// ### Operands
+    /// None.
+    /// `1 1 1 0 1 1 0 1` (ED)
+    /// `1 0 1 0 1 0 0 1` (A9)
+    /// ### Description
+    /// The contents of the memory location addressed by the HL register pair
+    /// is compared with the contents of the Accumulator. During the compare
+    /// operation, a condition bit is set. The HL and Byte Counter (register
+    /// pair BC) are decremented.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 4        | 16 (4, 4, 3, 5) | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    /// * S is set if the result is negative; otherwise, it is reset.
+    /// * Z is set if A equals (HL); otherwise, it is reset.
+    /// * H is set if borrow from bit 4; otherwise, it is reset.
+    /// * P/V is set if BC – 1 ≠ 0; otherwise, it is reset.
+    /// * N is set.
+    /// * C is not affected.
+    /// ### Example
+    /// If the HL register pair contains 1111h, memory location 1111h contains
+    /// 3Bh, the Accumulator contains 3Bh, and the Byte Counter contains 0001h.
+    /// Upon the execution of a CPD instruction, the Byte Counter contains
+    /// 0000h, the HL register pair contains 1110h, the flag in the F register
+    /// is set, and the P/V flag in the F register is reset. There is no effect
+    /// on the contents of the Accumulator or address 1111h.
+    pub fn cpd(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.block_compare(mem, -1);
+
+        // T states
+        16
+    }
+
+    /// ## CPDR
+    /// ### Operation
+    /// A – (HL), HL ← HL – 1, BC ← BC – 1
+    /// ### Op Code
+    /// CPDR
+    /// ### Operands
+    /// None.
+    /// `1 1 1 0 1 1 0 1` (ED)
+    /// `1 0 1 1 1 0 0 1` (B9)
+    /// ### Description
+    /// The contents of the memory location addressed by the HL register pair
+    /// is compared with the contents of the Accumulator. During the compare
+    /// operation, a condition bit is set. The HL and Byte Counter (BC) Register
+    /// pairs are decremented. If decrementing allows the BC to go to 0 or if
+    /// A = (HL), the instruction is terminated. If BC is not 0 and A ≠ (HL),
+    /// the program counter is decremented by two and the instruction is
+    /// repeated. Interrupts are recognized and two refresh cycles execute after
+    /// each data transfer. When the BC is set to 0, prior to instruction
+    /// execution, the instruction loops through 64 KB if no match is found.
+    ///
+    /// For BC ≠ 0 and A ≠ (HL):
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 5        | 21 (4, 4, 3, 5, 5) | 5.25       |
+    ///
+    /// For BC = 0 or A = (HL):
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 4        | 16 (4, 4, 3, 5) | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    /// * S is set if the result is negative; otherwise, it is reset.
+    /// * Z is set if A = (HL); otherwise, it is reset.
+    /// * H is set if borrow from bit 4; otherwise, it is reset.
+    /// * P/V is set if BC – 1 ≠ 0; otherwise, it is reset.
+    /// * N is set.
+    /// * C is not affected.
+    /// ### Example
+    /// If the HL register pair contains 1118h, the Accumulator contains F3h,
+    /// the Byte Counter contains 0007h, and memory locations contain the
+    /// following data.
+    ///
+    /// |         |          |     |
+    /// | ------- | -------- | --- |
+    /// | (1118h) | contains | 52h |
+    /// | (1117h) | contains | 00h |
+    /// | (1116h) | contains | F3h |
+    ///
+    /// Upon the execution of a CPDR instruction, the HL register pair contains
+    /// 1115h, the Byte Counter contains 0004h, the P/V flag in the F register
+    /// is set, and the Z flag in the F register is set.
+    pub fn cpdr(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.block_compare(mem, -1);
+        self.repeat_block_instruction(self.bc() != 0 && !z_flag(&self.f), mem)
+    }
+
+    /// Compares A with (HL), moves HL by `step` and decrements BC, for CPI,
+    /// CPD, CPIR and CPDR.
+    fn block_compare(&mut self, mem: &(impl Z80Memory + ?Sized), step: i16) {
         let address = self.hl();
-        let data = mem.read(address);
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 5);
 
         let a = self.a.value();
         let n = a.wrapping_sub(data);
 
-        let hl = address.wrapping_add(1);
-        self.set_hl(hl);
+        self.set_hl(address.wrapping_add_signed(step));
+        self.memptr = self.memptr.wrapping_add_signed(step);
 
         let bc = self.bc().wrapping_sub(1);
         self.set_bc(bc);
@@ -645,25 +763,51 @@ impl Z80 {
         let sign_flag = n & S_FLAG_BITMASK == S_FLAG_BITMASK;
         set_s_flag_with(&mut self.f, sign_flag);
         set_z_flag_with(&mut self.f, n == 0);
-        let half_carry_borrow = (a & 0x10) != (n & 0x10);
+        let half_carry_borrow = (a ^ data ^ n) & 0x10 == 0x10;
         set_h_flag_with(&mut self.f, half_carry_borrow);
         set_p_flag_with(&mut self.f, bc != 0);
         set_n_flag(&mut self.f);
 
         // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        let n = n.wrapping_sub(half_carry_borrow as u8);
         let y_flag = n & 0b00000010 == 0b00000010;
         set_y_flag_with(&mut self.f, y_flag);
         let x_flag = n & 0b00001000 == 0b00001000;
         set_x_flag_with(&mut self.f, x_flag);
+    }
+
+    /// Finishes one iteration of LDIR, LDDR, CPIR or CPDR. While the
+    /// instruction repeats, the program counter is moved back onto it so that
+    /// it is fetched again, and interrupts can be accepted in between.
+    fn repeat_block_instruction(&mut self, repeat: bool, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        if !repeat {
+            // T states
+            return 16;
+        }
+
+        self.internal_cycles(mem, 5);
+        self.program_counter = self.program_counter.wrapping_sub(2);
+        self.memptr = self.program_counter.wrapping_add(1);
+        self.set_xy_flags_from_program_counter();
 
         // T states
-        16
+        21
+    }
+
+    /// Extra behaviour of repeating block instructions, found after
+    /// z80-documented.pdf was written. The extra M cycle that moves the
+    /// program counter back leaves Y and X from bits 13 and 11 of PC.
+    pub(super) fn set_xy_flags_from_program_counter(&mut self) {
+        let [pc_high, _] = self.program_counter.to_be_bytes();
+        set_y_flag_with(&mut self.f, pc_high & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, pc_high & X_FLAG_BITMASK != 0);
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::z80::{
-        register_flags::{h_flag, n_flag, p_flag, s_flag, z_flag},
+        register_flags::{h_flag, n_flag, p_flag, s_flag, x_flag, y_flag, z_flag},
         tests::Ram,
     };
 
@@ -752,6 +896,7 @@ mod tests {
         assert_eq!(0x11, z80.l.value());
         assert_eq!(0x12, ram.read(1));
         assert_eq!(0x70, ram.read(2));
+        assert_eq!(0x2211, z80.memptr);
     }
 
     #[test]
@@ -869,6 +1014,7 @@ mod tests {
         assert_eq!(21, t_states);
 
         assert_eq!(0, z80.program_counter);
+        assert_eq!(0x0001, z80.memptr);
         assert_eq!(ram.read(2), ram.read(3));
         assert_eq!(0x0003, z80.hl());
         assert_eq!(0x0004, z80.de());
@@ -1079,6 +1225,7 @@ mod tests {
         let t_states = z80.cpi(&mem);
 
         assert_eq!(16, t_states);
+        assert_eq!(0x0001, z80.memptr);
 
         assert_eq!(false, s_flag(&z80.f));
         assert_eq!(true, z_flag(&z80.f));
@@ -1122,8 +1269,142 @@ mod tests {
 
         assert_eq!(true, s_flag(&z80.f));
         assert_eq!(false, z_flag(&z80.f));
-        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
         assert_eq!(true, p_flag(&z80.f));
         assert_eq!(true, n_flag(&z80.f));
     }
+
+    #[test]
+    fn test_cpi_xy_flags_use_half_carry_borrow() {
+        let mut bytes = [0xED, 0xA1, 0x07];
+        let mem = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.set_hl(0x0002);
+        z80.set_bc(0x0002);
+        z80.set_a(0x12);
+
+        z80.cpi(&mem);
+
+        // 12h - 07h = 0Bh, less the half borrow gives 0Ah
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cpd() {
+        let mut bytes = [0xED, 0xA9, 0x3B];
+        let mem = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.set_hl(0x0002);
+        z80.set_bc(0x0001);
+        z80.set_a(0x3B);
+        z80.memptr = 0x0010;
+
+        let t_states = z80.cpd(&mem);
+
+        assert_eq!(16, t_states);
+        assert_eq!(0x0001, z80.hl());
+        assert_eq!(0x0000, z80.bc());
+        assert_eq!(0x000F, z80.memptr);
+        assert_eq!(0x3B, z80.a.value());
+
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(true, n_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cpir() {
+        let mut bytes = [0xED, 0xB1, 0x52, 0x00, 0xF3];
+        let mem = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.program_counter = 2;
+        z80.set_hl(0x0002);
+        z80.set_bc(0x0007);
+        z80.set_a(0xF3);
+
+        let scenarios = [
+            (21, 0x0003, 0x0006, 0),
+            (21, 0x0004, 0x0005, 0),
+            (16, 0x0005, 0x0004, 2),
+        ];
+        for (t_states, hl, bc, program_counter) in scenarios {
+            assert_eq!(t_states, z80.cpir(&mem));
+            assert_eq!(hl, z80.hl());
+            assert_eq!(bc, z80.bc());
+            assert_eq!(program_counter, z80.program_counter);
+            z80.program_counter = 2;
+        }
+
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, z_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cpir_bc_result_0() {
+        let mut bytes = [0xED, 0xB1, 0x52];
+        let mem = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.program_counter = 2;
+        z80.set_hl(0x0002);
+        z80.set_bc(0x0001);
+        z80.set_a(0xF3);
+
+        let t_states = z80.cpir(&mem);
+
+        assert_eq!(16, t_states);
+        assert_eq!(2, z80.program_counter);
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(false, z_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cpdr() {
+        let mut bytes = [0xED, 0xB9, 0xF3, 0x00, 0x52];
+        let mem = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.program_counter = 2;
+        z80.set_hl(0x0004);
+        z80.set_bc(0x0007);
+        z80.set_a(0xF3);
+
+        let scenarios = [
+            (21, 0x0003, 0x0006, 0),
+            (21, 0x0002, 0x0005, 0),
+            (16, 0x0001, 0x0004, 2),
+        ];
+        for (t_states, hl, bc, program_counter) in scenarios {
+            assert_eq!(t_states, z80.cpdr(&mem));
+            assert_eq!(hl, z80.hl());
+            assert_eq!(bc, z80.bc());
+            assert_eq!(program_counter, z80.program_counter);
+            z80.program_counter = 2;
+        }
+
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, z_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_repeat_xy_flags_from_program_counter() {
+        let mut bytes = [0x00; 4];
+        let mut ram = Ram::new(&mut bytes);
+        let mut z80 = Z80::new();
+        z80.program_counter = 0x2802;
+        z80.set_hl(0x0000);
+        z80.set_de(0x0001);
+        z80.set_bc(0x0002);
+
+        assert_eq!(21, z80.ldir(&mut ram));
+        assert_eq!(0x2800, z80.program_counter);
+        assert_eq!(0x2801, z80.memptr);
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+
+        z80.program_counter = 0x0002;
+        assert_eq!(16, z80.ldir(&mut ram));
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+    }
 }
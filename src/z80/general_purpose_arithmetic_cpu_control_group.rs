@@ -0,0 +1,822 @@
+use super::{
+    register_flags::{
+        c_flag, h_flag, n_flag, parity, set_c_flag, set_c_flag_with, set_h_flag, set_h_flag_with,
+        set_n_flag, set_p_flag_with, set_s_flag_with, set_x_flag_with, set_y_flag_with,
+        set_z_flag_with, unset_h_flag, unset_n_flag, S_FLAG_BITMASK, X_FLAG_BITMASK,
+        Y_FLAG_BITMASK,
+    },
+    CpuVariant, Z80,
+};
+
+impl Z80 {
+    // General-Purpose Arithmetic and CPU Control Groups
+
+    /// ### Operation
+    ///
+    /// @
+    ///
+    /// ### Op Code
+    ///
+    /// DAA: `0 0 1 0 0 1 1 1` (0x27)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// This instruction conditionally adjusts the Accumulator for BCD addition
+    /// and subtraction operations. For addition (ADD, ADC, INC) or subtraction
+    /// (SUB, SBC, DEC, NEG), the following table indicates the operation being
+    /// performed:
+    ///
+    /// | Operation | C Before DAA | Hex Value In Upper Digit (Bits 7–4) | H Before DAA | Hex Value In Lower Digit (Bits 3–0) | Number Added To Byte | C After DAA |
+    /// | --------- | ------------ | ----------------------------------- | ------------ | ----------------------------------- | -------------------- | ----------- |
+    /// |           | 0            | 9–0                                 | 0            | 0–9                                 | 00                   | 0           |
+    /// |           | 0            | 0–8                                 | 0            | A–F                                 | 06                   | 0           |
+    /// |           | 0            | 0–9                                 | 1            | 0–3                                 | 06                   | 0           |
+    /// | ADD       | 0            | A–F                                 | 0            | 0–9                                 | 60                   | 1           |
+    /// | ADC       | 0            | 9–F                                 | 0            | A–F                                 | 66                   | 1           |
+    /// | INC       | 0            | A–F                                 | 1            | 0–3                                 | 66                   | 1           |
+    /// |           | 1            | 0–2                                 | 0            | 0–9                                 | 60                   | 1           |
+    /// |           | 1            | 0–2                                 | 0            | A–F                                 | 66                   | 1           |
+    /// |           | 1            | 0–3                                 | 1            | 0–3                                 | 66                   | 1           |
+    /// | SUB       | 0            | 0–9                                 | 0            | 0–9                                 | 00                   | 0           |
+    /// | SBC       | 0            | 0–8                                 | 1            | 6–F                                 | FA                   | 0           |
+    /// | DEC       | 1            | 7–F                                 | 0            | 0–9                                 | A0                   | 1           |
+    /// | NEG       | 1            | 6–7                                 | 1            | 6–F                                 | 9A                   | 1           |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if most-significant bit of the
+    /// Accumulator is 1 after an operation; otherwise, it is reset.
+    ///
+    /// Z is set if the Accumulator is 0 after an operation; otherwise, it is
+    /// reset.
+    ///
+    /// H: see the DAA instruction table on the previous page.
+    ///
+    /// P/V is set if the Accumulator is at even parity after an operation;
+    /// otherwise, it is reset.
+    ///
+    /// N is not affected.
+    ///
+    /// C: see the DAA instruction table on the previous page.
+    ///
+    /// ### Example
+    ///
+    /// An addition operation is performed between 15 (BCD) and 27 (BCD); simple
+    /// decimal arithmetic provides the following result:
+    /// ```text
+    ///   15
+    /// + 27
+    ///   --
+    ///   42
+    /// ```
+    ///
+    /// The binary representations are added in the Accumulator according to
+    /// standard binary arithmetic, as follows:
+    /// ```text
+    ///   0001 0101
+    /// + 0010 0111
+    ///   ---- ----
+    ///   0011 1100 = 3C
+    /// ```
+    ///
+    /// The sum is ambiguous. The DAA instruction adjusts this result so that
+    /// the correct BCD representation is obtained, as follows:
+    /// ```text
+    ///   0011 1100
+    /// + 0000 0110
+    ///   ---- ----
+    ///   0100 0010 = 42
+    /// ```
+    pub fn daa(&mut self) -> u8 {
+        let a = self.a.value();
+        let a_high = a >> 4;
+        let a_low = a & 0x0F;
+        let carry = c_flag(&self.f);
+        let half_carry = h_flag(&self.f);
+        let subtract = n_flag(&self.f);
+
+        let diff: u8 = match (carry, a_high, half_carry, a_low) {
+            (false, 0x0..=0x9, false, 0x0..=0x9) => 0x00,
+            (false, 0x0..=0x9, true, 0x0..=0x9) => 0x06,
+            (false, 0x0..=0x8, _, 0xA..=0xF) => 0x06,
+            (false, 0xA..=0xF, false, 0x0..=0x9) => 0x60,
+            (true, _, false, 0x0..=0x9) => 0x60,
+            (true, _, true, 0x0..=0x9) => 0x66,
+            (true, _, _, 0xA..=0xF) => 0x66,
+            (false, 0x9..=0xF, _, 0xA..=0xF) => 0x66,
+            (false, 0xA..=0xF, true, 0x0..=0x9) => 0x66,
+            _ => 0x00,
+        };
+
+        let new_c_flag = carry
+            || matches!(
+                (a_high, a_low),
+                (0x9..=0xF, 0xA..=0xF) | (0xA..=0xF, 0x0..=0x9)
+            );
+
+        let new_h_flag = match (subtract, half_carry, a_low) {
+            (false, _, a_low) => a_low >= 0x0A,
+            (true, half_carry, a_low) => half_carry && a_low <= 0x05,
+        };
+
+        let result = if subtract {
+            a.wrapping_sub(diff)
+        } else {
+            a.wrapping_add(diff)
+        };
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, new_h_flag);
+        set_p_flag_with(&mut self.f, parity(result));
+        set_c_flag_with(&mut self.f, new_c_flag);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        // T states
+        4
+    }
+
+    /// Hex: 00
+    /// Op Code: NOP
+    /// T-States: 4
+    pub fn nop() -> u8 {
+        // T states
+        4
+    }
+
+    /// ## CPL
+    ///
+    /// ### Operation
+    ///
+    /// A ← ~A
+    ///
+    /// ### Op Code
+    ///
+    /// CPL: `0 0 1 0 1 1 1 1` (2F)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator (Register A) are inverted (one’s
+    /// complement).
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    /// Z is not affected.
+    /// H is set.
+    /// P/V is not affected.
+    /// N is set.
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 1011 0100, then upon the execution of a CPL
+    /// instruction, the Accumulator contains 0100 1011.
+    pub fn cpl(&mut self) -> u8 {
+        let a = !self.a.value();
+        self.a.set_value(a);
+
+        set_h_flag(&mut self.f);
+        set_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.17
+        set_y_flag_with(&mut self.f, a & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, a & X_FLAG_BITMASK != 0);
+
+        // T states
+        4
+    }
+
+    /// ## CCF
+    ///
+    /// ### Operation
+    ///
+    /// CY ← ~CY
+    ///
+    /// ### Op Code
+    ///
+    /// CCF: `0 0 1 1 1 1 1 1` (3F)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The Carry flag in the F Register is inverted.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    /// Z is not affected.
+    /// H, previous carry is copied.
+    /// P/V is not affected.
+    /// N is reset.
+    /// C is set if CY was 0 before operation; otherwise, it is reset.
+    pub fn ccf(&mut self) -> u8 {
+        let carry = c_flag(&self.f);
+        let (y, x) = self.carry_flag_xy_flags();
+
+        set_h_flag_with(&mut self.f, carry);
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, !carry);
+
+        set_y_flag_with(&mut self.f, y);
+        set_x_flag_with(&mut self.f, x);
+
+        // T states
+        4
+    }
+
+    /// ## SCF
+    ///
+    /// ### Operation
+    ///
+    /// CY ← 1
+    ///
+    /// ### Op Code
+    ///
+    /// SCF: `0 0 1 1 0 1 1 1` (37)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The Carry flag in the F Register is set.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    /// Z is not affected.
+    /// H is reset.
+    /// P/V is not affected.
+    /// N is reset.
+    /// C is set.
+    pub fn scf(&mut self) -> u8 {
+        let (y, x) = self.carry_flag_xy_flags();
+
+        unset_h_flag(&mut self.f);
+        unset_n_flag(&mut self.f);
+        set_c_flag(&mut self.f);
+
+        set_y_flag_with(&mut self.f, y);
+        set_x_flag_with(&mut self.f, x);
+
+        // T states
+        4
+    }
+
+    /// Extra behaviour of SCF and CCF found after z80-documented.pdf was
+    /// written, which says Y and X are copied from A. On Zilog parts they are
+    /// set from A ORed with the flags, but only when the previous instruction
+    /// did not update the flags: (Q ⊕ F) ∨ A. NEC parts copy A alone, and
+    /// Toshiba parts take Y as Zilog parts do but copy X from A.
+    fn carry_flag_xy_flags(&self) -> (bool, bool) {
+        let a = self.a.value();
+        let zilog = (self.q ^ self.f.value()) | a;
+
+        let (y_source, x_source) = match self.variant {
+            CpuVariant::ZilogNmos | CpuVariant::ZilogCmos => (zilog, zilog),
+            CpuVariant::NecNmos => (a, a),
+            CpuVariant::Toshiba => (zilog, a),
+        };
+
+        (
+            y_source & Y_FLAG_BITMASK != 0,
+            x_source & X_FLAG_BITMASK != 0,
+        )
+    }
+
+    /// ## HALT
+    ///
+    /// ### Operation
+    ///
+    /// -
+    ///
+    /// ### Op Code
+    ///
+    /// HALT: `0 1 1 1 0 1 1 0` (76)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The HALT instruction suspends CPU operation until a subsequent interrupt
+    /// or reset is received. While in the HALT state, the processor executes
+    /// NOPs to maintain memory refresh logic.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn halt(&mut self) -> u8 {
+        self.halted = true;
+
+        // T states
+        4
+    }
+
+    /// ## DI
+    ///
+    /// ### Operation
+    ///
+    /// IFF ← 0
+    ///
+    /// ### Op Code
+    ///
+    /// DI: `1 1 1 1 0 0 1 1` (F3)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// DI disables the maskable interrupt by resetting the interrupt enable
+    /// flip-flops (IFF1 and IFF2).
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// When the CPU executes the instruction DI the maskable interrupt is
+    /// disabled until it is subsequently re-enabled by an EI instruction. The
+    /// CPU does not respond to an Interrupt Request (INT) signal.
+    pub fn di(&mut self) -> u8 {
+        self.iff1 = false;
+        self.iff2 = false;
+
+        // T states
+        4
+    }
+
+    /// ## EI
+    ///
+    /// ### Operation
+    ///
+    /// IFF ← 1
+    ///
+    /// ### Op Code
+    ///
+    /// EI: `1 1 1 1 1 0 1 1` (FB)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The enable interrupt instruction sets both interrupt enable flip flops
+    /// (IFFI and IFF2) to a logic 1, allowing recognition of any maskable
+    /// interrupt.
+    ///
+    /// Note: during the execution of this instruction and the following
+    /// instruction, maskable interrupts are disabled.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn ei(&mut self) -> u8 {
+        self.iff1 = true;
+        self.iff2 = true;
+        self.interrupt_delay = true;
+
+        // T states
+        4
+    }
+
+    /// ## NEG
+    ///
+    /// ### Operation
+    ///
+    /// A ← 0 – A
+    ///
+    /// ### Op Code
+    ///
+    /// NEG: `1 1 1 0 1 1 0 1` (ED) `0 1 0 0 0 1 0 0` (44)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator are negated (two’s complement). This
+    /// method is the same as subtracting the contents of the Accumulator from
+    /// zero. The 80h value remains unchanged.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 4; otherwise, it is reset.
+    ///
+    /// P/V is set if Accumulator was 80h before operation; otherwise, it is
+    /// reset.
+    ///
+    /// N is set.
+    ///
+    /// C is set if Accumulator was not 00h before operation; otherwise, it is
+    /// reset.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator contains the following data: 1001 1000. Upon the
+    /// execution of a NEG instruction, the Accumulator contains: 0110 1000.
+    pub fn neg(&mut self) -> u8 {
+        let a = self.a.value();
+        let result = 0u8.wrapping_sub(a);
+        self.a.set_value(result);
+
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        set_h_flag_with(&mut self.f, a & 0x0F != 0);
+        set_p_flag_with(&mut self.f, a == 0x80);
+        set_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, a != 0);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        // T states
+        8
+    }
+
+    /// ## IM 0
+    ///
+    /// ### Operation
+    ///
+    /// Sets Interrupt Mode 0
+    ///
+    /// ### Op Code
+    ///
+    /// IM: `1 1 1 0 1 1 0 1` (ED) `0 1 0 0 0 1 1 0` (46)
+    ///
+    /// ### Operands
+    ///
+    /// 0
+    ///
+    /// ### Description
+    ///
+    /// The IM 0 instruction sets Interrupt Mode 0. In this mode, the
+    /// interrupting device can insert any instruction on the data bus for
+    /// execution by the CPU. The first byte of a multi-byte instruction is read
+    /// during the interrupt acknowledge cycle. Subsequent bytes are read in by
+    /// a normal memory read sequence.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn im_0(&mut self) -> u8 {
+        self.interrupt_mode = 0;
+
+        // T states
+        8
+    }
+
+    /// ## IM 1
+    ///
+    /// ### Operation
+    ///
+    /// Sets Interrupt Mode 1
+    ///
+    /// ### Op Code
+    ///
+    /// IM: `1 1 1 0 1 1 0 1` (ED) `0 1 0 1 0 1 1 0` (56)
+    ///
+    /// ### Operands
+    ///
+    /// 1
+    ///
+    /// ### Description
+    ///
+    /// The IM 1 instruction sets Interrupt Mode 1. In this mode, the processor
+    /// responds to an interrupt by executing a restart at address 0038h.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn im_1(&mut self) -> u8 {
+        self.interrupt_mode = 1;
+
+        // T states
+        8
+    }
+
+    /// ## IM 2
+    ///
+    /// ### Operation
+    ///
+    /// Sets Interrupt Mode 2
+    ///
+    /// ### Op Code
+    ///
+    /// IM: `1 1 1 0 1 1 0 1` (ED) `0 1 0 1 1 1 1 0` (5E)
+    ///
+    /// ### Operands
+    ///
+    /// 2
+    ///
+    /// ### Description
+    ///
+    /// The IM 2 instruction sets the vectored Interrupt Mode 2. This mode
+    /// allows an indirect call to any memory location by an 8-bit vector
+    /// supplied from the peripheral device. This vector then becomes the least
+    /// significant eight bits of the indirect pointer, while the I Register in
+    /// the CPU provides the most-significant eight bits. This address points
+    /// to an address in a vector table that is the starting address for the
+    /// interrupt service routine.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 2        | 8 (4, 4) | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn im_2(&mut self) -> u8 {
+        self.interrupt_mode = 2;
+
+        // T states
+        8
+    }
+
+    /// ED prefixed opcodes that do not decode to an instruction do nothing,
+    /// behaving as two NOPs.
+    pub fn ed_nop() -> u8 {
+        // T states
+        8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{p_flag, s_flag, x_flag, y_flag, z_flag},
+        tests::{Ports, Ram},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_daa_after_add() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x15);
+        z80.b.set_value(0x27);
+        z80.add_a_b();
+
+        let t_states = z80.daa();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0x42, z80.a.value());
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_daa_after_add_with_carry_out() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x99);
+        z80.b.set_value(0x01);
+        z80.add_a_b();
+
+        z80.daa();
+
+        assert_eq!(0x00, z80.a.value());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_daa_after_sub() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x42);
+        z80.b.set_value(0x15);
+        z80.sub_b();
+
+        z80.daa();
+
+        assert_eq!(0x27, z80.a.value());
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+
+        z80.a.set_value(0x10);
+        z80.b.set_value(0x20);
+        z80.sub_b();
+
+        z80.daa();
+
+        assert_eq!(0x90, z80.a.value());
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_cpl() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b10110100);
+
+        let t_states = z80.cpl();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0b01001011, z80.a.value());
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_scf() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b00100000);
+        set_h_flag(&mut z80.f);
+        set_n_flag(&mut z80.f);
+
+        let t_states = z80.scf();
+        assert_eq!(4, t_states);
+
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_ccf() {
+        let z80 = &mut Z80::new();
+        set_c_flag(&mut z80.f);
+        set_n_flag(&mut z80.f);
+
+        let t_states = z80.ccf();
+        assert_eq!(4, t_states);
+
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+
+        z80.ccf();
+
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_scf_ccf_xy_flags_depend_on_q() {
+        let scenarios = [
+            (CpuVariant::ZilogNmos, true, true),
+            (CpuVariant::ZilogCmos, true, true),
+            (CpuVariant::NecNmos, false, false),
+            (CpuVariant::Toshiba, true, false),
+        ];
+
+        for (variant, y, x) in scenarios {
+            let mut bytes = [
+                0x00, // NOP
+                0x37, // SCF
+                0xAF, // XOR A
+                0x3F, // CCF
+            ];
+            let ram = &mut Ram::new(&mut bytes);
+            let io = &mut Ports::new(&[]);
+            let z80 = &mut Z80::with_variant(variant);
+            z80.f.set_value(Y_FLAG_BITMASK | X_FLAG_BITMASK);
+
+            // The NOP leaves the flags alone, so F is mixed into Y and X
+            z80.process_next_instruction(ram, io);
+            z80.process_next_instruction(ram, io);
+            assert_eq!(y, y_flag(&z80.f));
+            assert_eq!(x, x_flag(&z80.f));
+
+            // XOR A updates the flags, so only A is used and A is 0
+            z80.process_next_instruction(ram, io);
+            z80.process_next_instruction(ram, io);
+            assert_eq!(false, y_flag(&z80.f));
+            assert_eq!(false, x_flag(&z80.f));
+        }
+    }
+
+    #[test]
+    fn test_halt() {
+        let z80 = &mut Z80::new();
+
+        assert_eq!(4, z80.halt());
+        assert_eq!(true, z80.is_halted());
+    }
+
+    #[test]
+    fn test_di_ei() {
+        let z80 = &mut Z80::new();
+
+        assert_eq!(4, z80.di());
+        assert_eq!(false, z80.iff1);
+        assert_eq!(false, z80.iff2);
+
+        assert_eq!(4, z80.ei());
+        assert_eq!(true, z80.iff1);
+        assert_eq!(true, z80.iff2);
+        assert_eq!(true, z80.interrupt_delay);
+    }
+
+    #[test]
+    fn test_neg() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b10011000);
+
+        let t_states = z80.neg();
+        assert_eq!(8, t_states);
+
+        assert_eq!(0b01101000, z80.a.value());
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+
+        z80.a.set_value(0x80);
+        z80.neg();
+
+        assert_eq!(0x80, z80.a.value());
+        assert_eq!(true, p_flag(&z80.f));
+
+        z80.a.set_value(0x00);
+        z80.neg();
+
+        assert_eq!(0x00, z80.a.value());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_im() {
+        let z80 = &mut Z80::new();
+
+        assert_eq!(8, z80.im_2());
+        assert_eq!(2, z80.interrupt_mode);
+
+        assert_eq!(8, z80.im_1());
+        assert_eq!(1, z80.interrupt_mode);
+
+        assert_eq!(8, z80.im_0());
+        assert_eq!(0, z80.interrupt_mode);
+    }
+}
@@ -0,0 +1,1175 @@
+use super::{
+    register_flags::{
+        c_flag, n_flag, p_flag, parity, set_c_flag_with, set_h_flag_with, set_n_flag_with,
+        set_p_flag_with, set_s_flag_with, set_x_flag_with, set_y_flag_with, set_z_flag_with,
+        unset_h_flag, unset_n_flag, S_FLAG_BITMASK, X_FLAG_BITMASK, Y_FLAG_BITMASK,
+    },
+    Register, Z80Io, Z80Memory, Z80,
+};
+
+impl Z80 {
+    // Input and Output Group
+
+    /// ## IN A, (n)
+    ///
+    /// ### Operation
+    ///
+    /// A ← (n)
+    ///
+    /// ### Op Code
+    ///
+    /// IN: `1 1 0 1 1 0 1 1` (DB) followed by `n n n n n n n n`
+    ///
+    /// ### Operands
+    ///
+    /// A, (n)
+    ///
+    /// ### Description
+    ///
+    /// The operand n is placed on the bottom half (A0 through A7) of the
+    /// address bus to select the I/O device at one of 256 possible ports. The
+    /// contents of the Accumulator also appear on the top half (A8 through A15)
+    /// of the address bus at this time. Then one byte from the selected port is
+    /// placed on the data bus and written to the Accumulator (Register A) in
+    /// the CPU.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (4, 3, 4)    | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator contains 23h, and byte 7Bh is available at the
+    /// peripheral device mapped to I/O port address 01h. Upon the execution of
+    /// an IN A, (01h) instruction, the Accumulator contains 7Bh.
+    pub fn in_a_n(
+        &mut self,
+        mem: &(impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        let port = u16::from_be_bytes([self.a.value(), n]);
+        self.memptr = port.wrapping_add(1);
+
+        let data = self.read_io(io, port);
+        self.a.set_value(data);
+
+        // T states
+        11
+    }
+
+    /// ## IN r, (C)
+    ///
+    /// ### Operation
+    ///
+    /// r ← (C)
+    ///
+    /// ### Op Code
+    ///
+    /// IN: `1 1 1 0 1 1 0 1` (ED) `0 1 r r r 0 0 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// r, (C)
+    ///
+    /// ### Description
+    ///
+    /// This instruction places the contents of Register C on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. The contents of Register B are placed on the top
+    /// half (A8 through A15) of the address bus at this time. Then one byte
+    /// from the selected port is placed on the data bus and written to
+    /// register r in the CPU.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 12 (4, 4, 4)    | 3.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if input data is negative; otherwise, it is reset.
+    ///
+    /// Z is set if input data is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// Register C contains 07h, Register B contains 10h, and byte 7Bh is
+    /// available at the peripheral device mapped to I/O port address 07h. Upon
+    /// the execution of an IN D, (C) command, the D Register contains 7Bh.
+    fn in_r_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        let port = self.bc();
+        self.memptr = port.wrapping_add(1);
+
+        let data = self.read_io(io, port);
+
+        set_s_flag_with(&mut self.f, data & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, data == 0);
+        unset_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(data));
+        unset_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, data & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, data & X_FLAG_BITMASK != 0);
+
+        data
+    }
+
+    fn in_register_c(
+        &mut self,
+        io: &mut (impl Z80Io + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.in_r_c(io);
+        r(self).set_value(data);
+
+        // T states
+        12
+    }
+
+    pub fn in_a_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.a)
+    }
+
+    pub fn in_b_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.b)
+    }
+
+    pub fn in_c_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.c)
+    }
+
+    pub fn in_d_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.d)
+    }
+
+    pub fn in_e_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.e)
+    }
+
+    pub fn in_h_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.h)
+    }
+
+    pub fn in_l_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_register_c(io, |z80| &mut z80.l)
+    }
+
+    /// Undocumented. ED 70 reads the port and sets the flags as IN r, (C)
+    /// does, but discards the value that was read.
+    pub fn in_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.in_r_c(io);
+
+        // T states
+        12
+    }
+
+    /// ## INI
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (C), B ← B – 1, HL ← HL + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INI: `1 1 1 0 1 1 0 1` (ED) `1 0 1 0 0 0 1 0` (A2)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of Register C are placed on the bottom half (A0 through A7)
+    /// of the address bus to select the I/O device at one of 256 possible
+    /// ports. Register B can be used as a byte counter, and its contents are
+    /// placed on the top half (A8 through A15) of the address bus at this time.
+    /// Then one byte from the selected port is placed on the data bus and
+    /// written to the CPU. The contents of the HL register pair are then placed
+    /// on the address bus and the input byte is written to the corresponding
+    /// location of memory. Finally, the byte counter is decremented and
+    /// register pair HL is incremented.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if B – 1 = 0; otherwise it is reset.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Undocumented Condition Bits
+    ///
+    /// S, Z, Y and X are set from the decremented B as DEC B would. N is a
+    /// copy of bit 7 of the byte that was transferred. With k being that byte
+    /// plus C + 1 (C – 1 for IND), H and C are set if k > FFh, and P/V is the
+    /// parity of (k ∧ 7) ⊕ B.
+    pub fn ini(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.input_block(mem, io, 1);
+
+        // T states
+        16
+    }
+
+    /// ## INIR
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (C), B ← B – 1, HL ← HL + 1, repeat while B ≠ 0
+    ///
+    /// ### Op Code
+    ///
+    /// INIR: `1 1 1 0 1 1 0 1` (ED) `1 0 1 1 0 0 1 0` (B2)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of Register C are placed on the bottom half (A0 through A7)
+    /// of the address bus to select the I/O device at one of 256 possible
+    /// ports. Register B can be used as a byte counter, and its contents are
+    /// placed on the top half (A8 through A15) of the address bus at this time.
+    /// Then one byte from the selected port is placed on the data bus and
+    /// written to the CPU. The contents of the HL register pair are placed on
+    /// the address bus and the input byte is written to the corresponding
+    /// location of memory. Finally, register pair HL is incremented, the byte
+    /// counter is decremented. If decrementing causes B to go to 0, the
+    /// instruction is terminated. If B is not 0, the Program Counter is
+    /// decremented by two and the instruction repeated. Interrupts are
+    /// recognized and two refresh cycles execute after each data transfer.
+    ///
+    /// If B ≠ 0:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 21 (4, 5, 3, 4, 5)    | 5.25       |
+    ///
+    /// If B = 0:
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Undocumented Condition Bits
+    ///
+    /// Each iteration sets the flags as INI does. When the instruction repeats,
+    /// Y and X are then taken from the high byte of the program counter, and H
+    /// and P/V are adjusted again from B.
+    pub fn inir(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.input_block(mem, io, 1);
+        self.repeat_block_io(mem)
+    }
+
+    /// ## IND
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (C), B ← B – 1, HL ← HL – 1
+    ///
+    /// ### Op Code
+    ///
+    /// IND: `1 1 1 0 1 1 0 1` (ED) `1 0 1 0 1 0 1 0` (AA)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of Register C are placed on the bottom half (A0 through A7)
+    /// of the address bus to select the I/O device at one of 256 possible
+    /// ports. Register B can be used as a byte counter, and its contents are
+    /// placed on the top half (A8 through A15) of the address bus at this time.
+    /// Then one byte from the selected port is placed on the data bus and
+    /// written to the CPU. The contents of the HL register pair are placed on
+    /// the address bus and the input byte is written to the corresponding
+    /// location of memory. Finally, the byte counter and register pair HL are
+    /// decremented.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if B – 1 = 0; otherwise it is reset.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn ind(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.input_block(mem, io, -1);
+
+        // T states
+        16
+    }
+
+    /// ## INDR
+    ///
+    /// ### Operation
+    ///
+    /// (HL) ← (C), B ← B – 1, HL ← HL – 1, repeat while B ≠ 0
+    ///
+    /// ### Op Code
+    ///
+    /// INDR: `1 1 1 0 1 1 0 1` (ED) `1 0 1 1 1 0 1 0` (BA)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of Register C are placed on the bottom half (A0 through A7)
+    /// of the address bus to select the I/O device at one of 256 possible
+    /// ports. Register B is used as a byte counter, and its contents are placed
+    /// on the top half (A8 through A15) of the address bus at this time. Then
+    /// one byte from the selected port is placed on the data bus and written
+    /// to the CPU. The contents of the HL register pair are placed on the
+    /// address bus and the input byte is written to the corresponding location
+    /// of memory. Finally, register pair HL and the byte counter are
+    /// decremented. If decrementing causes B to go to 0, the instruction is
+    /// terminated. If B is not 0, the Program Counter is decremented by two and
+    /// the instruction repeated. Interrupts are recognized and two refresh
+    /// cycles are executed after each data transfer.
+    ///
+    /// If B ≠ 0:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 21 (4, 5, 3, 4, 5)    | 5.25       |
+    ///
+    /// If B = 0:
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn indr(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.input_block(mem, io, -1);
+        self.repeat_block_io(mem)
+    }
+
+    /// Performs one INI or IND transfer, moving HL by `step`.
+    fn input_block(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+        step: i16,
+    ) {
+        self.internal_cycles(mem, 1);
+        let port = self.bc();
+        self.memptr = port.wrapping_add_signed(step);
+
+        let data = self.read_io(io, port);
+        let hl = self.hl();
+        self.write_memory(mem, hl, data);
+        self.set_hl(hl.wrapping_add_signed(step));
+        self.b.dec();
+
+        let c = self.c.value().wrapping_add_signed(step as i8);
+        self.block_io_flags(data, data as u16 + c as u16);
+    }
+
+    /// ## OUT (n), A
+    ///
+    /// ### Operation
+    ///
+    /// (n) ← A
+    ///
+    /// ### Op Code
+    ///
+    /// OUT: `1 1 0 1 0 0 1 1` (D3) followed by `n n n n n n n n`
+    ///
+    /// ### Operands
+    ///
+    /// (n), A
+    ///
+    /// ### Description
+    ///
+    /// The operand n is placed on the bottom half (A0 through A7) of the
+    /// address bus to select the I/O device at one of 256 possible ports. The
+    /// contents of the Accumulator (Register A) also appear on the top half
+    /// (A8 through A15) of the address bus at this time. Then the byte
+    /// contained in the Accumulator is placed on the data bus and written to
+    /// the selected peripheral device.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (4, 3, 4)    | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Accumulator contains 23h, then upon the execution of an OUT
+    /// (01h), A instruction, byte 23h is written to the peripheral device
+    /// mapped to I/O port address 01h.
+    pub fn out_n_a(
+        &mut self,
+        mem: &(impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        let n = self.fetch_next_byte(mem);
+        let a = self.a.value();
+        self.memptr = u16::from_be_bytes([a, n.wrapping_add(1)]);
+
+        self.write_io(io, u16::from_be_bytes([a, n]), a);
+
+        // T states
+        11
+    }
+
+    /// ## OUT (C), r
+    ///
+    /// ### Operation
+    ///
+    /// (C) ← r
+    ///
+    /// ### Op Code
+    ///
+    /// OUT: `1 1 1 0 1 1 0 1` (ED) `0 1 r r r 0 0 1`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// (C), r
+    ///
+    /// ### Description
+    ///
+    /// This instruction places the contents of Register C on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. The contents of Register B are placed on the top
+    /// half (A8 through A15) of the address bus at this time. Then the byte
+    /// contained in register r is placed on the data bus and written to the
+    /// selected peripheral device.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 12 (4, 4, 4)    | 3.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If Register C contains 01h and the D Register contains 5Ah, then upon
+    /// the execution of an OUT (C), D instruction, byte 5Ah is written to the
+    /// peripheral device mapped to I/O port address 01h.
+    fn out_c_r(&mut self, io: &mut (impl Z80Io + ?Sized), data: u8) -> u8 {
+        let port = self.bc();
+        self.memptr = port.wrapping_add(1);
+
+        self.write_io(io, port, data);
+
+        // T states
+        12
+    }
+
+    pub fn out_c_a(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.a.value())
+    }
+
+    pub fn out_c_b(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.b.value())
+    }
+
+    pub fn out_c_c(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.c.value())
+    }
+
+    pub fn out_c_d(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.d.value())
+    }
+
+    pub fn out_c_e(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.e.value())
+    }
+
+    pub fn out_c_h(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.h.value())
+    }
+
+    pub fn out_c_l(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        self.out_c_r(io, self.l.value())
+    }
+
+    /// Undocumented. ED 71 writes zero to the port on NMOS parts, and FFh on
+    /// CMOS parts.
+    pub fn out_c_0(&mut self, io: &mut (impl Z80Io + ?Sized)) -> u8 {
+        let data = if self.variant.is_cmos() { 0xFF } else { 0x00 };
+        self.out_c_r(io, data)
+    }
+
+    /// ## OUTI
+    ///
+    /// ### Operation
+    ///
+    /// B ← B – 1, (C) ← (HL), HL ← HL + 1
+    ///
+    /// ### Op Code
+    ///
+    /// OUTI: `1 1 1 0 1 1 0 1` (ED) `1 0 1 0 0 0 1 1` (A3)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the HL register pair are placed on the address bus to
+    /// select a location in memory. The byte contained in this memory location
+    /// is temporarily stored in the CPU. Then, after the byte counter (B) is
+    /// decremented, the contents of Register C are placed on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. Register B can be used as a byte counter, and its
+    /// decremented value is placed on the top half (A8 through A15) of the
+    /// address bus. The byte to be output is placed on the data bus and
+    /// written to a selected peripheral device. Finally, the register pair HL
+    /// is incremented.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if B – 1 = 0; otherwise it is reset.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Undocumented Condition Bits
+    ///
+    /// As for INI, except that k is the byte that was transferred plus L after
+    /// HL has been incremented or decremented.
+    pub fn outi(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.output_block(mem, io, 1);
+
+        // T states
+        16
+    }
+
+    /// ## OTIR
+    ///
+    /// ### Operation
+    ///
+    /// B ← B – 1, (C) ← (HL), HL ← HL + 1, repeat while B ≠ 0
+    ///
+    /// ### Op Code
+    ///
+    /// OTIR: `1 1 1 0 1 1 0 1` (ED) `1 0 1 1 0 0 1 1` (B3)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the HL register pair are placed on the address bus to
+    /// select a location in memory. The byte contained in this memory location
+    /// is temporarily loaded to the CPU. Then, after the byte counter (B) is
+    /// decremented, the contents of Register C are placed on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. Register B can be used as a byte counter, and its
+    /// decremented value is placed on the top half (A8 through A15) of the
+    /// address bus at this time. Next, the byte to be output is placed on the
+    /// data bus and written to the selected peripheral device. Then register
+    /// pair HL is incremented. If the decremented B Register is not 0, the
+    /// Program Counter (PC) is decremented by two and the instruction is
+    /// repeated. If B has gone to 0, the instruction is terminated. Interrupts
+    /// are recognized and two refresh cycles are executed after each data
+    /// transfer.
+    ///
+    /// If B ≠ 0:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 21 (4, 5, 3, 4, 5)    | 5.25       |
+    ///
+    /// If B = 0:
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn otir(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.output_block(mem, io, 1);
+        self.repeat_block_io(mem)
+    }
+
+    /// ## OUTD
+    ///
+    /// ### Operation
+    ///
+    /// B ← B – 1, (C) ← (HL), HL ← HL – 1
+    ///
+    /// ### Op Code
+    ///
+    /// OUTD: `1 1 1 0 1 1 0 1` (ED) `1 0 1 0 1 0 1 1` (AB)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the HL register pair are placed on the address bus to
+    /// select a location in memory. The byte contained in this memory location
+    /// is temporarily stored in the CPU. Then, after the byte counter (B) is
+    /// decremented, the contents of Register C are placed on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. Register B can be used as a byte counter, and its
+    /// decremented value is placed on the top half (A8 through A15) of the
+    /// address bus. The byte to be output is placed on the data bus and
+    /// written to a selected peripheral device. Finally, the register pair HL
+    /// is decremented.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set if B – 1 = 0; otherwise it is reset.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn outd(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.output_block(mem, io, -1);
+
+        // T states
+        16
+    }
+
+    /// ## OTDR
+    ///
+    /// ### Operation
+    ///
+    /// B ← B – 1, (C) ← (HL), HL ← HL – 1, repeat while B ≠ 0
+    ///
+    /// ### Op Code
+    ///
+    /// OTDR: `1 1 1 0 1 1 0 1` (ED) `1 0 1 1 1 0 1 1` (BB)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the HL register pair are placed on the address bus to
+    /// select a location in memory. The byte contained in this memory location
+    /// is temporarily loaded to the CPU. Then, after the byte counter (B) is
+    /// decremented, the contents of Register C are placed on the bottom half
+    /// (A0 through A7) of the address bus to select the I/O device at one of
+    /// 256 possible ports. Register B can be used as a byte counter, and its
+    /// decremented value is placed on the top half (A8 through A15) of the
+    /// address bus at this time. Next, the byte to be output is placed on the
+    /// data bus and written to the selected peripheral device. Then, register
+    /// pair HL is decremented and if the decremented B Register is not 0, the
+    /// Program Counter (PC) is decremented by two and the instruction is
+    /// repeated. If B has gone to 0, the instruction is terminated. Interrupts
+    /// are recognized and two refresh cycles are executed after each data
+    /// transfer.
+    ///
+    /// If B ≠ 0:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 21 (4, 5, 3, 4, 5)    | 5.25       |
+    ///
+    /// If B = 0:
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 16 (4, 5, 3, 4)    | 4.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is unknown.
+    ///
+    /// Z is set.
+    ///
+    /// H is unknown.
+    ///
+    /// P/V is unknown.
+    ///
+    /// N is set.
+    ///
+    /// C is not affected.
+    pub fn otdr(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        self.output_block(mem, io, -1);
+        self.repeat_block_io(mem)
+    }
+
+    /// Performs one OUTI or OUTD transfer, moving HL by `step`.
+    fn output_block(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        io: &mut (impl Z80Io + ?Sized),
+        step: i16,
+    ) {
+        self.internal_cycles(mem, 1);
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.b.dec();
+
+        let port = self.bc();
+        self.memptr = port.wrapping_add_signed(step);
+        self.write_io(io, port, data);
+        self.set_hl(hl.wrapping_add_signed(step));
+
+        self.block_io_flags(data, data as u16 + self.l.value() as u16);
+    }
+
+    fn block_io_flags(&mut self, data: u8, k: u16) {
+        let b = self.b.value();
+
+        set_s_flag_with(&mut self.f, b & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, b == 0);
+        set_h_flag_with(&mut self.f, k > 0xFF);
+        set_p_flag_with(&mut self.f, parity((k as u8 & 0x07) ^ b));
+        set_n_flag_with(&mut self.f, data & 0x80 != 0);
+        set_c_flag_with(&mut self.f, k > 0xFF);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, b & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, b & X_FLAG_BITMASK != 0);
+    }
+
+    /// Repeats the block I/O instruction while B ≠ 0 by moving the program
+    /// counter back onto its opcode, returning the T states taken.
+    fn repeat_block_io(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        if self.b.value() == 0 {
+            // T states
+            return 16;
+        }
+
+        self.internal_cycles(mem, 5);
+        self.program_counter = self.program_counter.wrapping_sub(2);
+        self.block_io_repeat_flags();
+
+        // T states
+        21
+    }
+
+    /// Extra behaviour of a repeating INIR, INDR, OTIR or OTDR, found after
+    /// z80-documented.pdf was written. As well as taking Y and X from PC like
+    /// the other repeating block instructions, the extra M cycle adjusts H and
+    /// P/V by a further B – 1, B + 1 or B, depending on the carry and the
+    /// direction given by N.
+    fn block_io_repeat_flags(&mut self) {
+        let b = self.b.value();
+        self.set_xy_flags_from_program_counter();
+
+        let p = p_flag(&self.f);
+        let adjusted_b = if !c_flag(&self.f) {
+            b
+        } else if n_flag(&self.f) {
+            set_h_flag_with(&mut self.f, b & 0x0F == 0x00);
+            b.wrapping_sub(1)
+        } else {
+            set_h_flag_with(&mut self.f, b & 0x0F == 0x0F);
+            b.wrapping_add(1)
+        };
+        set_p_flag_with(&mut self.f, p ^ !parity(adjusted_b & 0x07));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{
+            c_flag, h_flag, n_flag, p_flag, s_flag, set_c_flag, x_flag, y_flag, z_flag,
+        },
+        tests::{Ports, Ram},
+        CpuVariant,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_in_a_n() {
+        let mut bytes = [0xFE];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[0x1F]);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x7F);
+
+        let t_states = z80.in_a_n(ram, io);
+        assert_eq!(11, t_states);
+
+        assert_eq!(vec![0x7FFE], io.reads);
+        assert_eq!(0x1F, z80.a.value());
+        assert_eq!(0x7FFF, z80.memptr);
+        assert_eq!(1, z80.program_counter);
+    }
+
+    #[test]
+    fn test_in_r_c() {
+        let io = &mut Ports::new(&[0x7B]);
+        let z80 = &mut Z80::new();
+        z80.b.set_value(0x10);
+        z80.c.set_value(0x07);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.in_d_c(io);
+        assert_eq!(12, t_states);
+
+        assert_eq!(vec![0x1007], io.reads);
+        assert_eq!(0x7B, z80.d.value());
+        assert_eq!(0x1008, z80.memptr);
+        assert_eq!(false, s_flag(&z80.f));
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_in_c_only_sets_flags() {
+        let io = &mut Ports::new(&[0x00]);
+        let z80 = &mut Z80::new();
+        z80.b.set_value(0x12);
+        z80.c.set_value(0x34);
+
+        let t_states = z80.in_c(io);
+        assert_eq!(12, t_states);
+
+        assert_eq!(0x1234, z80.bc());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_out_n_a() {
+        let mut bytes = [0x01];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x23);
+
+        let t_states = z80.out_n_a(ram, io);
+        assert_eq!(11, t_states);
+
+        assert_eq!(vec![(0x2301, 0x23)], io.writes);
+        assert_eq!(0x2302, z80.memptr);
+        assert_eq!(1, z80.program_counter);
+    }
+
+    #[test]
+    fn test_out_c_r() {
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.c.set_value(0x01);
+        z80.d.set_value(0x5A);
+
+        let t_states = z80.out_c_d(io);
+        assert_eq!(12, t_states);
+
+        z80.out_c_0(io);
+
+        assert_eq!(vec![(0x0001, 0x5A), (0x0001, 0x00)], io.writes);
+        assert_eq!(0x0002, z80.memptr);
+    }
+
+    #[test]
+    fn test_out_c_0_per_variant() {
+        let scenarios = [
+            (CpuVariant::ZilogNmos, 0x00),
+            (CpuVariant::ZilogCmos, 0xFF),
+            (CpuVariant::NecNmos, 0x00),
+            (CpuVariant::Toshiba, 0xFF),
+        ];
+
+        for (variant, data) in scenarios {
+            let io = &mut Ports::new(&[]);
+            let z80 = &mut Z80::with_variant(variant);
+            z80.c.set_value(0xFE);
+
+            z80.out_c_0(io);
+
+            assert_eq!(vec![(0x00FE, data)], io.writes);
+        }
+    }
+
+    #[test]
+    fn test_ini() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[0x7B]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0004);
+        z80.b.set_value(0x02);
+        z80.c.set_value(0x10);
+
+        let t_states = z80.ini(ram, io);
+        assert_eq!(16, t_states);
+
+        assert_eq!(vec![0x0210], io.reads);
+        assert_eq!(0x7B, ram.read(0x0004));
+        assert_eq!(0x0005, z80.hl());
+        assert_eq!(0x01, z80.b.value());
+        assert_eq!(0x0211, z80.memptr);
+        assert_eq!(false, z_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_inir() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[0x80, 0xFF]);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 2;
+        z80.set_hl(0x0008);
+        z80.b.set_value(0x02);
+        z80.c.set_value(0x10);
+
+        let t_states = z80.inir(ram, io);
+        assert_eq!(21, t_states);
+
+        assert_eq!(0, z80.program_counter);
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+
+        z80.program_counter = 2;
+        let t_states = z80.inir(ram, io);
+        assert_eq!(16, t_states);
+
+        assert_eq!(2, z80.program_counter);
+        assert_eq!(vec![0x0210, 0x0110], io.reads);
+        assert_eq!(0x80, ram.read(0x0008));
+        assert_eq!(0xFF, ram.read(0x0009));
+        assert_eq!(0x000A, z80.hl());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_ind() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[0x01]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0003);
+        z80.b.set_value(0x01);
+        z80.c.set_value(0x00);
+
+        let t_states = z80.ind(ram, io);
+        assert_eq!(16, t_states);
+
+        assert_eq!(vec![0x0100], io.reads);
+        assert_eq!(0x01, ram.read(0x0003));
+        assert_eq!(0x0002, z80.hl());
+        assert_eq!(0x00FF, z80.memptr);
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_outd() {
+        let mut bytes = [0x00, 0x00, 0x00, 0x00, 0x00, 0x59];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0005);
+        z80.b.set_value(0x01);
+        z80.c.set_value(0xFE);
+
+        let t_states = z80.outd(ram, io);
+        assert_eq!(16, t_states);
+
+        assert_eq!(vec![(0x00FE, 0x59)], io.writes);
+        assert_eq!(0x0004, z80.hl());
+        assert_eq!(0x00FD, z80.memptr);
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_otir_repeat_flags() {
+        let mut bytes = [0x00; 0x100];
+        bytes[0xF0] = 0x20;
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x2A02;
+        z80.set_hl(0x00F0);
+        z80.b.set_value(0x10);
+        z80.c.set_value(0x10);
+
+        let t_states = z80.otir(ram, io);
+        assert_eq!(21, t_states);
+
+        assert_eq!(vec![(0x0F10, 0x20)], io.writes);
+        assert_eq!(0x2A00, z80.program_counter);
+        assert_eq!(0x00F1, z80.hl());
+        assert_eq!(0x0F11, z80.memptr);
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+}
@@ -0,0 +1,1453 @@
+// Decoding of instruction bytes into a typed Instruction, for the
+// disassembler, the assembler and debuggers. The executor does not use it,
+// as it dispatches from its own opcode tables, and the tests here check the
+// two against each other.
+
+// Decoding follows the opcode structure described in
+// http://www.z80.info/decoding.htm, where each opcode is split into the fields
+// x (bits 7-6), y (bits 5-3), z (bits 2-0), p (bits 5-4) and q (bit 3).
+
+/// The prefix bytes that select the opcode page an instruction is taken from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Prefix {
+    None,
+    Cb,
+    Ed,
+    Dd,
+    Fd,
+    DdCb,
+    FdCb,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Register {
+    A,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+    I,
+    R,
+    Ixh,
+    Ixl,
+    Iyh,
+    Iyl,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RegisterPair {
+    Af,
+    AfPrime,
+    Bc,
+    De,
+    Hl,
+    Sp,
+    Ix,
+    Iy,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Condition {
+    Nz,
+    Z,
+    Nc,
+    C,
+    Po,
+    Pe,
+    P,
+    M,
+}
+
+/// The rotate and shift operations of the CB page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Shift {
+    Rlc,
+    Rrc,
+    Rl,
+    Rr,
+    Sla,
+    Sra,
+    /// Undocumented.
+    Sll,
+    Srl,
+}
+
+/// An operand, named after the addressing mode it uses in the Zilog manual.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operand {
+    /// r
+    Register(Register),
+    /// rr
+    RegisterPair(RegisterPair),
+    /// n
+    Immediate(u8),
+    /// nn
+    ImmediateExtended(u16),
+    /// (rr)
+    RegisterIndirect(RegisterPair),
+    /// (IX+d) or (IY+d)
+    Indexed(RegisterPair, i8),
+    /// (nn)
+    Extended(u16),
+    /// e, the displacement as encoded, relative to the address of the next
+    /// instruction.
+    Relative(i8),
+    /// (n)
+    Port(u8),
+    /// (C)
+    PortC,
+}
+
+/// An operation and its operands, named after the Zilog mnemonics. Where an
+/// operation always works on the accumulator, as with SUB, AND or CP, it is
+/// left out of the operands like it is in the mnemonic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operation {
+    Nop,
+    Halt,
+    Di,
+    Ei,
+    Im(u8),
+    Ld(Operand, Operand),
+    Push(Operand),
+    Pop(Operand),
+    Ex(Operand, Operand),
+    Exx,
+    Ldi,
+    Ldir,
+    Ldd,
+    Lddr,
+    Cpi,
+    Cpir,
+    Cpd,
+    Cpdr,
+    Add(Operand, Operand),
+    Adc(Operand, Operand),
+    Sub(Operand),
+    Sbc(Operand, Operand),
+    And(Operand),
+    Or(Operand),
+    Xor(Operand),
+    Cp(Operand),
+    Inc(Operand),
+    Dec(Operand),
+    Daa,
+    Cpl,
+    Neg,
+    Ccf,
+    Scf,
+    Rlca,
+    Rla,
+    Rrca,
+    Rra,
+    Rld,
+    Rrd,
+    Shift(Shift, Operand),
+    /// Undocumented. LD r, RLC (IX+d) and the other rotates and shifts of an
+    /// indexed operand that also copy the result into a register.
+    LdShift(Register, Shift, Operand),
+    Bit(u8, Operand),
+    Set(u8, Operand),
+    /// Undocumented. LD r, SET b, (IX+d).
+    LdSet(Register, u8, Operand),
+    Res(u8, Operand),
+    /// Undocumented. LD r, RES b, (IX+d).
+    LdRes(Register, u8, Operand),
+    Jp(Option<Condition>, Operand),
+    Jr(Option<Condition>, Operand),
+    Djnz(Operand),
+    Call(Option<Condition>, Operand),
+    Ret(Option<Condition>),
+    Reti,
+    Retn,
+    Rst(u8),
+    /// IN r, (n) or IN r, (C), or the undocumented IN (C) when no register is
+    /// given.
+    In(Option<Register>, Operand),
+    Out(Operand, Operand),
+    Ini,
+    Inir,
+    Ind,
+    Indr,
+    Outi,
+    Otir,
+    Outd,
+    Otdr,
+}
+
+/// A decoded instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Instruction {
+    pub prefix: Prefix,
+    /// The opcode byte within the page selected by the prefix.
+    pub opcode: u8,
+    pub operation: Operation,
+}
+
+/// Where HL is used by an instruction, a DD or FD prefix replaces it by IX or
+/// IY, H and L by the halves of the index register, and (HL) by (IX+d) or
+/// (IY+d).
+#[derive(Clone, Copy)]
+enum Index {
+    Hl,
+    Ix,
+    Iy,
+}
+
+/// Reads the bytes of an instruction, treating those past the end of the
+/// slice as 0.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    length: usize,
+}
+
+impl Reader<'_> {
+    fn next_byte(&mut self) -> u8 {
+        let byte = self.bytes.get(self.length).copied().unwrap_or(0x00);
+        self.length += 1;
+        byte
+    }
+
+    fn next_displacement(&mut self) -> i8 {
+        self.next_byte() as i8
+    }
+
+    fn next_word(&mut self) -> u16 {
+        let low = self.next_byte();
+        let high = self.next_byte();
+        u16::from_le_bytes([low, high])
+    }
+}
+
+/// Decodes the instruction at the start of `bytes`, returning it with its
+/// length in bytes.
+///
+/// Only the last of a run of DD and FD prefixes has an effect, and the
+/// processor executes the run as part of the instruction that ends it, so it
+/// decodes as that instruction with the earlier prefixes counted in the
+/// length. A DD or FD prefix in front of an instruction that makes no use of HL is
+/// ignored, but counted in the length. Bytes past the end of the slice are
+/// read as 0, so a length greater than the slice shows the instruction was
+/// cut short.
+pub fn decode(bytes: &[u8]) -> (Instruction, usize) {
+    let reader = &mut Reader { bytes, length: 0 };
+    let instruction = decode_main(reader, Index::Hl);
+    (instruction, reader.length)
+}
+
+fn decode_main(reader: &mut Reader, index: Index) -> Instruction {
+    let opcode = reader.next_byte();
+    let prefix = match index {
+        Index::Hl => Prefix::None,
+        Index::Ix => Prefix::Dd,
+        Index::Iy => Prefix::Fd,
+    };
+    let instruction = |operation| Instruction {
+        prefix,
+        opcode,
+        operation,
+    };
+
+    let [x, y, z, p, q] = fields(opcode);
+    let operation = match (x, z) {
+        (0, 0) => match y {
+            0 => Operation::Nop,
+            1 => Operation::Ex(
+                Operand::RegisterPair(RegisterPair::Af),
+                Operand::RegisterPair(RegisterPair::AfPrime),
+            ),
+            2 => Operation::Djnz(Operand::Relative(reader.next_displacement())),
+            3 => Operation::Jr(None, Operand::Relative(reader.next_displacement())),
+            _ => Operation::Jr(
+                Some(condition(y - 4)),
+                Operand::Relative(reader.next_displacement()),
+            ),
+        },
+        (0, 1) if q == 0 => {
+            Operation::Ld(rp(p, index), Operand::ImmediateExtended(reader.next_word()))
+        }
+        (0, 1) => Operation::Add(rp(2, index), rp(p, index)),
+        (0, 2) => {
+            let a = Operand::Register(Register::A);
+            let memory = match p {
+                0 => Operand::RegisterIndirect(RegisterPair::Bc),
+                1 => Operand::RegisterIndirect(RegisterPair::De),
+                _ => Operand::Extended(reader.next_word()),
+            };
+            let register = if p == 2 { rp(2, index) } else { a };
+            if q == 0 {
+                Operation::Ld(memory, register)
+            } else {
+                Operation::Ld(register, memory)
+            }
+        }
+        (0, 3) if q == 0 => Operation::Inc(rp(p, index)),
+        (0, 3) => Operation::Dec(rp(p, index)),
+        (0, 4) => Operation::Inc(r(reader, y, index)),
+        (0, 5) => Operation::Dec(r(reader, y, index)),
+        (0, 6) => {
+            let register = r(reader, y, index);
+            Operation::Ld(register, Operand::Immediate(reader.next_byte()))
+        }
+        (0, _) => [
+            Operation::Rlca,
+            Operation::Rrca,
+            Operation::Rla,
+            Operation::Rra,
+            Operation::Daa,
+            Operation::Cpl,
+            Operation::Scf,
+            Operation::Ccf,
+        ][y as usize],
+        (1, 6) if y == 6 => Operation::Halt,
+        (1, _) if y == 6 || z == 6 => {
+            // The index register only replaces the memory operand, so the
+            // other one stays H or L
+            let (y_index, z_index) = if y == 6 {
+                (index, Index::Hl)
+            } else {
+                (Index::Hl, index)
+            };
+            let destination = r(reader, y, y_index);
+            Operation::Ld(destination, r(reader, z, z_index))
+        }
+        (1, _) => Operation::Ld(r(reader, y, index), r(reader, z, index)),
+        (2, _) => alu(y, r(reader, z, index)),
+        (_, 0) => Operation::Ret(Some(condition(y))),
+        (_, 1) if q == 0 => Operation::Pop(rp2(p, index)),
+        (_, 1) => match p {
+            0 => Operation::Ret(None),
+            1 => Operation::Exx,
+            2 => Operation::Jp(None, indirect(index)),
+            _ => Operation::Ld(Operand::RegisterPair(RegisterPair::Sp), rp(2, index)),
+        },
+        (_, 2) => Operation::Jp(
+            Some(condition(y)),
+            Operand::ImmediateExtended(reader.next_word()),
+        ),
+        (_, 3) => match y {
+            0 => Operation::Jp(None, Operand::ImmediateExtended(reader.next_word())),
+            1 => return decode_bit(reader, index),
+            2 => Operation::Out(
+                Operand::Port(reader.next_byte()),
+                Operand::Register(Register::A),
+            ),
+            3 => Operation::In(Some(Register::A), Operand::Port(reader.next_byte())),
+            4 => Operation::Ex(Operand::RegisterIndirect(RegisterPair::Sp), rp(2, index)),
+            5 => Operation::Ex(
+                Operand::RegisterPair(RegisterPair::De),
+                Operand::RegisterPair(RegisterPair::Hl),
+            ),
+            6 => Operation::Di,
+            _ => Operation::Ei,
+        },
+        (_, 4) => Operation::Call(
+            Some(condition(y)),
+            Operand::ImmediateExtended(reader.next_word()),
+        ),
+        (_, 5) if q == 0 => Operation::Push(rp2(p, index)),
+        (_, 5) => match p {
+            0 => Operation::Call(None, Operand::ImmediateExtended(reader.next_word())),
+            2 => return decode_misc(reader),
+            1 => return decode_index(reader, Index::Ix),
+            _ => return decode_index(reader, Index::Iy),
+        },
+        (_, 6) => alu(y, Operand::Immediate(reader.next_byte())),
+        _ => Operation::Rst(y * 8),
+    };
+
+    instruction(operation)
+}
+
+/// Decodes the instruction after a DD or FD prefix.
+fn decode_index(reader: &mut Reader, index: Index) -> Instruction {
+    decode_main(reader, index)
+}
+
+/// Decodes the instruction after a CB prefix, or after DDCB or FDCB, where the
+/// displacement comes before the opcode.
+fn decode_bit(reader: &mut Reader, index: Index) -> Instruction {
+    let (prefix, displacement) = match index {
+        Index::Hl => (Prefix::Cb, 0),
+        Index::Ix => (Prefix::DdCb, reader.next_displacement()),
+        Index::Iy => (Prefix::FdCb, reader.next_displacement()),
+    };
+    let opcode = reader.next_byte();
+    let [x, y, z, _, _] = fields(opcode);
+
+    let operation = match index {
+        Index::Hl => {
+            let operand = r(reader, z, Index::Hl);
+            match x {
+                0 => Operation::Shift(shift(y), operand),
+                1 => Operation::Bit(y, operand),
+                2 => Operation::Res(y, operand),
+                _ => Operation::Set(y, operand),
+            }
+        }
+        Index::Ix | Index::Iy => {
+            let index_register = if matches!(index, Index::Ix) {
+                RegisterPair::Ix
+            } else {
+                RegisterPair::Iy
+            };
+            let operand = Operand::Indexed(index_register, displacement);
+            // Apart from BIT, the result is also copied into the register
+            // that would have been the operand without the prefix
+            let copy = match r_plain(z) {
+                Operand::Register(register) => Some(register),
+                _ => None,
+            };
+            match (x, copy) {
+                (0, None) => Operation::Shift(shift(y), operand),
+                (0, Some(register)) => Operation::LdShift(register, shift(y), operand),
+                (1, _) => Operation::Bit(y, operand),
+                (2, None) => Operation::Res(y, operand),
+                (2, Some(register)) => Operation::LdRes(register, y, operand),
+                (_, None) => Operation::Set(y, operand),
+                (_, Some(register)) => Operation::LdSet(register, y, operand),
+            }
+        }
+    };
+
+    Instruction {
+        prefix,
+        opcode,
+        operation,
+    }
+}
+
+/// Decodes the instruction after an ED prefix. Any DD or FD prefix before it
+/// has no effect.
+fn decode_misc(reader: &mut Reader) -> Instruction {
+    let opcode = reader.next_byte();
+    let [x, y, z, p, q] = fields(opcode);
+
+    let operation = match (x, z) {
+        (1, 0) if y == 6 => Operation::In(None, Operand::PortC),
+        (1, 0) => match r_plain(y) {
+            Operand::Register(register) => Operation::In(Some(register), Operand::PortC),
+            _ => unreachable!("y = 6 is IN (C)"),
+        },
+        (1, 1) if y == 6 => Operation::Out(Operand::PortC, Operand::Immediate(0)),
+        (1, 1) => Operation::Out(Operand::PortC, r_plain(y)),
+        (1, 2) if q == 0 => Operation::Sbc(rp(2, Index::Hl), rp(p, Index::Hl)),
+        (1, 2) => Operation::Adc(rp(2, Index::Hl), rp(p, Index::Hl)),
+        (1, 3) => {
+            let memory = Operand::Extended(reader.next_word());
+            if q == 0 {
+                Operation::Ld(memory, rp(p, Index::Hl))
+            } else {
+                Operation::Ld(rp(p, Index::Hl), memory)
+            }
+        }
+        (1, 4) => Operation::Neg,
+        (1, 5) if y == 1 => Operation::Reti,
+        (1, 5) => Operation::Retn,
+        (1, 6) => Operation::Im([0, 0, 1, 2][(y & 0b11) as usize]),
+        (1, _) => {
+            let a = Operand::Register(Register::A);
+            let i = Operand::Register(Register::I);
+            let r = Operand::Register(Register::R);
+            match y {
+                0 => Operation::Ld(i, a),
+                1 => Operation::Ld(r, a),
+                2 => Operation::Ld(a, i),
+                3 => Operation::Ld(a, r),
+                4 => Operation::Rrd,
+                5 => Operation::Rld,
+                _ => Operation::Nop,
+            }
+        }
+        (2, 0..=3) if y >= 4 => [
+            [
+                Operation::Ldi,
+                Operation::Cpi,
+                Operation::Ini,
+                Operation::Outi,
+            ],
+            [
+                Operation::Ldd,
+                Operation::Cpd,
+                Operation::Ind,
+                Operation::Outd,
+            ],
+            [
+                Operation::Ldir,
+                Operation::Cpir,
+                Operation::Inir,
+                Operation::Otir,
+            ],
+            [
+                Operation::Lddr,
+                Operation::Cpdr,
+                Operation::Indr,
+                Operation::Otdr,
+            ],
+        ][(y - 4) as usize][z as usize],
+        _ => Operation::Nop,
+    };
+
+    Instruction {
+        prefix: Prefix::Ed,
+        opcode,
+        operation,
+    }
+}
+
+/// Splits an opcode into its x, y, z, p and q fields.
+fn fields(opcode: u8) -> [u8; 5] {
+    let y = (opcode >> 3) & 0b111;
+    [opcode >> 6, y, opcode & 0b111, y >> 1, y & 1]
+}
+
+/// The 8-bit register or (HL) operand selected by a 3-bit field, reading the
+/// displacement if (HL) is replaced by (IX+d) or (IY+d).
+fn r(reader: &mut Reader, field: u8, index: Index) -> Operand {
+    match (field, index) {
+        (4, Index::Ix) => Operand::Register(Register::Ixh),
+        (5, Index::Ix) => Operand::Register(Register::Ixl),
+        (4, Index::Iy) => Operand::Register(Register::Iyh),
+        (5, Index::Iy) => Operand::Register(Register::Iyl),
+        (6, Index::Ix) => Operand::Indexed(RegisterPair::Ix, reader.next_displacement()),
+        (6, Index::Iy) => Operand::Indexed(RegisterPair::Iy, reader.next_displacement()),
+        _ => r_plain(field),
+    }
+}
+
+fn r_plain(field: u8) -> Operand {
+    match field {
+        0 => Operand::Register(Register::B),
+        1 => Operand::Register(Register::C),
+        2 => Operand::Register(Register::D),
+        3 => Operand::Register(Register::E),
+        4 => Operand::Register(Register::H),
+        5 => Operand::Register(Register::L),
+        6 => Operand::RegisterIndirect(RegisterPair::Hl),
+        _ => Operand::Register(Register::A),
+    }
+}
+
+fn index_register(index: Index) -> RegisterPair {
+    match index {
+        Index::Hl => RegisterPair::Hl,
+        Index::Ix => RegisterPair::Ix,
+        Index::Iy => RegisterPair::Iy,
+    }
+}
+
+/// (HL), (IX) or (IY), as used by JP.
+fn indirect(index: Index) -> Operand {
+    Operand::RegisterIndirect(index_register(index))
+}
+
+/// The register pair selected by a 2-bit field, with SP as the last one.
+fn rp(field: u8, index: Index) -> Operand {
+    Operand::RegisterPair(match field {
+        0 => RegisterPair::Bc,
+        1 => RegisterPair::De,
+        2 => index_register(index),
+        _ => RegisterPair::Sp,
+    })
+}
+
+/// The register pair selected by a 2-bit field, with AF as the last one, as
+/// used by PUSH and POP.
+fn rp2(field: u8, index: Index) -> Operand {
+    match field {
+        3 => Operand::RegisterPair(RegisterPair::Af),
+        _ => rp(field, index),
+    }
+}
+
+fn condition(field: u8) -> Condition {
+    [
+        Condition::Nz,
+        Condition::Z,
+        Condition::Nc,
+        Condition::C,
+        Condition::Po,
+        Condition::Pe,
+        Condition::P,
+        Condition::M,
+    ][field as usize]
+}
+
+fn shift(field: u8) -> Shift {
+    [
+        Shift::Rlc,
+        Shift::Rrc,
+        Shift::Rl,
+        Shift::Rr,
+        Shift::Sla,
+        Shift::Sra,
+        Shift::Sll,
+        Shift::Srl,
+    ][field as usize]
+}
+
+fn alu(field: u8, operand: Operand) -> Operation {
+    let a = Operand::Register(Register::A);
+    match field {
+        0 => Operation::Add(a, operand),
+        1 => Operation::Adc(a, operand),
+        2 => Operation::Sub(operand),
+        3 => Operation::Sbc(a, operand),
+        4 => Operation::And(operand),
+        5 => Operation::Xor(operand),
+        6 => Operation::Or(operand),
+        _ => Operation::Cp(operand),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        tests::{Ports, Ram},
+        Z80,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let a = Operand::Register(Register::A);
+        let hl = Operand::RegisterPair(RegisterPair::Hl);
+        let ix = Operand::RegisterPair(RegisterPair::Ix);
+        let scenarios = [
+            (&[0x00][..], Prefix::None, 0x00, Operation::Nop, 1),
+            (
+                &[0x01, 0x34, 0x12],
+                Prefix::None,
+                0x01,
+                Operation::Ld(
+                    Operand::RegisterPair(RegisterPair::Bc),
+                    Operand::ImmediateExtended(0x1234),
+                ),
+                3,
+            ),
+            (
+                &[0x20, 0xFE],
+                Prefix::None,
+                0x20,
+                Operation::Jr(Some(Condition::Nz), Operand::Relative(-2)),
+                2,
+            ),
+            (
+                &[0x2A, 0x00, 0x80],
+                Prefix::None,
+                0x2A,
+                Operation::Ld(hl, Operand::Extended(0x8000)),
+                3,
+            ),
+            (
+                &[0x7E],
+                Prefix::None,
+                0x7E,
+                Operation::Ld(a, Operand::RegisterIndirect(RegisterPair::Hl)),
+                1,
+            ),
+            (
+                &[0xD3, 0xFE],
+                Prefix::None,
+                0xD3,
+                Operation::Out(Operand::Port(0xFE), a),
+                2,
+            ),
+            (
+                &[0xCB, 0x3E],
+                Prefix::Cb,
+                0x3E,
+                Operation::Shift(Shift::Srl, Operand::RegisterIndirect(RegisterPair::Hl)),
+                2,
+            ),
+            (&[0xED, 0x4E], Prefix::Ed, 0x4E, Operation::Im(0), 2),
+            (
+                &[0xED, 0x70],
+                Prefix::Ed,
+                0x70,
+                Operation::In(None, Operand::PortC),
+                2,
+            ),
+            (
+                &[0xED, 0x71],
+                Prefix::Ed,
+                0x71,
+                Operation::Out(Operand::PortC, Operand::Immediate(0)),
+                2,
+            ),
+            (&[0xED, 0x00], Prefix::Ed, 0x00, Operation::Nop, 2),
+            (
+                &[0xED, 0x43, 0x00, 0x40],
+                Prefix::Ed,
+                0x43,
+                Operation::Ld(
+                    Operand::Extended(0x4000),
+                    Operand::RegisterPair(RegisterPair::Bc),
+                ),
+                4,
+            ),
+            (
+                &[0xDD, 0x21, 0x00, 0x40],
+                Prefix::Dd,
+                0x21,
+                Operation::Ld(ix, Operand::ImmediateExtended(0x4000)),
+                4,
+            ),
+            (
+                &[0xDD, 0x36, 0x05, 0x22],
+                Prefix::Dd,
+                0x36,
+                Operation::Ld(
+                    Operand::Indexed(RegisterPair::Ix, 5),
+                    Operand::Immediate(0x22),
+                ),
+                4,
+            ),
+            (
+                &[0xDD, 0x66, 0xFE],
+                Prefix::Dd,
+                0x66,
+                Operation::Ld(
+                    Operand::Register(Register::H),
+                    Operand::Indexed(RegisterPair::Ix, -2),
+                ),
+                3,
+            ),
+            (
+                &[0xFD, 0x65],
+                Prefix::Fd,
+                0x65,
+                Operation::Ld(
+                    Operand::Register(Register::Iyh),
+                    Operand::Register(Register::Iyl),
+                ),
+                2,
+            ),
+            (
+                &[0xDD, 0xE9],
+                Prefix::Dd,
+                0xE9,
+                Operation::Jp(None, Operand::RegisterIndirect(RegisterPair::Ix)),
+                2,
+            ),
+            (
+                &[0xDD, 0xEB],
+                Prefix::Dd,
+                0xEB,
+                Operation::Ex(Operand::RegisterPair(RegisterPair::De), hl),
+                2,
+            ),
+            (&[0xDD, 0x00], Prefix::Dd, 0x00, Operation::Nop, 2),
+            (&[0xDD, 0xFD, 0x00], Prefix::Fd, 0x00, Operation::Nop, 3),
+            (
+                &[0xDD, 0xFD, 0x21, 0x34, 0x12],
+                Prefix::Fd,
+                0x21,
+                Operation::Ld(
+                    Operand::RegisterPair(RegisterPair::Iy),
+                    Operand::ImmediateExtended(0x1234),
+                ),
+                5,
+            ),
+            (&[0xFD, 0xED, 0xB0], Prefix::Ed, 0xB0, Operation::Ldir, 3),
+            (
+                &[0xFD, 0xCB, 0x01, 0x7E],
+                Prefix::FdCb,
+                0x7E,
+                Operation::Bit(7, Operand::Indexed(RegisterPair::Iy, 1)),
+                4,
+            ),
+            (
+                &[0xDD, 0xCB, 0x03, 0x00],
+                Prefix::DdCb,
+                0x00,
+                Operation::LdShift(
+                    Register::B,
+                    Shift::Rlc,
+                    Operand::Indexed(RegisterPair::Ix, 3),
+                ),
+                4,
+            ),
+            (
+                &[0xDD, 0xCB, 0x03, 0xFE],
+                Prefix::DdCb,
+                0xFE,
+                Operation::Set(7, Operand::Indexed(RegisterPair::Ix, 3)),
+                4,
+            ),
+        ];
+
+        for (bytes, prefix, opcode, operation, length) in scenarios {
+            let expected = Instruction {
+                prefix,
+                opcode,
+                operation,
+            };
+            assert_eq!((expected, length), decode(bytes), "{:02X?}", bytes);
+        }
+    }
+
+    #[test]
+    fn test_decode_past_end_of_bytes() {
+        let (instruction, length) = decode(&[0xC3, 0x00]);
+
+        assert_eq!(
+            Operation::Jp(None, Operand::ImmediateExtended(0x0000)),
+            instruction.operation
+        );
+        assert_eq!(3, length);
+    }
+
+    #[test]
+    fn test_decode_length_matches_execution() {
+        // A DDCB or FDCB opcode follows its displacement
+        let prefixes: [&[u8]; 7] = [
+            &[],
+            &[0xCB],
+            &[0xED],
+            &[0xDD],
+            &[0xFD],
+            &[0xDD, 0xCB, 0x05],
+            &[0xFD, 0xCB, 0x05],
+        ];
+
+        for prefix in prefixes {
+            for opcode in 0x00..=0xFF {
+                let mut bytes = vec![0x00; 0x10000];
+                let start = 0x8000;
+                let instruction_bytes = [prefix, &[opcode]].concat();
+                bytes[start..start + instruction_bytes.len()].copy_from_slice(&instruction_bytes);
+
+                let (instruction, length) = decode(&bytes[start..]);
+                let changes_flow = matches!(
+                    instruction.operation,
+                    Operation::Jp(..)
+                        | Operation::Jr(..)
+                        | Operation::Djnz(..)
+                        | Operation::Call(..)
+                        | Operation::Ret(..)
+                        | Operation::Reti
+                        | Operation::Retn
+                        | Operation::Rst(..)
+                        | Operation::Ldir
+                        | Operation::Lddr
+                        | Operation::Cpir
+                        | Operation::Cpdr
+                        | Operation::Inir
+                        | Operation::Indr
+                        | Operation::Otir
+                        | Operation::Otdr
+                );
+                if changes_flow {
+                    continue;
+                }
+
+                let ram = &mut Ram::new(&mut bytes);
+                let io = &mut Ports::new(&[]);
+                let z80 = &mut Z80::new();
+                z80.program_counter = start as u16;
+                z80.stack_pointer = 0xF000;
+                z80.process_next_instruction(ram, io);
+
+                assert_eq!(
+                    start + length,
+                    z80.program_counter as usize,
+                    "{:02X?}",
+                    instruction_bytes
+                );
+            }
+        }
+    }
+
+    /// The registers and other state an instruction can change. Only the
+    /// carry flag is modelled, as the decoded operation says nothing about
+    /// how the flags are set.
+    #[derive(Clone, Debug, PartialEq)]
+    struct Machine {
+        a: u8,
+        f: u8,
+        b: u8,
+        c: u8,
+        d: u8,
+        e: u8,
+        h: u8,
+        l: u8,
+        alternates: [u8; 8],
+        i: u8,
+        r: u8,
+        ix: u16,
+        iy: u16,
+        sp: u16,
+        pc: u16,
+        iff1: bool,
+        iff2: bool,
+        interrupt_mode: u8,
+        halted: bool,
+        writes: Vec<(u16, u8)>,
+    }
+
+    impl Machine {
+        fn of(z80: &Z80, writes: &[(u16, u8)]) -> Machine {
+            Machine {
+                a: z80.a.value(),
+                f: z80.f.value(),
+                b: z80.b.value(),
+                c: z80.c.value(),
+                d: z80.d.value(),
+                e: z80.e.value(),
+                h: z80.h.value(),
+                l: z80.l.value(),
+                alternates: [
+                    z80.a_prime.value(),
+                    z80.f_prime.value(),
+                    z80.b_prime.value(),
+                    z80.c_prime.value(),
+                    z80.d_prime.value(),
+                    z80.e_prime.value(),
+                    z80.h_prime.value(),
+                    z80.l_prime.value(),
+                ],
+                i: z80.i.value(),
+                r: z80.r.value(),
+                ix: z80.ix,
+                iy: z80.iy,
+                sp: z80.stack_pointer,
+                pc: z80.program_counter,
+                iff1: z80.iff1,
+                iff2: z80.iff2,
+                interrupt_mode: z80.interrupt_mode,
+                halted: z80.halted,
+                writes: writes.to_vec(),
+            }
+        }
+
+        fn register(&self, register: Register) -> u8 {
+            match register {
+                Register::A => self.a,
+                Register::B => self.b,
+                Register::C => self.c,
+                Register::D => self.d,
+                Register::E => self.e,
+                Register::H => self.h,
+                Register::L => self.l,
+                Register::I => self.i,
+                Register::R => self.r,
+                Register::Ixh => (self.ix >> 8) as u8,
+                Register::Ixl => self.ix as u8,
+                Register::Iyh => (self.iy >> 8) as u8,
+                Register::Iyl => self.iy as u8,
+            }
+        }
+
+        fn set_register(&mut self, register: Register, value: u8) {
+            match register {
+                Register::A => self.a = value,
+                Register::B => self.b = value,
+                Register::C => self.c = value,
+                Register::D => self.d = value,
+                Register::E => self.e = value,
+                Register::H => self.h = value,
+                Register::L => self.l = value,
+                Register::I => self.i = value,
+                Register::R => self.r = value,
+                Register::Ixh => self.ix = (self.ix & 0x00FF) | (value as u16) << 8,
+                Register::Ixl => self.ix = (self.ix & 0xFF00) | value as u16,
+                Register::Iyh => self.iy = (self.iy & 0x00FF) | (value as u16) << 8,
+                Register::Iyl => self.iy = (self.iy & 0xFF00) | value as u16,
+            }
+        }
+
+        fn pair(&self, pair: RegisterPair) -> u16 {
+            match pair {
+                RegisterPair::Af => u16::from_be_bytes([self.a, self.f]),
+                RegisterPair::AfPrime => {
+                    u16::from_be_bytes([self.alternates[0], self.alternates[1]])
+                }
+                RegisterPair::Bc => u16::from_be_bytes([self.b, self.c]),
+                RegisterPair::De => u16::from_be_bytes([self.d, self.e]),
+                RegisterPair::Hl => u16::from_be_bytes([self.h, self.l]),
+                RegisterPair::Sp => self.sp,
+                RegisterPair::Ix => self.ix,
+                RegisterPair::Iy => self.iy,
+            }
+        }
+
+        fn set_pair(&mut self, pair: RegisterPair, value: u16) {
+            let [high, low] = value.to_be_bytes();
+            match pair {
+                RegisterPair::Af => (self.a, self.f) = (high, low),
+                RegisterPair::AfPrime => (self.alternates[0], self.alternates[1]) = (high, low),
+                RegisterPair::Bc => (self.b, self.c) = (high, low),
+                RegisterPair::De => (self.d, self.e) = (high, low),
+                RegisterPair::Hl => (self.h, self.l) = (high, low),
+                RegisterPair::Sp => self.sp = value,
+                RegisterPair::Ix => self.ix = value,
+                RegisterPair::Iy => self.iy = value,
+            }
+        }
+
+        fn address(&self, operand: Operand) -> u16 {
+            match operand {
+                Operand::RegisterIndirect(pair) => self.pair(pair),
+                Operand::Indexed(pair, displacement) => {
+                    self.pair(pair).wrapping_add(displacement as u16)
+                }
+                Operand::Extended(address) => address,
+                _ => unreachable!("{:?} is not in memory", operand),
+            }
+        }
+
+        fn port(&self, operand: Operand) -> u16 {
+            match operand {
+                Operand::Port(port) => u16::from_be_bytes([self.a, port]),
+                Operand::PortC => self.pair(RegisterPair::Bc),
+                _ => unreachable!("{:?} is not a port", operand),
+            }
+        }
+
+        fn read(&self, memory: &[u8], operand: Operand) -> u8 {
+            match operand {
+                Operand::Register(register) => self.register(register),
+                Operand::Immediate(value) => value,
+                // Ports reads 0xFF once its input has run out
+                Operand::Port(_) | Operand::PortC => 0xFF,
+                _ => memory[self.address(operand) as usize],
+            }
+        }
+
+        fn write(&mut self, memory: &mut [u8], operand: Operand, value: u8) {
+            match operand {
+                Operand::Register(register) => self.set_register(register, value),
+                Operand::Port(_) | Operand::PortC => {
+                    let port = self.port(operand);
+                    self.writes.push((port, value));
+                }
+                _ => memory[self.address(operand) as usize] = value,
+            }
+        }
+
+        fn read_word(&self, memory: &[u8], operand: Operand) -> u16 {
+            match operand {
+                Operand::RegisterPair(pair) => self.pair(pair),
+                Operand::ImmediateExtended(value) => value,
+                _ => {
+                    let address = self.address(operand);
+                    u16::from_le_bytes([
+                        memory[address as usize],
+                        memory[address.wrapping_add(1) as usize],
+                    ])
+                }
+            }
+        }
+
+        fn write_word(&mut self, memory: &mut [u8], operand: Operand, value: u16) {
+            match operand {
+                Operand::RegisterPair(pair) => self.set_pair(pair, value),
+                _ => {
+                    let address = self.address(operand);
+                    let [low, high] = value.to_le_bytes();
+                    memory[address as usize] = low;
+                    memory[address.wrapping_add(1) as usize] = high;
+                }
+            }
+        }
+
+        fn push(&mut self, memory: &mut [u8], value: u16) {
+            self.sp = self.sp.wrapping_sub(2);
+            let stack = Operand::RegisterIndirect(RegisterPair::Sp);
+            self.write_word(memory, stack, value);
+        }
+
+        fn pop(&mut self, memory: &[u8]) -> u16 {
+            let value = self.read_word(memory, Operand::RegisterIndirect(RegisterPair::Sp));
+            self.sp = self.sp.wrapping_add(2);
+            value
+        }
+
+        fn step_pair(&mut self, pair: RegisterPair, step: i16) {
+            self.set_pair(pair, self.pair(pair).wrapping_add(step as u16));
+        }
+
+        /// Carries out `operation` with every flag but carry clear, so that
+        /// NZ, PO and P hold.
+        fn execute(&mut self, memory: &mut [u8], operation: Operation) {
+            let carry = self.f & 0x01;
+            let is_word = |operand| {
+                matches!(
+                    operand,
+                    Operand::RegisterPair(_) | Operand::ImmediateExtended(_)
+                )
+            };
+            let holds = |condition: Option<Condition>| match condition {
+                None | Some(Condition::Nz | Condition::Po | Condition::P) => true,
+                Some(Condition::Nc) => carry == 0,
+                Some(Condition::C) => carry == 1,
+                Some(Condition::Z | Condition::Pe | Condition::M) => false,
+            };
+            let step = |operation| match operation {
+                Operation::Ldi
+                | Operation::Ldir
+                | Operation::Cpi
+                | Operation::Cpir
+                | Operation::Ini
+                | Operation::Inir
+                | Operation::Outi
+                | Operation::Otir => 1,
+                _ => -1,
+            };
+            // A repeating block instruction goes round again by winding PC
+            // back onto itself
+            let repeat = |machine: &mut Machine, again: bool| {
+                if again {
+                    machine.pc = machine.pc.wrapping_sub(2);
+                }
+            };
+
+            match operation {
+                Operation::Nop | Operation::Scf | Operation::Ccf => {}
+                Operation::Bit(..) | Operation::Cp(_) => {}
+                Operation::Halt => self.halted = true,
+                Operation::Di => (self.iff1, self.iff2) = (false, false),
+                Operation::Ei => (self.iff1, self.iff2) = (true, true),
+                Operation::Im(mode) => self.interrupt_mode = mode,
+                Operation::Ld(destination, source) => {
+                    if is_word(destination) || is_word(source) {
+                        let value = self.read_word(memory, source);
+                        self.write_word(memory, destination, value);
+                    } else {
+                        let value = self.read(memory, source);
+                        self.write(memory, destination, value);
+                    }
+                }
+                Operation::Push(operand) => {
+                    let value = self.read_word(memory, operand);
+                    self.push(memory, value);
+                }
+                Operation::Pop(operand) => {
+                    let value = self.pop(memory);
+                    self.write_word(memory, operand, value);
+                }
+                Operation::Ex(Operand::RegisterPair(RegisterPair::Af), _) => {
+                    std::mem::swap(&mut self.a, &mut self.alternates[0]);
+                    std::mem::swap(&mut self.f, &mut self.alternates[1]);
+                }
+                Operation::Ex(first, second) => {
+                    let value = self.read_word(memory, first);
+                    let other = self.read_word(memory, second);
+                    self.write_word(memory, first, other);
+                    self.write_word(memory, second, value);
+                }
+                Operation::Exx => {
+                    let mut registers = [self.b, self.c, self.d, self.e, self.h, self.l];
+                    registers.swap_with_slice(&mut self.alternates[2..]);
+                    [self.b, self.c, self.d, self.e, self.h, self.l] = registers;
+                }
+                Operation::Ldi | Operation::Ldd | Operation::Ldir | Operation::Lddr => {
+                    let hl = self.pair(RegisterPair::Hl);
+                    let de = self.pair(RegisterPair::De);
+                    memory[de as usize] = memory[hl as usize];
+                    self.step_pair(RegisterPair::Hl, step(operation));
+                    self.step_pair(RegisterPair::De, step(operation));
+                    self.step_pair(RegisterPair::Bc, -1);
+                    let again = matches!(operation, Operation::Ldir | Operation::Lddr)
+                        && self.pair(RegisterPair::Bc) != 0;
+                    repeat(self, again);
+                }
+                Operation::Cpi | Operation::Cpd | Operation::Cpir | Operation::Cpdr => {
+                    let matched = self.a == memory[self.pair(RegisterPair::Hl) as usize];
+                    self.step_pair(RegisterPair::Hl, step(operation));
+                    self.step_pair(RegisterPair::Bc, -1);
+                    let again = matches!(operation, Operation::Cpir | Operation::Cpdr)
+                        && self.pair(RegisterPair::Bc) != 0
+                        && !matched;
+                    repeat(self, again);
+                }
+                Operation::Ini | Operation::Ind | Operation::Inir | Operation::Indr => {
+                    memory[self.pair(RegisterPair::Hl) as usize] = 0xFF;
+                    self.b = self.b.wrapping_sub(1);
+                    self.step_pair(RegisterPair::Hl, step(operation));
+                    let again =
+                        matches!(operation, Operation::Inir | Operation::Indr) && self.b != 0;
+                    repeat(self, again);
+                }
+                Operation::Outi | Operation::Outd | Operation::Otir | Operation::Otdr => {
+                    let value = memory[self.pair(RegisterPair::Hl) as usize];
+                    self.b = self.b.wrapping_sub(1);
+                    self.write(memory, Operand::PortC, value);
+                    self.step_pair(RegisterPair::Hl, step(operation));
+                    let again =
+                        matches!(operation, Operation::Otir | Operation::Otdr) && self.b != 0;
+                    repeat(self, again);
+                }
+                Operation::Add(destination, source) | Operation::Adc(destination, source)
+                    if is_word(destination) =>
+                {
+                    let carry = if matches!(operation, Operation::Adc(..)) {
+                        carry
+                    } else {
+                        0
+                    };
+                    let value = self
+                        .read_word(memory, destination)
+                        .wrapping_add(self.read_word(memory, source))
+                        .wrapping_add(carry as u16);
+                    self.write_word(memory, destination, value);
+                }
+                Operation::Sbc(destination, source) if is_word(destination) => {
+                    let value = self
+                        .read_word(memory, destination)
+                        .wrapping_sub(self.read_word(memory, source))
+                        .wrapping_sub(carry as u16);
+                    self.write_word(memory, destination, value);
+                }
+                Operation::Add(_, source) => {
+                    self.a = self.a.wrapping_add(self.read(memory, source));
+                }
+                Operation::Adc(_, source) => {
+                    self.a = self
+                        .a
+                        .wrapping_add(self.read(memory, source))
+                        .wrapping_add(carry);
+                }
+                Operation::Sub(source) => {
+                    self.a = self.a.wrapping_sub(self.read(memory, source));
+                }
+                Operation::Sbc(_, source) => {
+                    self.a = self
+                        .a
+                        .wrapping_sub(self.read(memory, source))
+                        .wrapping_sub(carry);
+                }
+                Operation::And(source) => self.a &= self.read(memory, source),
+                Operation::Or(source) => self.a |= self.read(memory, source),
+                Operation::Xor(source) => self.a ^= self.read(memory, source),
+                Operation::Inc(operand) | Operation::Dec(operand) => {
+                    let step = if matches!(operation, Operation::Inc(_)) {
+                        1
+                    } else {
+                        -1
+                    };
+                    if is_word(operand) {
+                        let value = self.read_word(memory, operand).wrapping_add(step as u16);
+                        self.write_word(memory, operand, value);
+                    } else {
+                        let value = self.read(memory, operand).wrapping_add(step as u8);
+                        self.write(memory, operand, value);
+                    }
+                }
+                Operation::Daa => {
+                    let mut correction = 0;
+                    if self.a & 0x0F > 9 {
+                        correction |= 0x06;
+                    }
+                    if self.a > 0x99 || carry == 1 {
+                        correction |= 0x60;
+                    }
+                    self.a = self.a.wrapping_add(correction);
+                }
+                Operation::Cpl => self.a = !self.a,
+                Operation::Neg => self.a = self.a.wrapping_neg(),
+                Operation::Rlca => self.a = self.a.rotate_left(1),
+                Operation::Rrca => self.a = self.a.rotate_right(1),
+                Operation::Rla => self.a = (self.a << 1) | carry,
+                Operation::Rra => self.a = (self.a >> 1) | (carry << 7),
+                Operation::Rld | Operation::Rrd => {
+                    let address = self.pair(RegisterPair::Hl) as usize;
+                    let value = memory[address];
+                    if operation == Operation::Rld {
+                        memory[address] = (value << 4) | (self.a & 0x0F);
+                        self.a = (self.a & 0xF0) | (value >> 4);
+                    } else {
+                        memory[address] = (self.a << 4) | (value >> 4);
+                        self.a = (self.a & 0xF0) | (value & 0x0F);
+                    }
+                }
+                Operation::Shift(kind, operand) | Operation::LdShift(_, kind, operand) => {
+                    let value = self.read(memory, operand);
+                    let value = match kind {
+                        Shift::Rlc => value.rotate_left(1),
+                        Shift::Rrc => value.rotate_right(1),
+                        Shift::Rl => (value << 1) | carry,
+                        Shift::Rr => (value >> 1) | (carry << 7),
+                        Shift::Sla => value << 1,
+                        Shift::Srl => value >> 1,
+                        Shift::Sra => (value >> 1) | (value & 0x80),
+                        Shift::Sll => (value << 1) | 0x01,
+                    };
+                    self.write(memory, operand, value);
+                    if let Operation::LdShift(register, ..) = operation {
+                        self.set_register(register, value);
+                    }
+                }
+                Operation::Set(bit, operand) | Operation::LdSet(_, bit, operand) => {
+                    let value = self.read(memory, operand) | (1 << bit);
+                    self.write(memory, operand, value);
+                    if let Operation::LdSet(register, ..) = operation {
+                        self.set_register(register, value);
+                    }
+                }
+                Operation::Res(bit, operand) | Operation::LdRes(_, bit, operand) => {
+                    let value = self.read(memory, operand) & !(1 << bit);
+                    self.write(memory, operand, value);
+                    if let Operation::LdRes(register, ..) = operation {
+                        self.set_register(register, value);
+                    }
+                }
+                Operation::Jp(condition, target) => {
+                    if holds(condition) {
+                        self.pc = match target {
+                            Operand::RegisterIndirect(pair) => self.pair(pair),
+                            _ => self.read_word(memory, target),
+                        };
+                    }
+                }
+                Operation::Jr(condition, Operand::Relative(displacement)) => {
+                    if holds(condition) {
+                        self.pc = self.pc.wrapping_add(displacement as u16);
+                    }
+                }
+                Operation::Djnz(Operand::Relative(displacement)) => {
+                    self.b = self.b.wrapping_sub(1);
+                    if self.b != 0 {
+                        self.pc = self.pc.wrapping_add(displacement as u16);
+                    }
+                }
+                Operation::Call(condition, target) => {
+                    if holds(condition) {
+                        self.push(memory, self.pc);
+                        self.pc = self.read_word(memory, target);
+                    }
+                }
+                Operation::Ret(condition) => {
+                    if holds(condition) {
+                        self.pc = self.pop(memory);
+                    }
+                }
+                Operation::Reti | Operation::Retn => {
+                    self.pc = self.pop(memory);
+                    self.iff1 = self.iff2;
+                }
+                Operation::Rst(address) => {
+                    self.push(memory, self.pc);
+                    self.pc = address as u16;
+                }
+                Operation::In(register, _) => {
+                    if let Some(register) = register {
+                        self.set_register(register, 0xFF);
+                    }
+                }
+                Operation::Out(port, source) => {
+                    let value = self.read(memory, source);
+                    self.write(memory, port, value);
+                }
+                Operation::Jr(..) | Operation::Djnz(_) => {
+                    unreachable!("{:?} has no displacement", operation)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decode_operation_matches_execution() {
+        let prefixes: [&[u8]; 7] = [
+            &[],
+            &[0xCB],
+            &[0xED],
+            &[0xDD],
+            &[0xFD],
+            &[0xDD, 0xCB, 0x05],
+            &[0xFD, 0xCB, 0x05],
+        ];
+
+        for prefix in prefixes {
+            for opcode in 0x00..=0xFF {
+                for carry in [false, true] {
+                    let start = 0x8000;
+                    let instruction_bytes = [prefix, &[opcode, 0x34, 0x12]].concat();
+                    let mut bytes: Vec<u8> = (0..0x10000).map(|i| (i ^ (i >> 8)) as u8).collect();
+                    bytes[start..start + instruction_bytes.len()]
+                        .copy_from_slice(&instruction_bytes);
+
+                    let z80 = &mut Z80::new();
+                    z80.program_counter = start as u16;
+                    z80.stack_pointer = 0xF000;
+                    z80.ix = 0x5000;
+                    z80.iy = 0x6000;
+                    z80.i.set_value(0x3F);
+                    // Two refreshes carry into bit 7, which R keeps as it is
+                    z80.r.set_value(0x7E);
+                    // DAA corrects both digits
+                    z80.a.set_value(0x9C);
+                    z80.f.set_value(carry as u8);
+                    for (register, value) in [
+                        (&mut z80.b, 0x02),
+                        (&mut z80.c, 0x03),
+                        (&mut z80.d, 0x44),
+                        (&mut z80.e, 0x55),
+                        (&mut z80.h, 0x46),
+                        (&mut z80.l, 0x77),
+                        (&mut z80.a_prime, 0x11),
+                        (&mut z80.f_prime, 0x22),
+                        (&mut z80.b_prime, 0x33),
+                        (&mut z80.c_prime, 0x44),
+                        (&mut z80.d_prime, 0x55),
+                        (&mut z80.e_prime, 0x66),
+                        (&mut z80.h_prime, 0x77),
+                        (&mut z80.l_prime, 0x88),
+                    ] {
+                        register.set_value(value);
+                    }
+                    // Different so that RETI, RETN, DI and EI can be told apart
+                    z80.iff1 = false;
+                    z80.iff2 = true;
+                    z80.interrupt_mode = 1;
+
+                    let (instruction, length) = decode(&bytes[start..]);
+                    let mut expected = Machine::of(z80, &[]);
+                    let mut expected_bytes = bytes.clone();
+                    // Each prefix and the opcode are fetched by M1 cycles,
+                    // except the displacement and opcode of DDCB and FDCB
+                    let index_prefixes = instruction_bytes
+                        .iter()
+                        .take_while(|&&byte| byte == 0xDD || byte == 0xFD)
+                        .count();
+                    let refreshes = match instruction_bytes[index_prefixes] {
+                        0xCB if index_prefixes > 0 => index_prefixes + 1,
+                        0xCB | 0xED => index_prefixes + 2,
+                        _ => index_prefixes + 1,
+                    } as u8;
+                    let refreshed = expected.r.wrapping_add(refreshes) & 0x7F;
+                    expected.r = (expected.r & 0x80) | refreshed;
+                    expected.pc = expected.pc.wrapping_add(length as u16);
+                    expected.execute(&mut expected_bytes, instruction.operation);
+
+                    let ram = &mut Ram::new(&mut bytes);
+                    let io = &mut Ports::new(&[]);
+                    z80.process_next_instruction(ram, io);
+                    expected.f = z80.f.value();
+
+                    assert_eq!(
+                        expected,
+                        Machine::of(z80, &io.writes),
+                        "{:02X?} {:?} with carry {}",
+                        &instruction_bytes[..length],
+                        instruction.operation,
+                        carry
+                    );
+                    let difference = (0..bytes.len()).find(|&i| bytes[i] != expected_bytes[i]);
+                    assert_eq!(
+                        None,
+                        difference,
+                        "{:02X?} {:?} with carry {}",
+                        &instruction_bytes[..length],
+                        instruction.operation,
+                        carry
+                    );
+                }
+            }
+        }
+    }
+}
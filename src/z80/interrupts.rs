@@ -0,0 +1,482 @@
+use super::{register_flags::P_FLAG_BITMASK, MachineCycle, Opcodes, Z80Io, Z80Memory, Z80};
+
+impl Z80 {
+    // Interrupts
+
+    /// Asserts the maskable interrupt line, with `data` being the byte the
+    /// interrupting device places on the data bus when the interrupt is
+    /// acknowledged. INT is level triggered, so the line stays asserted until
+    /// [`Z80::release_interrupt`] is called.
+    pub fn request_interrupt(&mut self, data: u8) {
+        self.interrupt_request = Some(data);
+    }
+
+    /// Releases the maskable interrupt line.
+    pub fn release_interrupt(&mut self) {
+        self.interrupt_request = None;
+    }
+
+    /// Signals a falling edge on the non-maskable interrupt line. The request
+    /// is latched and accepted at the next instruction boundary.
+    pub fn request_nmi(&mut self) {
+        self.nmi_request = true;
+    }
+
+    /// Accepts a pending interrupt at an instruction boundary, returning the
+    /// T states taken by the acknowledge, or `None` when there is nothing to
+    /// accept. A non-maskable interrupt takes priority over a maskable one,
+    /// and a maskable interrupt is not accepted directly after EI.
+    pub(super) fn accept_interrupt<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> Option<u8> {
+        let delayed = std::mem::take(&mut self.interrupt_delay);
+        let after_ld_a_ir = std::mem::take(&mut self.after_ld_a_ir);
+
+        if self.nmi_request {
+            self.nmi_request = false;
+            return Some(self.accept_nmi(mem));
+        }
+
+        match self.interrupt_request {
+            Some(data) if self.iff1 && !delayed => {
+                if after_ld_a_ir && !self.variant.is_cmos() {
+                    // NMOS parts accept the interrupt while LD A, I or LD A, R
+                    // is copying IFF2 into P/V, which then reads as 0.
+                    let f = self.f.value();
+                    self.f.set_value(f & !P_FLAG_BITMASK);
+                }
+
+                Some(self.accept_int(data, mem, io))
+            }
+            _ => None,
+        }
+    }
+
+    /// ## NMI
+    ///
+    /// ### Operation
+    ///
+    /// IFF1 ← 0, (SP – 1) ← PCH, (SP – 2) ← PCL, PC ← 0066h
+    ///
+    /// ### Description
+    ///
+    /// The nonmaskable interrupt cannot be disabled by the programmer. It
+    /// also ends the HALT state, with the address of the instruction
+    /// following the HALT being pushed as the return address. IFF1 is
+    /// reset to lock out maskable interrupts, while IFF2 keeps the state IFF1
+    /// had before the interrupt so that RETN can restore it. The program
+    /// counter is pushed onto the stack and execution continues at 0066h.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (5, 3, 3)    | 2.75       |
+    fn accept_nmi(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.iff1 = false;
+        self.halted = false;
+
+        // The opcode fetched in the first machine cycle is ignored
+        self.opcode_fetch_cycle(mem, self.program_counter);
+        self.internal_cycles(mem, 1);
+
+        self.push_program_counter(mem);
+        self.program_counter = 0x0066;
+        self.memptr = self.program_counter;
+
+        // T states
+        11
+    }
+
+    /// ## INT
+    ///
+    /// ### Operation
+    ///
+    /// IFF1 ← 0, IFF2 ← 0, then as selected by the interrupt mode
+    ///
+    /// ### Description
+    ///
+    /// A maskable interrupt is accepted when IFF1 is set, ending any HALT
+    /// state. Both flip flops are reset, and the acknowledge cycle adds two wait states to the opcode
+    /// fetch before the interrupt is serviced according to the mode:
+    ///
+    /// - Mode 0: the byte on the data bus is executed as an instruction,
+    ///   normally one of the RST instructions. Any operand bytes are read
+    ///   from memory at the program counter.
+    /// - Mode 1: a restart to location 0038h.
+    /// - Mode 2: the I register forms the high byte and the data bus the low
+    ///   byte of a pointer into a vector table. The program counter is loaded
+    ///   from the two bytes at that address.
+    ///
+    /// Mode 0 with an RST instruction and Mode 1:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 13 (7, 3, 3)    | 3.25       |
+    ///
+    /// Mode 2:
+    ///
+    /// | M Cycles | T States              | 4 MHz E.T. |
+    /// | -------- | --------------------- | ---------- |
+    /// | 5        | 19 (7, 3, 3, 3, 3)    | 4.75       |
+    fn accept_int<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        data: u8,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        self.iff1 = false;
+        self.iff2 = false;
+        self.halted = false;
+
+        // The acknowledge cycle puts the program counter on the address bus,
+        // but the data is read from the interrupting device
+        let cycle = MachineCycle::InterruptAcknowledge(self.program_counter);
+        self.begin_machine_cycle(mem, cycle);
+        self.refresh_cycle();
+        self.t_states += 6;
+
+        match self.interrupt_mode {
+            0 => {
+                let opcode_function = Opcodes::<M, I>::MAIN_FUNCTIONS[data as usize];
+
+                // T states
+                2 + opcode_function(self, mem, io)
+            }
+            1 => {
+                self.internal_cycles(mem, 1);
+                self.push_program_counter(mem);
+                self.program_counter = 0x0038;
+                self.memptr = self.program_counter;
+
+                // T states
+                13
+            }
+            _ => {
+                self.internal_cycles(mem, 1);
+                self.push_program_counter(mem);
+                let vector = u16::from_be_bytes([self.i.value(), data]);
+                let low = self.read_memory(mem, vector);
+                let high = self.read_memory(mem, vector.wrapping_add(1));
+                self.program_counter = u16::from_le_bytes([low, high]);
+                self.memptr = self.program_counter;
+
+                // T states
+                19
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::p_flag,
+        tests::{Ports, Ram},
+        CpuVariant,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_interrupt_mode_1() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.program_counter = 0x1234;
+        z80.stack_pointer = 0x0010;
+        z80.interrupt_mode = 1;
+
+        z80.request_interrupt(0xFF);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(13, t_states);
+
+        assert_eq!(0x0038, z80.program_counter);
+        assert_eq!(0x000E, z80.stack_pointer);
+        assert_eq!(0x34, ram.read(0x000E));
+        assert_eq!(0x12, ram.read(0x000F));
+        assert_eq!(false, z80.iff1);
+        assert_eq!(false, z80.iff2);
+    }
+
+    #[test]
+    fn test_interrupt_mode_0_executes_data_bus() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.program_counter = 0x0005;
+        z80.stack_pointer = 0x0010;
+
+        z80.request_interrupt(0xD7); // RST 10h
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(13, t_states);
+
+        assert_eq!(0x0010, z80.program_counter);
+        assert_eq!(0x05, ram.read(0x000E));
+    }
+
+    #[test]
+    fn test_interrupt_mode_2() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x08] = 0x78;
+        bytes[0x09] = 0x56;
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.program_counter = 0x1234;
+        z80.stack_pointer = 0x0008;
+        z80.interrupt_mode = 2;
+        z80.i.set_value(0x00);
+
+        z80.request_interrupt(0x08);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(19, t_states);
+
+        assert_eq!(0x5678, z80.program_counter);
+        assert_eq!(0x0006, z80.stack_pointer);
+        assert_eq!(0x5678, z80.memptr);
+    }
+
+    #[test]
+    fn test_interrupts_disabled_at_reset() {
+        let mut bytes = [
+            0xF3, // DI
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        assert_eq!(false, z80.iff1);
+        assert_eq!(false, z80.iff2);
+        assert_eq!(0, z80.interrupt_mode);
+
+        z80.request_interrupt(0xFF);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(4, t_states);
+
+        assert_eq!(1, z80.program_counter);
+        assert_eq!(0, z80.stack_pointer);
+    }
+
+    #[test]
+    fn test_interrupt_masked() {
+        let mut bytes = [
+            0x00, // NOP
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.di();
+
+        z80.request_interrupt(0xFF);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(4, t_states);
+
+        assert_eq!(1, z80.program_counter);
+    }
+
+    #[test]
+    fn test_interrupt_delayed_after_ei() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x00] = 0xFB; // EI
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.stack_pointer = 0x0010;
+        z80.interrupt_mode = 1;
+        z80.di();
+
+        z80.request_interrupt(0xFF);
+        z80.process_next_instruction(ram, io);
+        z80.process_next_instruction(ram, io);
+        assert_eq!(2, z80.program_counter);
+
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(13, t_states);
+        assert_eq!(0x0038, z80.program_counter);
+    }
+
+    #[test]
+    fn test_interrupt_released() {
+        let mut bytes = [
+            0x00, // NOP
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+
+        z80.request_interrupt(0xFF);
+        z80.release_interrupt();
+        z80.process_next_instruction(ram, io);
+
+        assert_eq!(1, z80.program_counter);
+    }
+
+    #[test]
+    fn test_nmi_and_retn() {
+        let mut bytes = [0x00; 0x80];
+        bytes[0x66] = 0xED; // RETN
+        bytes[0x67] = 0x45;
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.program_counter = 0x0004;
+        z80.stack_pointer = 0x0010;
+
+        z80.request_nmi();
+        z80.request_interrupt(0xFF);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(11, t_states);
+
+        assert_eq!(0x0066, z80.program_counter);
+        assert_eq!(false, z80.iff1);
+        assert_eq!(true, z80.iff2);
+
+        z80.release_interrupt();
+        z80.process_next_instruction(ram, io);
+
+        assert_eq!(0x0004, z80.program_counter);
+        assert_eq!(true, z80.iff1);
+    }
+
+    #[test]
+    fn test_halt_until_interrupt() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x00] = 0x76; // HALT
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.stack_pointer = 0x0010;
+        z80.interrupt_mode = 1;
+
+        z80.process_next_instruction(ram, io);
+        assert_eq!(true, z80.is_halted());
+
+        for _ in 0..3 {
+            let t_states = z80.process_next_instruction(ram, io);
+            assert_eq!(4, t_states);
+        }
+        assert_eq!(true, z80.is_halted());
+        assert_eq!(1, z80.program_counter);
+        assert_eq!(4, z80.r.value());
+
+        z80.request_interrupt(0xFF);
+        let t_states = z80.process_next_instruction(ram, io);
+        assert_eq!(13, t_states);
+
+        assert_eq!(false, z80.is_halted());
+        assert_eq!(0x0038, z80.program_counter);
+        assert_eq!(0x01, ram.read(0x000E));
+        assert_eq!(5, z80.r.value());
+    }
+
+    #[test]
+    fn test_interrupt_after_ld_a_ir_resets_parity() {
+        let scenarios = [
+            (CpuVariant::ZilogNmos, 0x57, false), // LD A, I
+            (CpuVariant::NecNmos, 0x5F, false),   // LD A, R
+            (CpuVariant::ZilogCmos, 0x57, true),
+            (CpuVariant::Toshiba, 0x5F, true),
+        ];
+
+        for (variant, opcode, parity) in scenarios {
+            let mut bytes = [0x00; 0x10];
+            bytes[0x00] = 0xED;
+            bytes[0x01] = opcode;
+            let ram = &mut Ram::new(&mut bytes);
+            let io = &mut Ports::new(&[]);
+            let z80 = &mut Z80::with_variant(variant);
+            z80.iff1 = true;
+            z80.iff2 = true;
+            z80.stack_pointer = 0x0010;
+            z80.interrupt_mode = 1;
+
+            z80.process_next_instruction(ram, io);
+            assert_eq!(true, p_flag(&z80.f));
+
+            z80.request_interrupt(0xFF);
+            z80.process_next_instruction(ram, io);
+
+            assert_eq!(0x0038, z80.program_counter);
+            assert_eq!(parity, p_flag(&z80.f));
+        }
+    }
+
+    #[test]
+    fn test_ld_a_ir_parity_kept_without_interrupt() {
+        let mut bytes = [
+            0xED, 0x57, // LD A, I
+            0x00, // NOP
+        ];
+        // With INT released, and with INT asserted while IFF1 masks it, as
+        // it does after an NMI
+        let scenarios = [(true, None), (false, Some(0xFF))];
+
+        for (iff1, interrupt) in scenarios {
+            let ram = &mut Ram::new(&mut bytes);
+            let io = &mut Ports::new(&[]);
+            let z80 = &mut Z80::new();
+            z80.stack_pointer = 0x0010;
+            z80.interrupt_mode = 1;
+            z80.iff1 = iff1;
+            z80.iff2 = true;
+            if let Some(data) = interrupt {
+                z80.request_interrupt(data);
+            }
+
+            z80.process_next_instruction(ram, io);
+            assert_eq!(true, p_flag(&z80.f));
+            z80.process_next_instruction(ram, io);
+
+            assert_eq!(3, z80.program_counter);
+            assert_eq!(true, p_flag(&z80.f));
+        }
+    }
+
+    #[test]
+    fn test_interrupt_between_ldir_iterations() {
+        let mut bytes = [0x00; 0x10];
+        bytes[0x00] = 0xED; // LDIR
+        bytes[0x01] = 0xB0;
+        bytes[0x08] = 0x11;
+        bytes[0x09] = 0x22;
+        bytes[0x0A] = 0x33;
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.iff1 = true;
+        z80.iff2 = true;
+        z80.stack_pointer = 0x0010;
+        z80.interrupt_mode = 1;
+        z80.set_hl(0x0008);
+        z80.set_de(0x000C);
+        z80.set_bc(0x0003);
+
+        assert_eq!(21, z80.process_next_instruction(ram, io));
+        assert_eq!(0x0000, z80.program_counter);
+
+        z80.request_interrupt(0xFF);
+        assert_eq!(13, z80.process_next_instruction(ram, io));
+        assert_eq!(0x0038, z80.program_counter);
+        assert_eq!(0x0002, z80.bc());
+        assert_eq!(0x000E, z80.stack_pointer);
+        assert_eq!(
+            0x0000,
+            ram.read(0x000E) as u16 | (ram.read(0x000F) as u16) << 8
+        );
+        assert_eq!(0x11, ram.read(0x000C));
+    }
+}
@@ -0,0 +1,597 @@
+use super::{
+    register_flags::{c_flag, p_flag, s_flag, z_flag},
+    Z80Memory, Z80,
+};
+
+impl Z80 {
+    // Jump Group
+
+    /// ## JP nn
+    ///
+    /// ### Operation
+    ///
+    /// PC ← nn
+    ///
+    /// ### Op Code
+    ///
+    /// JP: `1 1 0 0 0 0 1 1` (C3) followed by `n n n n n n n n` (low order)
+    /// and `n n n n n n n n` (high order)
+    ///
+    /// ### Operands
+    ///
+    /// nn
+    ///
+    /// ### Description
+    ///
+    /// Operand nn is loaded to register pair Program Counter (PC). The next
+    /// instruction is fetched from the location designated by the new contents
+    /// of the PC.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 10 (4, 3, 3)    | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn jp_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(true, mem)
+    }
+
+    /// ## JP cc, nn
+    ///
+    /// ### Operation
+    ///
+    /// IF cc true, PC ← nn
+    ///
+    /// ### Op Code
+    ///
+    /// JP: `1 1 c c c 0 1 0` followed by `n n n n n n n n` (low order) and
+    /// `n n n n n n n n` (high order)
+    ///
+    /// ### Operands
+    ///
+    /// cc, nn
+    ///
+    /// ### Description
+    ///
+    /// If condition cc is true, the instruction loads operand nn to register
+    /// pair Program Counter (PC), and the program continues with the
+    /// instruction beginning at address nn. If condition cc is false, the
+    /// Program Counter is incremented as usual, and the program continues with
+    /// the next sequential instruction. Condition cc is programmed as one of
+    /// eight statuses that correspond to condition bits in the Flag Register
+    /// (Register F). These eight statuses are defined in the following table,
+    /// which specifies the corresponding cc bit fields in the assembled object
+    /// code.
+    ///
+    /// | cc  | Condition       | Relevant Flag |
+    /// | --- | --------------- | ------------- |
+    /// | 000 | Non-Zero (NZ)   | Z             |
+    /// | 001 | Zero (Z)        | Z             |
+    /// | 010 | No Carry (NC)   | C             |
+    /// | 011 | Carry (C)       | C             |
+    /// | 100 | Parity Odd (PO) | P/V           |
+    /// | 101 | Parity Even(PE) | P/V           |
+    /// | 110 | Sign Positive(P)| S             |
+    /// | 111 | Sign Negative(M)| S             |
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 10 (4, 3, 3)    | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Carry flag (C flag in the F register) is set and address 1520h
+    /// contains 03h, then upon the execution of a JP C, 1520h instruction, the
+    /// Program Counter contains 1520h and, on the next machine cycle, the CPU
+    /// fetches opcode byte 03h from address 1520h.
+    fn jp_cc_nn(&mut self, condition: bool, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let address = self.fetch_next_address(mem);
+        self.memptr = address;
+
+        if condition {
+            self.program_counter = address;
+        }
+
+        // T states
+        10
+    }
+
+    pub fn jp_nz_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(!z_flag(&self.f), mem)
+    }
+
+    pub fn jp_z_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(z_flag(&self.f), mem)
+    }
+
+    pub fn jp_nc_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(!c_flag(&self.f), mem)
+    }
+
+    pub fn jp_c_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(c_flag(&self.f), mem)
+    }
+
+    pub fn jp_po_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(!p_flag(&self.f), mem)
+    }
+
+    pub fn jp_pe_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(p_flag(&self.f), mem)
+    }
+
+    pub fn jp_p_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(!s_flag(&self.f), mem)
+    }
+
+    pub fn jp_m_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jp_cc_nn(s_flag(&self.f), mem)
+    }
+
+    /// ## JR e
+    ///
+    /// ### Operation
+    ///
+    /// PC ← PC + e
+    ///
+    /// ### Op Code
+    ///
+    /// JR: `0 0 0 1 1 0 0 0` (18) followed by `e-2`
+    ///
+    /// ### Operands
+    ///
+    /// e
+    ///
+    /// ### Description
+    ///
+    /// This instruction provides for unconditional branching to other segments
+    /// of a program. The value of displacement e is added to the Program
+    /// Counter (PC) and the next instruction is fetched from the location
+    /// designated by the new contents of the PC. This jump is measured from
+    /// the address of the instruction op code and contains a range of –126 to
+    /// +129 bytes. The assembler automatically adjusts for the twice
+    /// incremented PC.
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 12 (4, 3, 5)    | 3.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// To jump forward five locations from address 480, the following assembly
+    /// language statement is used: JR $+5
+    pub fn jr_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jr_cc_e(true, mem)
+    }
+
+    /// ## JR cc, e
+    ///
+    /// ### Operation
+    ///
+    /// If cc true, PC ← PC + e
+    ///
+    /// ### Op Code
+    ///
+    /// JR NZ, e: `0 0 1 0 0 0 0 0` (20) followed by `e-2`
+    ///
+    /// JR Z, e: `0 0 1 0 1 0 0 0` (28) followed by `e-2`
+    ///
+    /// JR NC, e: `0 0 1 1 0 0 0 0` (30) followed by `e-2`
+    ///
+    /// JR C, e: `0 0 1 1 1 0 0 0` (38) followed by `e-2`
+    ///
+    /// ### Operands
+    ///
+    /// cc, e
+    ///
+    /// ### Description
+    ///
+    /// This instruction provides for conditional branching to other segments
+    /// of a program depending on the results of a test of the Zero or Carry
+    /// flag. If the condition is met, the value of displacement e is added to
+    /// the Program Counter (PC), and the next instruction is fetched from the
+    /// location designated by the new contents of the PC. The jump is measured
+    /// from the address of the instruction op code and contains a range of –126
+    /// to +129 bytes. If the condition is not met, the next instruction
+    /// executed is taken from the location following this instruction.
+    ///
+    /// If condition is met:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 12 (4, 3, 5)    | 3.00       |
+    ///
+    /// If condition is not met:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 2        | 7 (4, 3)        | 1.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// The Carry flag is set and it is required to jump back four locations
+    /// from 480. The assembler object code is 38 FA. Upon executing a JR C, $–4
+    /// instruction, the next instruction executed is located at 47C.
+    fn jr_cc_e(&mut self, condition: bool, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let e = self.fetch_next_byte(mem);
+
+        if !condition {
+            // T states
+            return 7;
+        }
+
+        self.internal_cycles(mem, 5);
+        self.jump_relative(e);
+
+        // T states
+        12
+    }
+
+    /// Adds the signed displacement of a relative jump to the program counter,
+    /// which already points at the next instruction.
+    fn jump_relative(&mut self, e: u8) {
+        let displacement = i8::from_ne_bytes(e.to_ne_bytes());
+        self.program_counter = self
+            .program_counter
+            .wrapping_add_signed(displacement as i16);
+        self.memptr = self.program_counter;
+    }
+
+    pub fn jr_nz_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jr_cc_e(!z_flag(&self.f), mem)
+    }
+
+    pub fn jr_z_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jr_cc_e(z_flag(&self.f), mem)
+    }
+
+    pub fn jr_nc_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jr_cc_e(!c_flag(&self.f), mem)
+    }
+
+    pub fn jr_c_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.jr_cc_e(c_flag(&self.f), mem)
+    }
+
+    /// ## JP (HL)
+    ///
+    /// ### Operation
+    ///
+    /// PC ← HL
+    ///
+    /// ### Op Code
+    ///
+    /// JP: `1 1 1 0 1 0 0 1` (E9)
+    ///
+    /// ### Operands
+    ///
+    /// (HL)
+    ///
+    /// ### Description
+    ///
+    /// The Program Counter (PC) is loaded with the contents of the HL register
+    /// pair. The next instruction is fetched from the location designated by
+    /// the new contents of the PC.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Program Counter contains 1000h and the HL register pair contains
+    /// 4800h, then upon the execution of a JP (HL) instruction, the Program
+    /// Counter contains 4800h.
+    pub fn jp_mem_hl(&mut self) -> u8 {
+        self.program_counter = self.hl();
+
+        // T states
+        4
+    }
+
+    /// ## JP (IX)
+    ///
+    /// ### Operation
+    ///
+    /// PC ← IX
+    ///
+    /// ### Op Code
+    ///
+    /// JP: `1 1 0 1 1 1 0 1` (DD) `1 1 1 0 1 0 0 1` (E9)
+    ///
+    /// ### Operands
+    ///
+    /// (IX)
+    ///
+    /// ### Description
+    ///
+    /// The Program Counter (PC) is loaded with the contents of the IX register
+    /// pair. The next instruction is fetched from the location designated by
+    /// the new contents of the PC.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 8 (4, 4)    | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Program Counter contains 1000h and the IX register pair contains
+    /// 4800h, then upon the execution of a JP (IX) instruction, the Program
+    /// Counter contains 4800h.
+    pub fn jp_mem_ix(&mut self) -> u8 {
+        self.program_counter = self.ix;
+
+        // T states
+        8
+    }
+
+    /// ## JP (IY)
+    ///
+    /// ### Operation
+    ///
+    /// PC ← IY
+    ///
+    /// ### Op Code
+    ///
+    /// JP: `1 1 1 1 1 1 0 1` (FD) `1 1 1 0 1 0 0 1` (E9)
+    ///
+    /// ### Operands
+    ///
+    /// (IY)
+    ///
+    /// ### Description
+    ///
+    /// The Program Counter (PC) is loaded with the contents of the IY register
+    /// pair. The next instruction is fetched from the location designated by
+    /// the new contents of the PC.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 8 (4, 4)    | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the Program Counter contains 1000h and the IY register pair contains
+    /// 4800h, then upon the execution of a JP (IY) instruction, the Program
+    /// Counter contains 4800h.
+    pub fn jp_mem_iy(&mut self) -> u8 {
+        self.program_counter = self.iy;
+
+        // T states
+        8
+    }
+
+    /// ## DJNZ, e
+    ///
+    /// ### Operation
+    ///
+    /// B ← B – 1, if B ≠ 0, PC ← PC + e
+    ///
+    /// ### Op Code
+    ///
+    /// DJNZ: `0 0 0 1 0 0 0 0` (10) followed by `e-2`
+    ///
+    /// ### Operands
+    ///
+    /// e
+    ///
+    /// ### Description
+    ///
+    /// This instruction is similar to the conditional jump instructions except
+    /// that a register value is used to determine branching. Register B is
+    /// decremented, and if a nonzero value remains, the value of displacement e
+    /// is added to the Program Counter (PC). The next instruction is fetched
+    /// from the location designated by the new contents of the PC. The jump is
+    /// measured from the address of the instruction op code and contains a
+    /// range of –126 to +129 bytes. If the result of decrementing leaves B with
+    /// a zero value, the next instruction executed is taken from the location
+    /// following this instruction.
+    ///
+    /// If B ≠ 0:
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 13 (5, 3, 5)    | 3.25       |
+    ///
+    /// If B = 0:
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 8 (5, 3)    | 2.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// A typical software routine is used to demonstrate the use of the DJNZ
+    /// instruction. This routine moves a line from an input buffer (INBUF) to
+    /// an output buffer (OUTBUF). It moves the bytes until it finds a CR, or
+    /// until it has moved 80 bytes, whichever occurs first.
+    ///
+    /// ```text
+    ///         LD  B, 80      ; Set up counter
+    ///         LD  HL, Inbuf  ; Set up pointers
+    ///         LD  DE, Outbuf
+    /// LOOP:   LD  A, (HL)    ; Get next byte from input buffer
+    ///         LD  (DE), A    ; Store in output buffer
+    ///         CP  0DH        ; Is it a CR?
+    ///         JR  Z, DONE    ; Yes finished
+    ///         INC HL         ; Increment pointers
+    ///         INC DE
+    ///         DJNZ LOOP      ; Loop back if 80 bytes have not been moved
+    /// DONE:
+    /// ```
+    pub fn djnz_e(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.b.dec();
+        let condition = self.b.value() != 0;
+
+        // The extra T state is spent decrementing B during the opcode fetch.
+        self.internal_cycles(mem, 1);
+        1 + self.jr_cc_e(condition, mem)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{set_c_flag, set_p_flag, set_s_flag, set_z_flag},
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_jp_nn() {
+        let bytes = &mut [0x34, 0x12];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+
+        let t_states = z80.jp_nn(ram);
+        assert_eq!(10, t_states);
+
+        assert_eq!(0x1234, z80.program_counter);
+        assert_eq!(0x1234, z80.memptr);
+    }
+
+    #[test]
+    fn test_jp_cc_nn() {
+        let scenarios: [(fn(&mut Z80, &_) -> u8, fn(&mut Z80), bool); 8] = [
+            (Z80::jp_nz_nn, |z80| set_z_flag(&mut z80.f), false),
+            (Z80::jp_z_nn, |z80| set_z_flag(&mut z80.f), true),
+            (Z80::jp_nc_nn, |z80| set_c_flag(&mut z80.f), false),
+            (Z80::jp_c_nn, |z80| set_c_flag(&mut z80.f), true),
+            (Z80::jp_po_nn, |z80| set_p_flag(&mut z80.f), false),
+            (Z80::jp_pe_nn, |z80| set_p_flag(&mut z80.f), true),
+            (Z80::jp_p_nn, |z80| set_s_flag(&mut z80.f), false),
+            (Z80::jp_m_nn, |z80| set_s_flag(&mut z80.f), true),
+        ];
+
+        let bytes = &mut [0x20, 0x15];
+        let ram = &Ram::new(bytes);
+
+        for (jp, set_flag, taken_when_set) in scenarios {
+            let z80 = &mut Z80::new();
+            let t_states = jp(z80, ram);
+            assert_eq!(10, t_states);
+            let expected = if taken_when_set { 0x0002 } else { 0x1520 };
+            assert_eq!(expected, z80.program_counter);
+
+            let z80 = &mut Z80::new();
+            set_flag(z80);
+            let t_states = jp(z80, ram);
+            assert_eq!(10, t_states);
+            let expected = if taken_when_set { 0x1520 } else { 0x0002 };
+            assert_eq!(expected, z80.program_counter);
+            assert_eq!(0x1520, z80.memptr);
+        }
+    }
+
+    #[test]
+    fn test_jr_e() {
+        let bytes = &mut [0x00; 0x0100];
+        bytes[0x80] = 0x03;
+        bytes[0x81] = 0xFA;
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.program_counter = 0x0080;
+
+        let t_states = z80.jr_e(ram);
+        assert_eq!(12, t_states);
+        assert_eq!(0x0084, z80.program_counter);
+        assert_eq!(0x0084, z80.memptr);
+
+        z80.program_counter = 0x0081;
+
+        z80.jr_e(ram);
+        assert_eq!(0x007C, z80.program_counter);
+    }
+
+    #[test]
+    fn test_jr_cc_e() {
+        let bytes = &mut [0xFA, 0x10];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.jr_c_e(ram);
+        assert_eq!(12, t_states);
+        assert_eq!(0xFFFB, z80.program_counter);
+
+        z80.program_counter = 0x0001;
+
+        let t_states = z80.jr_nc_e(ram);
+        assert_eq!(7, t_states);
+        assert_eq!(0x0002, z80.program_counter);
+
+        z80.program_counter = 0x0001;
+
+        let t_states = z80.jr_nz_e(ram);
+        assert_eq!(12, t_states);
+        assert_eq!(0x0012, z80.program_counter);
+    }
+
+    #[test]
+    fn test_jp_mem_index() {
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x4800);
+        z80.ix = 0x4900;
+        z80.iy = 0x4A00;
+
+        assert_eq!(4, z80.jp_mem_hl());
+        assert_eq!(0x4800, z80.program_counter);
+
+        assert_eq!(8, z80.jp_mem_ix());
+        assert_eq!(0x4900, z80.program_counter);
+
+        assert_eq!(8, z80.jp_mem_iy());
+        assert_eq!(0x4A00, z80.program_counter);
+    }
+
+    #[test]
+    fn test_djnz_e() {
+        let bytes = &mut [0xFE];
+        let ram = &mut Ram::new(bytes);
+        let z80 = &mut Z80::new();
+        z80.b.set_value(0x02);
+
+        let t_states = z80.djnz_e(ram);
+        assert_eq!(13, t_states);
+        assert_eq!(0x01, z80.b.value());
+        assert_eq!(0xFFFF, z80.program_counter);
+
+        z80.program_counter = 0x0000;
+
+        let t_states = z80.djnz_e(ram);
+        assert_eq!(8, t_states);
+        assert_eq!(0x00, z80.b.value());
+        assert_eq!(0x0001, z80.program_counter);
+    }
+}
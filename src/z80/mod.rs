@@ -1,2008 +1,3286 @@
+mod assembler;
+mod bit_set_reset_test_group;
+mod call_return_group;
+mod disassembler;
+mod eight_bit_arithmetic_group;
 mod eight_bit_load_group;
 mod exchange_block_transfer;
-mod register_flags;
+mod general_purpose_arithmetic_cpu_control_group;
+mod input_output_group;
+pub mod instruction;
+mod interrupts;
+mod jump_group;
+pub mod register_flags;
+mod rotate_shift_group;
+mod sixteen_bit_arithmetic_group;
 mod sixteen_bit_load_group;
 
-use self::register_flags::*;
+pub use assembler::{assemble, Assembly, AssemblyError};
+pub use disassembler::{disassemble, DisassembledInstruction};
+pub use instruction::{decode, Instruction, Prefix};
+
+use std::{error::Error, fmt, marker::PhantomData};
 
 // Official Z80 documentation: https://www.zilog.com/docs/z80/um0080.pdf
 // Unofficial undocumented functionality documentation: http://www.z80.info/zip/z80-documented.pdf
 // Integration test suites: https://mdfs.net/Software/Z80/Exerciser/Spectrum/
 
-const MAIN_FUNCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 98] = [
-    // 00000000 NOP
-    |_, _| Z80::nop(),
-    // 00000001 LD BC nn
-    |z80, mem| z80.ld_bc_nn(mem),
-    // 00000010 LD (BC), A
-    Z80::ld_mem_bc_a,
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110 LD B, n
-    Z80::ld_b_n,
-    // 00000111
-    // 00001000 LD AF, AF'
-    |z80, _| z80.ex_af_afp(),
-    // 00001001
-    // 00001010 LA A, (BC)
-    |z80, mem| z80.ld_a_mem_bc(mem),
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110 LD C, n
-    Z80::ld_c_n,
-    // 00001111
-    // 00010000
-    // 00010001 LD DE nn
-    |z80, mem| z80.ld_de_nn(mem),
-    // 00010010 LD (DE), A
-    Z80::ld_mem_de_a,
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110 LD D, n
-    Z80::ld_d_n,
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010 LA A, (DE)
-    |z80, mem| z80.ld_a_mem_de(mem),
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110 LD E, n
-    Z80::ld_e_n,
-    // 00011111
-    // 00100000
-    // 00100001 LD HL nn
-    |z80, mem| z80.ld_hl_nn(mem),
-    // 00100010 LD (nn), HL
-    Z80::ld_mem_nn_hl,
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110 LD H, n
-    Z80::ld_h_n,
-    // 00100111
-    |z80, _| z80.daa(),
-    // 00101000
-    // 00101001
-    // 00101010 LD HL, (nn)
-    |z80, mem| z80.ld_hl_mem_nn(mem),
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110 LD L, n
-    Z80::ld_l_n,
-    // 00101111
-    // 00110000
-    // 00110001 LD SP nn
-    |z80, mem| z80.ld_sp_nn(mem),
-    // 00110010 LD (nn), A
-    Z80::ld_mem_nn_a,
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110 LD (HL), n
-    |z80, mem| z80.ld_mem_hl_n(mem),
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010 LD A, (nn)
-    |z80, mem| z80.ld_a_mem_nn(mem),
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110 LD A, n
-    Z80::ld_a_n,
-    // 00111111
-    // 01000000 LD B, B
-    |z80, _| z80.ld_b_b(),
-    // 01000001 LD B, C
-    |z80, _| z80.ld_b_c(),
-    // 01000010 LD B, D
-    |z80, _| z80.ld_b_d(),
-    // 01000011 LD B, E
-    |z80, _| z80.ld_b_e(),
-    // 01000100 LD B, H
-    |z80, _| z80.ld_b_h(),
-    // 01000101 LD B, L
-    |z80, _| z80.ld_b_l(),
-    // 01000110 LD B, (HL)
-    |z80, mem| z80.ld_b_mem_hl(mem),
-    // 01000111 LD B, A
-    |z80, _| z80.ld_b_a(),
-    // 01001000 LD C, B
-    |z80, _| z80.ld_c_b(),
-    // 01001001 LD C, C
-    |z80, _| z80.ld_c_c(),
-    // 01001010 LD C, D
-    |z80, _| z80.ld_c_d(),
-    // 01001011 LD C, E
-    |z80, _| z80.ld_c_e(),
-    // 01001100 LD C, H
-    |z80, _| z80.ld_c_h(),
-    // 01001101 LD C, L
-    |z80, _| z80.ld_c_l(),
-    // 01001110 LD C, (HL)
-    |z80, mem| z80.ld_c_mem_hl(mem),
-    // 01001111 LD C, A
-    |z80, _| z80.ld_c_a(),
-    // 01010000 LD D, B
-    |z80, _| z80.ld_d_b(),
-    // 01010001 LD D, C
-    |z80, _| z80.ld_d_c(),
-    // 01010010 LD D, D
-    |z80, _| z80.ld_d_d(),
-    // 01010011 LD D, E
-    |z80, _| z80.ld_d_e(),
-    // 01010100 LD D, H
-    |z80, _| z80.ld_d_h(),
-    // 01010101 LD D, L
-    |z80, _| z80.ld_d_l(),
-    // 01010110 LD D, (HL)
-    |z80, mem| z80.ld_d_mem_hl(mem),
-    // 01010111 LD D, A
-    |z80, _| z80.ld_d_a(),
-    // 01011000 LD E, B
-    |z80, _| z80.ld_e_b(),
-    // 01011001 LD E, C
-    |z80, _| z80.ld_e_c(),
-    // 01011010 LD E, D
-    |z80, _| z80.ld_e_d(),
-    // 01011011 LD E, E
-    |z80, _| z80.ld_e_e(),
-    // 01011100 LD E, H
-    |z80, _| z80.ld_e_h(),
-    // 01011101 LD E, L
-    |z80, _| z80.ld_e_l(),
-    // 01011110 LD E, (HL)
-    |z80, mem| z80.ld_e_mem_hl(mem),
-    // 01011111 LD E, A
-    |z80, _| z80.ld_e_a(),
-    // 01100000 LD H, B
-    |z80, _| z80.ld_h_b(),
-    // 01100001 LD H, C
-    |z80, _| z80.ld_h_c(),
-    // 01100010 LD H, D
-    |z80, _| z80.ld_h_d(),
-    // 01100011 LD H, E
-    |z80, _| z80.ld_h_e(),
-    // 01100100 LD H, H
-    |z80, _| z80.ld_h_h(),
-    // 01100101 LD H, L
-    |z80, _| z80.ld_h_l(),
-    // 01100110 LD H, (HL)
-    |z80, mem| z80.ld_h_mem_hl(mem),
-    // 01100111 LD H, A
-    |z80, _| z80.ld_h_a(),
-    // 01101000 LD L, B
-    |z80, _| z80.ld_l_b(),
-    // 01101001 LD L, C
-    |z80, _| z80.ld_l_c(),
-    // 01101010 LD L, D
-    |z80, _| z80.ld_l_d(),
-    // 01101011 LD L, E
-    |z80, _| z80.ld_l_e(),
-    // 01101100 LD L, H
-    |z80, _| z80.ld_l_h(),
-    // 01101101 LD L, L
-    |z80, _| z80.ld_l_l(),
-    // 01101110 LD L, (HL)
-    |z80, mem| z80.ld_l_mem_hl(mem),
-    // 01101111 LD L, A
-    |z80, _| z80.ld_l_a(),
-    // 01110000 LD (HL), B
-    |z80, mem| z80.ld_mem_hl_b(mem),
-    // 01110001 LD (HL), C
-    |z80, mem| z80.ld_mem_hl_c(mem),
-    // 01110010 LD (HL), D
-    |z80, mem| z80.ld_mem_hl_d(mem),
-    // 01110011 LD (HL), E
-    |z80, mem| z80.ld_mem_hl_e(mem),
-    // 01110100 LD (HL), H
-    |z80, mem| z80.ld_mem_hl_h(mem),
-    // 01110101 LD (HL), L
-    |z80, mem| z80.ld_mem_hl_l(mem),
-    // 01110110
-    // 01110111 LD (HL), A
-    |z80, mem| z80.ld_mem_hl_a(mem),
-    // 01111000 LD A, B
-    |z80, _| z80.ld_a_b(),
-    // 01111001 LD A, C
-    |z80, _| z80.ld_a_c(),
-    // 01111010 LD A, D
-    |z80, _| z80.ld_a_d(),
-    // 01111011 LD A, E
-    |z80, _| z80.ld_a_e(),
-    // 01111100 LD A, H
-    |z80, _| z80.ld_a_h(),
-    // 01111101 LD A, L
-    |z80, _| z80.ld_a_l(),
-    // 01111110 LD A, (HL)
-    |z80, mem| z80.ld_a_mem_hl(mem),
-    // 01111111 LD A, A
-    |z80, _| z80.ld_a_a(),
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001 POP BC
-    |z80, mem| z80.pop_qqbc(mem),
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101 PUSH BC
-    Z80::push_qqbc,
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001 POP DE
-    |z80, mem| z80.pop_qqde(mem),
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101 PUSH DE
-    Z80::push_qqde,
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001 EXX
-    |z80, _| z80.exx(),
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001 POP HL
-    |z80, mem| z80.pop_qqhl(mem),
-    // 11100010
-    // 11100011 EX (SP), HL
-    Z80::ex_mem_sp_hl,
-    // 11100100
-    // 11100101 PUSH HL
-    Z80::push_qqhl,
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011 EX DE, HL
-    |z80, _| z80.ex_de_hl(),
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001 POP AF
-    |z80, mem| z80.pop_qqaf(mem),
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101 PUSH AF
-    Z80::push_qqaf,
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001 LD SP, HL
-    |z80, _| z80.ld_sp_hl(),
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
-];
+/// An instruction handler for the opcode pages that can reach the I/O bus,
+/// returning the number of T states the instruction took.
+type OpcodeFunction<M, I> = fn(&mut Z80, &mut M, &mut I) -> u8;
+
+/// An instruction handler for the CB prefixed opcode pages, which never
+/// reach the I/O bus.
+type BitOpcodeFunction<M> = fn(&mut Z80, &mut M) -> u8;
+
+/// The opcode tables for a memory bus `M` and I/O bus `I`. They are generic
+/// so that every handler is compiled for the buses it runs on, letting the
+/// bus accesses be inlined rather than made through a vtable.
+///
+/// The executor dispatches from these tables rather than from
+/// [`decode`], which the disassembler, the assembler and debuggers use
+/// instead. The two describe every opcode separately, so a change to one has
+/// to be made to the other, and the tests of the `instruction` module run
+/// every opcode on every page to check that they agree.
+struct Opcodes<M: ?Sized, I: ?Sized>(PhantomData<OpcodeFunction<M, I>>);
+
+/// The CB prefixed opcode tables for a memory bus `M`.
+struct BitOpcodes<M: ?Sized>(PhantomData<BitOpcodeFunction<M>>);
+
+impl<M: Z80Memory + ?Sized, I: Z80Io + ?Sized> Opcodes<M, I> {
+    const MAIN_FUNCTIONS: [OpcodeFunction<M, I>; 256] = [
+        // 00000000 NOP
+        |_, _, _| Z80::nop(),
+        // 00000001 LD BC, nn
+        |z80, mem, _| z80.ld_bc_nn(mem),
+        // 00000010 LD (BC), A
+        |z80, mem, _| z80.ld_mem_bc_a(mem),
+        // 00000011 INC BC
+        |z80, mem, _| z80.inc_bc(mem),
+        // 00000100 INC B
+        |z80, _, _| z80.inc_b(),
+        // 00000101 DEC B
+        |z80, _, _| z80.dec_b(),
+        // 00000110 LD B, n
+        |z80, mem, _| z80.ld_b_n(mem),
+        // 00000111 RLCA
+        |z80, _, _| z80.rlca(),
+        // 00001000 EX AF, AF'
+        |z80, _, _| z80.ex_af_afp(),
+        // 00001001 ADD HL, BC
+        |z80, mem, _| z80.add_hl_bc(mem),
+        // 00001010 LD A, (BC)
+        |z80, mem, _| z80.ld_a_mem_bc(mem),
+        // 00001011 DEC BC
+        |z80, mem, _| z80.dec_bc(mem),
+        // 00001100 INC C
+        |z80, _, _| z80.inc_c(),
+        // 00001101 DEC C
+        |z80, _, _| z80.dec_c(),
+        // 00001110 LD C, n
+        |z80, mem, _| z80.ld_c_n(mem),
+        // 00001111 RRCA
+        |z80, _, _| z80.rrca(),
+        // 00010000 DJNZ e
+        |z80, mem, _| z80.djnz_e(mem),
+        // 00010001 LD DE, nn
+        |z80, mem, _| z80.ld_de_nn(mem),
+        // 00010010 LD (DE), A
+        |z80, mem, _| z80.ld_mem_de_a(mem),
+        // 00010011 INC DE
+        |z80, mem, _| z80.inc_de(mem),
+        // 00010100 INC D
+        |z80, _, _| z80.inc_d(),
+        // 00010101 DEC D
+        |z80, _, _| z80.dec_d(),
+        // 00010110 LD D, n
+        |z80, mem, _| z80.ld_d_n(mem),
+        // 00010111 RLA
+        |z80, _, _| z80.rla(),
+        // 00011000 JR e
+        |z80, mem, _| z80.jr_e(mem),
+        // 00011001 ADD HL, DE
+        |z80, mem, _| z80.add_hl_de(mem),
+        // 00011010 LD A, (DE)
+        |z80, mem, _| z80.ld_a_mem_de(mem),
+        // 00011011 DEC DE
+        |z80, mem, _| z80.dec_de(mem),
+        // 00011100 INC E
+        |z80, _, _| z80.inc_e(),
+        // 00011101 DEC E
+        |z80, _, _| z80.dec_e(),
+        // 00011110 LD E, n
+        |z80, mem, _| z80.ld_e_n(mem),
+        // 00011111 RRA
+        |z80, _, _| z80.rra(),
+        // 00100000 JR NZ, e
+        |z80, mem, _| z80.jr_nz_e(mem),
+        // 00100001 LD HL, nn
+        |z80, mem, _| z80.ld_hl_nn(mem),
+        // 00100010 LD (nn), HL
+        |z80, mem, _| z80.ld_mem_nn_hl(mem),
+        // 00100011 INC HL
+        |z80, mem, _| z80.inc_hl(mem),
+        // 00100100 INC H
+        |z80, _, _| z80.inc_h(),
+        // 00100101 DEC H
+        |z80, _, _| z80.dec_h(),
+        // 00100110 LD H, n
+        |z80, mem, _| z80.ld_h_n(mem),
+        // 00100111 DAA
+        |z80, _, _| z80.daa(),
+        // 00101000 JR Z, e
+        |z80, mem, _| z80.jr_z_e(mem),
+        // 00101001 ADD HL, HL
+        |z80, mem, _| z80.add_hl_hl(mem),
+        // 00101010 LD HL, (nn)
+        |z80, mem, _| z80.ld_hl_mem_nn(mem),
+        // 00101011 DEC HL
+        |z80, mem, _| z80.dec_hl(mem),
+        // 00101100 INC L
+        |z80, _, _| z80.inc_l(),
+        // 00101101 DEC L
+        |z80, _, _| z80.dec_l(),
+        // 00101110 LD L, n
+        |z80, mem, _| z80.ld_l_n(mem),
+        // 00101111 CPL
+        |z80, _, _| z80.cpl(),
+        // 00110000 JR NC, e
+        |z80, mem, _| z80.jr_nc_e(mem),
+        // 00110001 LD SP, nn
+        |z80, mem, _| z80.ld_sp_nn(mem),
+        // 00110010 LD (nn), A
+        |z80, mem, _| z80.ld_mem_nn_a(mem),
+        // 00110011 INC SP
+        |z80, mem, _| z80.inc_sp(mem),
+        // 00110100 INC (HL)
+        |z80, mem, _| z80.inc_mem_hl(mem),
+        // 00110101 DEC (HL)
+        |z80, mem, _| z80.dec_mem_hl(mem),
+        // 00110110 LD (HL), n
+        |z80, mem, _| z80.ld_mem_hl_n(mem),
+        // 00110111 SCF
+        |z80, _, _| z80.scf(),
+        // 00111000 JR C, e
+        |z80, mem, _| z80.jr_c_e(mem),
+        // 00111001 ADD HL, SP
+        |z80, mem, _| z80.add_hl_sp(mem),
+        // 00111010 LD A, (nn)
+        |z80, mem, _| z80.ld_a_mem_nn(mem),
+        // 00111011 DEC SP
+        |z80, mem, _| z80.dec_sp(mem),
+        // 00111100 INC A
+        |z80, _, _| z80.inc_a(),
+        // 00111101 DEC A
+        |z80, _, _| z80.dec_a(),
+        // 00111110 LD A, n
+        |z80, mem, _| z80.ld_a_n(mem),
+        // 00111111 CCF
+        |z80, _, _| z80.ccf(),
+        // 01000000 LD B, B
+        |z80, _, _| z80.ld_b_b(),
+        // 01000001 LD B, C
+        |z80, _, _| z80.ld_b_c(),
+        // 01000010 LD B, D
+        |z80, _, _| z80.ld_b_d(),
+        // 01000011 LD B, E
+        |z80, _, _| z80.ld_b_e(),
+        // 01000100 LD B, H
+        |z80, _, _| z80.ld_b_h(),
+        // 01000101 LD B, L
+        |z80, _, _| z80.ld_b_l(),
+        // 01000110 LD B, (HL)
+        |z80, mem, _| z80.ld_b_mem_hl(mem),
+        // 01000111 LD B, A
+        |z80, _, _| z80.ld_b_a(),
+        // 01001000 LD C, B
+        |z80, _, _| z80.ld_c_b(),
+        // 01001001 LD C, C
+        |z80, _, _| z80.ld_c_c(),
+        // 01001010 LD C, D
+        |z80, _, _| z80.ld_c_d(),
+        // 01001011 LD C, E
+        |z80, _, _| z80.ld_c_e(),
+        // 01001100 LD C, H
+        |z80, _, _| z80.ld_c_h(),
+        // 01001101 LD C, L
+        |z80, _, _| z80.ld_c_l(),
+        // 01001110 LD C, (HL)
+        |z80, mem, _| z80.ld_c_mem_hl(mem),
+        // 01001111 LD C, A
+        |z80, _, _| z80.ld_c_a(),
+        // 01010000 LD D, B
+        |z80, _, _| z80.ld_d_b(),
+        // 01010001 LD D, C
+        |z80, _, _| z80.ld_d_c(),
+        // 01010010 LD D, D
+        |z80, _, _| z80.ld_d_d(),
+        // 01010011 LD D, E
+        |z80, _, _| z80.ld_d_e(),
+        // 01010100 LD D, H
+        |z80, _, _| z80.ld_d_h(),
+        // 01010101 LD D, L
+        |z80, _, _| z80.ld_d_l(),
+        // 01010110 LD D, (HL)
+        |z80, mem, _| z80.ld_d_mem_hl(mem),
+        // 01010111 LD D, A
+        |z80, _, _| z80.ld_d_a(),
+        // 01011000 LD E, B
+        |z80, _, _| z80.ld_e_b(),
+        // 01011001 LD E, C
+        |z80, _, _| z80.ld_e_c(),
+        // 01011010 LD E, D
+        |z80, _, _| z80.ld_e_d(),
+        // 01011011 LD E, E
+        |z80, _, _| z80.ld_e_e(),
+        // 01011100 LD E, H
+        |z80, _, _| z80.ld_e_h(),
+        // 01011101 LD E, L
+        |z80, _, _| z80.ld_e_l(),
+        // 01011110 LD E, (HL)
+        |z80, mem, _| z80.ld_e_mem_hl(mem),
+        // 01011111 LD E, A
+        |z80, _, _| z80.ld_e_a(),
+        // 01100000 LD H, B
+        |z80, _, _| z80.ld_h_b(),
+        // 01100001 LD H, C
+        |z80, _, _| z80.ld_h_c(),
+        // 01100010 LD H, D
+        |z80, _, _| z80.ld_h_d(),
+        // 01100011 LD H, E
+        |z80, _, _| z80.ld_h_e(),
+        // 01100100 LD H, H
+        |z80, _, _| z80.ld_h_h(),
+        // 01100101 LD H, L
+        |z80, _, _| z80.ld_h_l(),
+        // 01100110 LD H, (HL)
+        |z80, mem, _| z80.ld_h_mem_hl(mem),
+        // 01100111 LD H, A
+        |z80, _, _| z80.ld_h_a(),
+        // 01101000 LD L, B
+        |z80, _, _| z80.ld_l_b(),
+        // 01101001 LD L, C
+        |z80, _, _| z80.ld_l_c(),
+        // 01101010 LD L, D
+        |z80, _, _| z80.ld_l_d(),
+        // 01101011 LD L, E
+        |z80, _, _| z80.ld_l_e(),
+        // 01101100 LD L, H
+        |z80, _, _| z80.ld_l_h(),
+        // 01101101 LD L, L
+        |z80, _, _| z80.ld_l_l(),
+        // 01101110 LD L, (HL)
+        |z80, mem, _| z80.ld_l_mem_hl(mem),
+        // 01101111 LD L, A
+        |z80, _, _| z80.ld_l_a(),
+        // 01110000 LD (HL), B
+        |z80, mem, _| z80.ld_mem_hl_b(mem),
+        // 01110001 LD (HL), C
+        |z80, mem, _| z80.ld_mem_hl_c(mem),
+        // 01110010 LD (HL), D
+        |z80, mem, _| z80.ld_mem_hl_d(mem),
+        // 01110011 LD (HL), E
+        |z80, mem, _| z80.ld_mem_hl_e(mem),
+        // 01110100 LD (HL), H
+        |z80, mem, _| z80.ld_mem_hl_h(mem),
+        // 01110101 LD (HL), L
+        |z80, mem, _| z80.ld_mem_hl_l(mem),
+        // 01110110 HALT
+        |z80, _, _| z80.halt(),
+        // 01110111 LD (HL), A
+        |z80, mem, _| z80.ld_mem_hl_a(mem),
+        // 01111000 LD A, B
+        |z80, _, _| z80.ld_a_b(),
+        // 01111001 LD A, C
+        |z80, _, _| z80.ld_a_c(),
+        // 01111010 LD A, D
+        |z80, _, _| z80.ld_a_d(),
+        // 01111011 LD A, E
+        |z80, _, _| z80.ld_a_e(),
+        // 01111100 LD A, H
+        |z80, _, _| z80.ld_a_h(),
+        // 01111101 LD A, L
+        |z80, _, _| z80.ld_a_l(),
+        // 01111110 LD A, (HL)
+        |z80, mem, _| z80.ld_a_mem_hl(mem),
+        // 01111111 LD A, A
+        |z80, _, _| z80.ld_a_a(),
+        // 10000000 ADD A, B
+        |z80, _, _| z80.add_a_b(),
+        // 10000001 ADD A, C
+        |z80, _, _| z80.add_a_c(),
+        // 10000010 ADD A, D
+        |z80, _, _| z80.add_a_d(),
+        // 10000011 ADD A, E
+        |z80, _, _| z80.add_a_e(),
+        // 10000100 ADD A, H
+        |z80, _, _| z80.add_a_h(),
+        // 10000101 ADD A, L
+        |z80, _, _| z80.add_a_l(),
+        // 10000110 ADD A, (HL)
+        |z80, mem, _| z80.add_a_mem_hl(mem),
+        // 10000111 ADD A, A
+        |z80, _, _| z80.add_a_a(),
+        // 10001000 ADC A, B
+        |z80, _, _| z80.adc_a_b(),
+        // 10001001 ADC A, C
+        |z80, _, _| z80.adc_a_c(),
+        // 10001010 ADC A, D
+        |z80, _, _| z80.adc_a_d(),
+        // 10001011 ADC A, E
+        |z80, _, _| z80.adc_a_e(),
+        // 10001100 ADC A, H
+        |z80, _, _| z80.adc_a_h(),
+        // 10001101 ADC A, L
+        |z80, _, _| z80.adc_a_l(),
+        // 10001110 ADC A, (HL)
+        |z80, mem, _| z80.adc_a_mem_hl(mem),
+        // 10001111 ADC A, A
+        |z80, _, _| z80.adc_a_a(),
+        // 10010000 SUB B
+        |z80, _, _| z80.sub_b(),
+        // 10010001 SUB C
+        |z80, _, _| z80.sub_c(),
+        // 10010010 SUB D
+        |z80, _, _| z80.sub_d(),
+        // 10010011 SUB E
+        |z80, _, _| z80.sub_e(),
+        // 10010100 SUB H
+        |z80, _, _| z80.sub_h(),
+        // 10010101 SUB L
+        |z80, _, _| z80.sub_l(),
+        // 10010110 SUB (HL)
+        |z80, mem, _| z80.sub_mem_hl(mem),
+        // 10010111 SUB A
+        |z80, _, _| z80.sub_a(),
+        // 10011000 SBC A, B
+        |z80, _, _| z80.sbc_a_b(),
+        // 10011001 SBC A, C
+        |z80, _, _| z80.sbc_a_c(),
+        // 10011010 SBC A, D
+        |z80, _, _| z80.sbc_a_d(),
+        // 10011011 SBC A, E
+        |z80, _, _| z80.sbc_a_e(),
+        // 10011100 SBC A, H
+        |z80, _, _| z80.sbc_a_h(),
+        // 10011101 SBC A, L
+        |z80, _, _| z80.sbc_a_l(),
+        // 10011110 SBC A, (HL)
+        |z80, mem, _| z80.sbc_a_mem_hl(mem),
+        // 10011111 SBC A, A
+        |z80, _, _| z80.sbc_a_a(),
+        // 10100000 AND B
+        |z80, _, _| z80.and_b(),
+        // 10100001 AND C
+        |z80, _, _| z80.and_c(),
+        // 10100010 AND D
+        |z80, _, _| z80.and_d(),
+        // 10100011 AND E
+        |z80, _, _| z80.and_e(),
+        // 10100100 AND H
+        |z80, _, _| z80.and_h(),
+        // 10100101 AND L
+        |z80, _, _| z80.and_l(),
+        // 10100110 AND (HL)
+        |z80, mem, _| z80.and_mem_hl(mem),
+        // 10100111 AND A
+        |z80, _, _| z80.and_a(),
+        // 10101000 XOR B
+        |z80, _, _| z80.xor_b(),
+        // 10101001 XOR C
+        |z80, _, _| z80.xor_c(),
+        // 10101010 XOR D
+        |z80, _, _| z80.xor_d(),
+        // 10101011 XOR E
+        |z80, _, _| z80.xor_e(),
+        // 10101100 XOR H
+        |z80, _, _| z80.xor_h(),
+        // 10101101 XOR L
+        |z80, _, _| z80.xor_l(),
+        // 10101110 XOR (HL)
+        |z80, mem, _| z80.xor_mem_hl(mem),
+        // 10101111 XOR A
+        |z80, _, _| z80.xor_a(),
+        // 10110000 OR B
+        |z80, _, _| z80.or_b(),
+        // 10110001 OR C
+        |z80, _, _| z80.or_c(),
+        // 10110010 OR D
+        |z80, _, _| z80.or_d(),
+        // 10110011 OR E
+        |z80, _, _| z80.or_e(),
+        // 10110100 OR H
+        |z80, _, _| z80.or_h(),
+        // 10110101 OR L
+        |z80, _, _| z80.or_l(),
+        // 10110110 OR (HL)
+        |z80, mem, _| z80.or_mem_hl(mem),
+        // 10110111 OR A
+        |z80, _, _| z80.or_a(),
+        // 10111000 CP B
+        |z80, _, _| z80.cp_b(),
+        // 10111001 CP C
+        |z80, _, _| z80.cp_c(),
+        // 10111010 CP D
+        |z80, _, _| z80.cp_d(),
+        // 10111011 CP E
+        |z80, _, _| z80.cp_e(),
+        // 10111100 CP H
+        |z80, _, _| z80.cp_h(),
+        // 10111101 CP L
+        |z80, _, _| z80.cp_l(),
+        // 10111110 CP (HL)
+        |z80, mem, _| z80.cp_mem_hl(mem),
+        // 10111111 CP A
+        |z80, _, _| z80.cp_a(),
+        // 11000000 RET NZ
+        |z80, mem, _| z80.ret_nz(mem),
+        // 11000001 POP BC
+        |z80, mem, _| z80.pop_qqbc(mem),
+        // 11000010 JP NZ, nn
+        |z80, mem, _| z80.jp_nz_nn(mem),
+        // 11000011 JP nn
+        |z80, mem, _| z80.jp_nn(mem),
+        // 11000100 CALL NZ, nn
+        |z80, mem, _| z80.call_nz_nn(mem),
+        // 11000101 PUSH BC
+        |z80, mem, _| z80.push_qqbc(mem),
+        // 11000110 ADD A, n
+        |z80, mem, _| z80.add_a_n(mem),
+        // 11000111 RST 00h
+        |z80, mem, _| z80.rst_00h(mem),
+        // 11001000 RET Z
+        |z80, mem, _| z80.ret_z(mem),
+        // 11001001 RET
+        |z80, mem, _| z80.ret(mem),
+        // 11001010 JP Z, nn
+        |z80, mem, _| z80.jp_z_nn(mem),
+        // 11001011 CB prefix
+        |z80, mem, _| z80.process_bit_instruction(mem),
+        // 11001100 CALL Z, nn
+        |z80, mem, _| z80.call_z_nn(mem),
+        // 11001101 CALL nn
+        |z80, mem, _| z80.call_nn(mem),
+        // 11001110 ADC A, n
+        |z80, mem, _| z80.adc_a_n(mem),
+        // 11001111 RST 08h
+        |z80, mem, _| z80.rst_08h(mem),
+        // 11010000 RET NC
+        |z80, mem, _| z80.ret_nc(mem),
+        // 11010001 POP DE
+        |z80, mem, _| z80.pop_qqde(mem),
+        // 11010010 JP NC, nn
+        |z80, mem, _| z80.jp_nc_nn(mem),
+        // 11010011 OUT (n), A
+        |z80, mem, io| z80.out_n_a(mem, io),
+        // 11010100 CALL NC, nn
+        |z80, mem, _| z80.call_nc_nn(mem),
+        // 11010101 PUSH DE
+        |z80, mem, _| z80.push_qqde(mem),
+        // 11010110 SUB n
+        |z80, mem, _| z80.sub_n(mem),
+        // 11010111 RST 10h
+        |z80, mem, _| z80.rst_10h(mem),
+        // 11011000 RET C
+        |z80, mem, _| z80.ret_c(mem),
+        // 11011001 EXX
+        |z80, _, _| z80.exx(),
+        // 11011010 JP C, nn
+        |z80, mem, _| z80.jp_c_nn(mem),
+        // 11011011 IN A, (n)
+        |z80, mem, io| z80.in_a_n(mem, io),
+        // 11011100 CALL C, nn
+        |z80, mem, _| z80.call_c_nn(mem),
+        // 11011101 DD prefix
+        |z80, mem, io| z80.process_ix_instruction(mem, io),
+        // 11011110 SBC A, n
+        |z80, mem, _| z80.sbc_a_n(mem),
+        // 11011111 RST 18h
+        |z80, mem, _| z80.rst_18h(mem),
+        // 11100000 RET PO
+        |z80, mem, _| z80.ret_po(mem),
+        // 11100001 POP HL
+        |z80, mem, _| z80.pop_qqhl(mem),
+        // 11100010 JP PO, nn
+        |z80, mem, _| z80.jp_po_nn(mem),
+        // 11100011 EX (SP), HL
+        |z80, mem, _| z80.ex_mem_sp_hl(mem),
+        // 11100100 CALL PO, nn
+        |z80, mem, _| z80.call_po_nn(mem),
+        // 11100101 PUSH HL
+        |z80, mem, _| z80.push_qqhl(mem),
+        // 11100110 AND n
+        |z80, mem, _| z80.and_n(mem),
+        // 11100111 RST 20h
+        |z80, mem, _| z80.rst_20h(mem),
+        // 11101000 RET PE
+        |z80, mem, _| z80.ret_pe(mem),
+        // 11101001 JP (HL)
+        |z80, _, _| z80.jp_mem_hl(),
+        // 11101010 JP PE, nn
+        |z80, mem, _| z80.jp_pe_nn(mem),
+        // 11101011 EX DE, HL
+        |z80, _, _| z80.ex_de_hl(),
+        // 11101100 CALL PE, nn
+        |z80, mem, _| z80.call_pe_nn(mem),
+        // 11101101 ED prefix
+        |z80, mem, io| z80.process_misc_instruction(mem, io),
+        // 11101110 XOR n
+        |z80, mem, _| z80.xor_n(mem),
+        // 11101111 RST 28h
+        |z80, mem, _| z80.rst_28h(mem),
+        // 11110000 RET P
+        |z80, mem, _| z80.ret_p(mem),
+        // 11110001 POP AF
+        |z80, mem, _| z80.pop_qqaf(mem),
+        // 11110010 JP P, nn
+        |z80, mem, _| z80.jp_p_nn(mem),
+        // 11110011 DI
+        |z80, _, _| z80.di(),
+        // 11110100 CALL P, nn
+        |z80, mem, _| z80.call_p_nn(mem),
+        // 11110101 PUSH AF
+        |z80, mem, _| z80.push_qqaf(mem),
+        // 11110110 OR n
+        |z80, mem, _| z80.or_n(mem),
+        // 11110111 RST 30h
+        |z80, mem, _| z80.rst_30h(mem),
+        // 11111000 RET M
+        |z80, mem, _| z80.ret_m(mem),
+        // 11111001 LD SP, HL
+        |z80, mem, _| z80.ld_sp_hl(mem),
+        // 11111010 JP M, nn
+        |z80, mem, _| z80.jp_m_nn(mem),
+        // 11111011 EI
+        |z80, _, _| z80.ei(),
+        // 11111100 CALL M, nn
+        |z80, mem, _| z80.call_m_nn(mem),
+        // 11111101 FD prefix
+        |z80, mem, io| z80.process_iy_instruction(mem, io),
+        // 11111110 CP n
+        |z80, mem, _| z80.cp_n(mem),
+        // 11111111 RST 38h
+        |z80, mem, _| z80.rst_38h(mem),
+    ];
+}
 
 // CB prefix
-const BIT_INSTRUCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 0] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001
-    // 00100010
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011
-    // 01000100
-    // 01000101
-    // 01000110
-    // 01000111
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011
-    // 01001100
-    // 01001101
-    // 01001110
-    // 01001111
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011
-    // 01010100
-    // 01010101
-    // 01010110
-    // 01010111
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011
-    // 01011100
-    // 01011101
-    // 01011110
-    // 01011111
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011
-    // 01100100
-    // 01100101
-    // 01100110
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011
-    // 01101100
-    // 01101101
-    // 01101110
-    // 01101111
-    // 01110000
-    // 01110001
-    // 01110010
-    // 01110011
-    // 01110100
-    // 01110101
-    // 01110110
-    // 01110111
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011
-    // 01111100
-    // 01111101
-    // 01111110
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001
-    // 11100010
-    // 11100011
-    // 11100100
-    // 11100101
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
-];
+impl<M: Z80Memory + ?Sized> BitOpcodes<M> {
+    const BIT_INSTRUCTIONS: [BitOpcodeFunction<M>; 256] = [
+        // 00000000 RLC B
+        |z80, _| z80.rlc_b(),
+        // 00000001 RLC C
+        |z80, _| z80.rlc_c(),
+        // 00000010 RLC D
+        |z80, _| z80.rlc_d(),
+        // 00000011 RLC E
+        |z80, _| z80.rlc_e(),
+        // 00000100 RLC H
+        |z80, _| z80.rlc_h(),
+        // 00000101 RLC L
+        |z80, _| z80.rlc_l(),
+        // 00000110 RLC (HL)
+        |z80, mem| z80.rlc_mem_hl(mem),
+        // 00000111 RLC A
+        |z80, _| z80.rlc_a(),
+        // 00001000 RRC B
+        |z80, _| z80.rrc_b(),
+        // 00001001 RRC C
+        |z80, _| z80.rrc_c(),
+        // 00001010 RRC D
+        |z80, _| z80.rrc_d(),
+        // 00001011 RRC E
+        |z80, _| z80.rrc_e(),
+        // 00001100 RRC H
+        |z80, _| z80.rrc_h(),
+        // 00001101 RRC L
+        |z80, _| z80.rrc_l(),
+        // 00001110 RRC (HL)
+        |z80, mem| z80.rrc_mem_hl(mem),
+        // 00001111 RRC A
+        |z80, _| z80.rrc_a(),
+        // 00010000 RL B
+        |z80, _| z80.rl_b(),
+        // 00010001 RL C
+        |z80, _| z80.rl_c(),
+        // 00010010 RL D
+        |z80, _| z80.rl_d(),
+        // 00010011 RL E
+        |z80, _| z80.rl_e(),
+        // 00010100 RL H
+        |z80, _| z80.rl_h(),
+        // 00010101 RL L
+        |z80, _| z80.rl_l(),
+        // 00010110 RL (HL)
+        |z80, mem| z80.rl_mem_hl(mem),
+        // 00010111 RL A
+        |z80, _| z80.rl_a(),
+        // 00011000 RR B
+        |z80, _| z80.rr_b(),
+        // 00011001 RR C
+        |z80, _| z80.rr_c(),
+        // 00011010 RR D
+        |z80, _| z80.rr_d(),
+        // 00011011 RR E
+        |z80, _| z80.rr_e(),
+        // 00011100 RR H
+        |z80, _| z80.rr_h(),
+        // 00011101 RR L
+        |z80, _| z80.rr_l(),
+        // 00011110 RR (HL)
+        |z80, mem| z80.rr_mem_hl(mem),
+        // 00011111 RR A
+        |z80, _| z80.rr_a(),
+        // 00100000 SLA B
+        |z80, _| z80.sla_b(),
+        // 00100001 SLA C
+        |z80, _| z80.sla_c(),
+        // 00100010 SLA D
+        |z80, _| z80.sla_d(),
+        // 00100011 SLA E
+        |z80, _| z80.sla_e(),
+        // 00100100 SLA H
+        |z80, _| z80.sla_h(),
+        // 00100101 SLA L
+        |z80, _| z80.sla_l(),
+        // 00100110 SLA (HL)
+        |z80, mem| z80.sla_mem_hl(mem),
+        // 00100111 SLA A
+        |z80, _| z80.sla_a(),
+        // 00101000 SRA B
+        |z80, _| z80.sra_b(),
+        // 00101001 SRA C
+        |z80, _| z80.sra_c(),
+        // 00101010 SRA D
+        |z80, _| z80.sra_d(),
+        // 00101011 SRA E
+        |z80, _| z80.sra_e(),
+        // 00101100 SRA H
+        |z80, _| z80.sra_h(),
+        // 00101101 SRA L
+        |z80, _| z80.sra_l(),
+        // 00101110 SRA (HL)
+        |z80, mem| z80.sra_mem_hl(mem),
+        // 00101111 SRA A
+        |z80, _| z80.sra_a(),
+        // 00110000 SLL B (undocumented)
+        |z80, _| z80.sll_b(),
+        // 00110001 SLL C (undocumented)
+        |z80, _| z80.sll_c(),
+        // 00110010 SLL D (undocumented)
+        |z80, _| z80.sll_d(),
+        // 00110011 SLL E (undocumented)
+        |z80, _| z80.sll_e(),
+        // 00110100 SLL H (undocumented)
+        |z80, _| z80.sll_h(),
+        // 00110101 SLL L (undocumented)
+        |z80, _| z80.sll_l(),
+        // 00110110 SLL (HL) (undocumented)
+        |z80, mem| z80.sll_mem_hl(mem),
+        // 00110111 SLL A (undocumented)
+        |z80, _| z80.sll_a(),
+        // 00111000 SRL B
+        |z80, _| z80.srl_b(),
+        // 00111001 SRL C
+        |z80, _| z80.srl_c(),
+        // 00111010 SRL D
+        |z80, _| z80.srl_d(),
+        // 00111011 SRL E
+        |z80, _| z80.srl_e(),
+        // 00111100 SRL H
+        |z80, _| z80.srl_h(),
+        // 00111101 SRL L
+        |z80, _| z80.srl_l(),
+        // 00111110 SRL (HL)
+        |z80, mem| z80.srl_mem_hl(mem),
+        // 00111111 SRL A
+        |z80, _| z80.srl_a(),
+        // 01000000 BIT 0, B
+        |z80, _| z80.bit_b_b(0),
+        // 01000001 BIT 0, C
+        |z80, _| z80.bit_b_c(0),
+        // 01000010 BIT 0, D
+        |z80, _| z80.bit_b_d(0),
+        // 01000011 BIT 0, E
+        |z80, _| z80.bit_b_e(0),
+        // 01000100 BIT 0, H
+        |z80, _| z80.bit_b_h(0),
+        // 01000101 BIT 0, L
+        |z80, _| z80.bit_b_l(0),
+        // 01000110 BIT 0, (HL)
+        |z80, mem| z80.bit_b_mem_hl(0, mem),
+        // 01000111 BIT 0, A
+        |z80, _| z80.bit_b_a(0),
+        // 01001000 BIT 1, B
+        |z80, _| z80.bit_b_b(1),
+        // 01001001 BIT 1, C
+        |z80, _| z80.bit_b_c(1),
+        // 01001010 BIT 1, D
+        |z80, _| z80.bit_b_d(1),
+        // 01001011 BIT 1, E
+        |z80, _| z80.bit_b_e(1),
+        // 01001100 BIT 1, H
+        |z80, _| z80.bit_b_h(1),
+        // 01001101 BIT 1, L
+        |z80, _| z80.bit_b_l(1),
+        // 01001110 BIT 1, (HL)
+        |z80, mem| z80.bit_b_mem_hl(1, mem),
+        // 01001111 BIT 1, A
+        |z80, _| z80.bit_b_a(1),
+        // 01010000 BIT 2, B
+        |z80, _| z80.bit_b_b(2),
+        // 01010001 BIT 2, C
+        |z80, _| z80.bit_b_c(2),
+        // 01010010 BIT 2, D
+        |z80, _| z80.bit_b_d(2),
+        // 01010011 BIT 2, E
+        |z80, _| z80.bit_b_e(2),
+        // 01010100 BIT 2, H
+        |z80, _| z80.bit_b_h(2),
+        // 01010101 BIT 2, L
+        |z80, _| z80.bit_b_l(2),
+        // 01010110 BIT 2, (HL)
+        |z80, mem| z80.bit_b_mem_hl(2, mem),
+        // 01010111 BIT 2, A
+        |z80, _| z80.bit_b_a(2),
+        // 01011000 BIT 3, B
+        |z80, _| z80.bit_b_b(3),
+        // 01011001 BIT 3, C
+        |z80, _| z80.bit_b_c(3),
+        // 01011010 BIT 3, D
+        |z80, _| z80.bit_b_d(3),
+        // 01011011 BIT 3, E
+        |z80, _| z80.bit_b_e(3),
+        // 01011100 BIT 3, H
+        |z80, _| z80.bit_b_h(3),
+        // 01011101 BIT 3, L
+        |z80, _| z80.bit_b_l(3),
+        // 01011110 BIT 3, (HL)
+        |z80, mem| z80.bit_b_mem_hl(3, mem),
+        // 01011111 BIT 3, A
+        |z80, _| z80.bit_b_a(3),
+        // 01100000 BIT 4, B
+        |z80, _| z80.bit_b_b(4),
+        // 01100001 BIT 4, C
+        |z80, _| z80.bit_b_c(4),
+        // 01100010 BIT 4, D
+        |z80, _| z80.bit_b_d(4),
+        // 01100011 BIT 4, E
+        |z80, _| z80.bit_b_e(4),
+        // 01100100 BIT 4, H
+        |z80, _| z80.bit_b_h(4),
+        // 01100101 BIT 4, L
+        |z80, _| z80.bit_b_l(4),
+        // 01100110 BIT 4, (HL)
+        |z80, mem| z80.bit_b_mem_hl(4, mem),
+        // 01100111 BIT 4, A
+        |z80, _| z80.bit_b_a(4),
+        // 01101000 BIT 5, B
+        |z80, _| z80.bit_b_b(5),
+        // 01101001 BIT 5, C
+        |z80, _| z80.bit_b_c(5),
+        // 01101010 BIT 5, D
+        |z80, _| z80.bit_b_d(5),
+        // 01101011 BIT 5, E
+        |z80, _| z80.bit_b_e(5),
+        // 01101100 BIT 5, H
+        |z80, _| z80.bit_b_h(5),
+        // 01101101 BIT 5, L
+        |z80, _| z80.bit_b_l(5),
+        // 01101110 BIT 5, (HL)
+        |z80, mem| z80.bit_b_mem_hl(5, mem),
+        // 01101111 BIT 5, A
+        |z80, _| z80.bit_b_a(5),
+        // 01110000 BIT 6, B
+        |z80, _| z80.bit_b_b(6),
+        // 01110001 BIT 6, C
+        |z80, _| z80.bit_b_c(6),
+        // 01110010 BIT 6, D
+        |z80, _| z80.bit_b_d(6),
+        // 01110011 BIT 6, E
+        |z80, _| z80.bit_b_e(6),
+        // 01110100 BIT 6, H
+        |z80, _| z80.bit_b_h(6),
+        // 01110101 BIT 6, L
+        |z80, _| z80.bit_b_l(6),
+        // 01110110 BIT 6, (HL)
+        |z80, mem| z80.bit_b_mem_hl(6, mem),
+        // 01110111 BIT 6, A
+        |z80, _| z80.bit_b_a(6),
+        // 01111000 BIT 7, B
+        |z80, _| z80.bit_b_b(7),
+        // 01111001 BIT 7, C
+        |z80, _| z80.bit_b_c(7),
+        // 01111010 BIT 7, D
+        |z80, _| z80.bit_b_d(7),
+        // 01111011 BIT 7, E
+        |z80, _| z80.bit_b_e(7),
+        // 01111100 BIT 7, H
+        |z80, _| z80.bit_b_h(7),
+        // 01111101 BIT 7, L
+        |z80, _| z80.bit_b_l(7),
+        // 01111110 BIT 7, (HL)
+        |z80, mem| z80.bit_b_mem_hl(7, mem),
+        // 01111111 BIT 7, A
+        |z80, _| z80.bit_b_a(7),
+        // 10000000 RES 0, B
+        |z80, _| z80.res_b_b(0),
+        // 10000001 RES 0, C
+        |z80, _| z80.res_b_c(0),
+        // 10000010 RES 0, D
+        |z80, _| z80.res_b_d(0),
+        // 10000011 RES 0, E
+        |z80, _| z80.res_b_e(0),
+        // 10000100 RES 0, H
+        |z80, _| z80.res_b_h(0),
+        // 10000101 RES 0, L
+        |z80, _| z80.res_b_l(0),
+        // 10000110 RES 0, (HL)
+        |z80, mem| z80.res_b_mem_hl(0, mem),
+        // 10000111 RES 0, A
+        |z80, _| z80.res_b_a(0),
+        // 10001000 RES 1, B
+        |z80, _| z80.res_b_b(1),
+        // 10001001 RES 1, C
+        |z80, _| z80.res_b_c(1),
+        // 10001010 RES 1, D
+        |z80, _| z80.res_b_d(1),
+        // 10001011 RES 1, E
+        |z80, _| z80.res_b_e(1),
+        // 10001100 RES 1, H
+        |z80, _| z80.res_b_h(1),
+        // 10001101 RES 1, L
+        |z80, _| z80.res_b_l(1),
+        // 10001110 RES 1, (HL)
+        |z80, mem| z80.res_b_mem_hl(1, mem),
+        // 10001111 RES 1, A
+        |z80, _| z80.res_b_a(1),
+        // 10010000 RES 2, B
+        |z80, _| z80.res_b_b(2),
+        // 10010001 RES 2, C
+        |z80, _| z80.res_b_c(2),
+        // 10010010 RES 2, D
+        |z80, _| z80.res_b_d(2),
+        // 10010011 RES 2, E
+        |z80, _| z80.res_b_e(2),
+        // 10010100 RES 2, H
+        |z80, _| z80.res_b_h(2),
+        // 10010101 RES 2, L
+        |z80, _| z80.res_b_l(2),
+        // 10010110 RES 2, (HL)
+        |z80, mem| z80.res_b_mem_hl(2, mem),
+        // 10010111 RES 2, A
+        |z80, _| z80.res_b_a(2),
+        // 10011000 RES 3, B
+        |z80, _| z80.res_b_b(3),
+        // 10011001 RES 3, C
+        |z80, _| z80.res_b_c(3),
+        // 10011010 RES 3, D
+        |z80, _| z80.res_b_d(3),
+        // 10011011 RES 3, E
+        |z80, _| z80.res_b_e(3),
+        // 10011100 RES 3, H
+        |z80, _| z80.res_b_h(3),
+        // 10011101 RES 3, L
+        |z80, _| z80.res_b_l(3),
+        // 10011110 RES 3, (HL)
+        |z80, mem| z80.res_b_mem_hl(3, mem),
+        // 10011111 RES 3, A
+        |z80, _| z80.res_b_a(3),
+        // 10100000 RES 4, B
+        |z80, _| z80.res_b_b(4),
+        // 10100001 RES 4, C
+        |z80, _| z80.res_b_c(4),
+        // 10100010 RES 4, D
+        |z80, _| z80.res_b_d(4),
+        // 10100011 RES 4, E
+        |z80, _| z80.res_b_e(4),
+        // 10100100 RES 4, H
+        |z80, _| z80.res_b_h(4),
+        // 10100101 RES 4, L
+        |z80, _| z80.res_b_l(4),
+        // 10100110 RES 4, (HL)
+        |z80, mem| z80.res_b_mem_hl(4, mem),
+        // 10100111 RES 4, A
+        |z80, _| z80.res_b_a(4),
+        // 10101000 RES 5, B
+        |z80, _| z80.res_b_b(5),
+        // 10101001 RES 5, C
+        |z80, _| z80.res_b_c(5),
+        // 10101010 RES 5, D
+        |z80, _| z80.res_b_d(5),
+        // 10101011 RES 5, E
+        |z80, _| z80.res_b_e(5),
+        // 10101100 RES 5, H
+        |z80, _| z80.res_b_h(5),
+        // 10101101 RES 5, L
+        |z80, _| z80.res_b_l(5),
+        // 10101110 RES 5, (HL)
+        |z80, mem| z80.res_b_mem_hl(5, mem),
+        // 10101111 RES 5, A
+        |z80, _| z80.res_b_a(5),
+        // 10110000 RES 6, B
+        |z80, _| z80.res_b_b(6),
+        // 10110001 RES 6, C
+        |z80, _| z80.res_b_c(6),
+        // 10110010 RES 6, D
+        |z80, _| z80.res_b_d(6),
+        // 10110011 RES 6, E
+        |z80, _| z80.res_b_e(6),
+        // 10110100 RES 6, H
+        |z80, _| z80.res_b_h(6),
+        // 10110101 RES 6, L
+        |z80, _| z80.res_b_l(6),
+        // 10110110 RES 6, (HL)
+        |z80, mem| z80.res_b_mem_hl(6, mem),
+        // 10110111 RES 6, A
+        |z80, _| z80.res_b_a(6),
+        // 10111000 RES 7, B
+        |z80, _| z80.res_b_b(7),
+        // 10111001 RES 7, C
+        |z80, _| z80.res_b_c(7),
+        // 10111010 RES 7, D
+        |z80, _| z80.res_b_d(7),
+        // 10111011 RES 7, E
+        |z80, _| z80.res_b_e(7),
+        // 10111100 RES 7, H
+        |z80, _| z80.res_b_h(7),
+        // 10111101 RES 7, L
+        |z80, _| z80.res_b_l(7),
+        // 10111110 RES 7, (HL)
+        |z80, mem| z80.res_b_mem_hl(7, mem),
+        // 10111111 RES 7, A
+        |z80, _| z80.res_b_a(7),
+        // 11000000 SET 0, B
+        |z80, _| z80.set_b_b(0),
+        // 11000001 SET 0, C
+        |z80, _| z80.set_b_c(0),
+        // 11000010 SET 0, D
+        |z80, _| z80.set_b_d(0),
+        // 11000011 SET 0, E
+        |z80, _| z80.set_b_e(0),
+        // 11000100 SET 0, H
+        |z80, _| z80.set_b_h(0),
+        // 11000101 SET 0, L
+        |z80, _| z80.set_b_l(0),
+        // 11000110 SET 0, (HL)
+        |z80, mem| z80.set_b_mem_hl(0, mem),
+        // 11000111 SET 0, A
+        |z80, _| z80.set_b_a(0),
+        // 11001000 SET 1, B
+        |z80, _| z80.set_b_b(1),
+        // 11001001 SET 1, C
+        |z80, _| z80.set_b_c(1),
+        // 11001010 SET 1, D
+        |z80, _| z80.set_b_d(1),
+        // 11001011 SET 1, E
+        |z80, _| z80.set_b_e(1),
+        // 11001100 SET 1, H
+        |z80, _| z80.set_b_h(1),
+        // 11001101 SET 1, L
+        |z80, _| z80.set_b_l(1),
+        // 11001110 SET 1, (HL)
+        |z80, mem| z80.set_b_mem_hl(1, mem),
+        // 11001111 SET 1, A
+        |z80, _| z80.set_b_a(1),
+        // 11010000 SET 2, B
+        |z80, _| z80.set_b_b(2),
+        // 11010001 SET 2, C
+        |z80, _| z80.set_b_c(2),
+        // 11010010 SET 2, D
+        |z80, _| z80.set_b_d(2),
+        // 11010011 SET 2, E
+        |z80, _| z80.set_b_e(2),
+        // 11010100 SET 2, H
+        |z80, _| z80.set_b_h(2),
+        // 11010101 SET 2, L
+        |z80, _| z80.set_b_l(2),
+        // 11010110 SET 2, (HL)
+        |z80, mem| z80.set_b_mem_hl(2, mem),
+        // 11010111 SET 2, A
+        |z80, _| z80.set_b_a(2),
+        // 11011000 SET 3, B
+        |z80, _| z80.set_b_b(3),
+        // 11011001 SET 3, C
+        |z80, _| z80.set_b_c(3),
+        // 11011010 SET 3, D
+        |z80, _| z80.set_b_d(3),
+        // 11011011 SET 3, E
+        |z80, _| z80.set_b_e(3),
+        // 11011100 SET 3, H
+        |z80, _| z80.set_b_h(3),
+        // 11011101 SET 3, L
+        |z80, _| z80.set_b_l(3),
+        // 11011110 SET 3, (HL)
+        |z80, mem| z80.set_b_mem_hl(3, mem),
+        // 11011111 SET 3, A
+        |z80, _| z80.set_b_a(3),
+        // 11100000 SET 4, B
+        |z80, _| z80.set_b_b(4),
+        // 11100001 SET 4, C
+        |z80, _| z80.set_b_c(4),
+        // 11100010 SET 4, D
+        |z80, _| z80.set_b_d(4),
+        // 11100011 SET 4, E
+        |z80, _| z80.set_b_e(4),
+        // 11100100 SET 4, H
+        |z80, _| z80.set_b_h(4),
+        // 11100101 SET 4, L
+        |z80, _| z80.set_b_l(4),
+        // 11100110 SET 4, (HL)
+        |z80, mem| z80.set_b_mem_hl(4, mem),
+        // 11100111 SET 4, A
+        |z80, _| z80.set_b_a(4),
+        // 11101000 SET 5, B
+        |z80, _| z80.set_b_b(5),
+        // 11101001 SET 5, C
+        |z80, _| z80.set_b_c(5),
+        // 11101010 SET 5, D
+        |z80, _| z80.set_b_d(5),
+        // 11101011 SET 5, E
+        |z80, _| z80.set_b_e(5),
+        // 11101100 SET 5, H
+        |z80, _| z80.set_b_h(5),
+        // 11101101 SET 5, L
+        |z80, _| z80.set_b_l(5),
+        // 11101110 SET 5, (HL)
+        |z80, mem| z80.set_b_mem_hl(5, mem),
+        // 11101111 SET 5, A
+        |z80, _| z80.set_b_a(5),
+        // 11110000 SET 6, B
+        |z80, _| z80.set_b_b(6),
+        // 11110001 SET 6, C
+        |z80, _| z80.set_b_c(6),
+        // 11110010 SET 6, D
+        |z80, _| z80.set_b_d(6),
+        // 11110011 SET 6, E
+        |z80, _| z80.set_b_e(6),
+        // 11110100 SET 6, H
+        |z80, _| z80.set_b_h(6),
+        // 11110101 SET 6, L
+        |z80, _| z80.set_b_l(6),
+        // 11110110 SET 6, (HL)
+        |z80, mem| z80.set_b_mem_hl(6, mem),
+        // 11110111 SET 6, A
+        |z80, _| z80.set_b_a(6),
+        // 11111000 SET 7, B
+        |z80, _| z80.set_b_b(7),
+        // 11111001 SET 7, C
+        |z80, _| z80.set_b_c(7),
+        // 11111010 SET 7, D
+        |z80, _| z80.set_b_d(7),
+        // 11111011 SET 7, E
+        |z80, _| z80.set_b_e(7),
+        // 11111100 SET 7, H
+        |z80, _| z80.set_b_h(7),
+        // 11111101 SET 7, L
+        |z80, _| z80.set_b_l(7),
+        // 11111110 SET 7, (HL)
+        |z80, mem| z80.set_b_mem_hl(7, mem),
+        // 11111111 SET 7, A
+        |z80, _| z80.set_b_a(7),
+    ];
+}
 
 // DD prefix
-const IX_FUNCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 22] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001 LD IX, nn
-    |z80, mem| z80.ld_ix_nn(mem),
-    // 00100010 LD (nn), IX
-    Z80::ld_mem_nn_ix,
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010 LD IX, (nn)
-    |z80, mem| z80.ld_ix_mem_nn(mem),
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110 LD (IX+d), n
-    |z80, mem| z80.ld_mem_ixd_n(mem),
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011
-    // 01000100
-    // 01000101
-    // 01000110 LD B, (IX+d)
-    |z80, mem| z80.ld_b_mem_ixd(mem),
-    // 01000111
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011
-    // 01001100
-    // 01001101
-    // 01001110 LD C, (IX+d)
-    |z80, mem| z80.ld_c_ixd(mem),
-    // 01001111
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011
-    // 01010100
-    // 01010101
-    // 01010110 LD D, (IX+d)
-    |z80, mem| z80.ld_d_mem_ixd(mem),
-    // 01010111
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011
-    // 01011100
-    // 01011101
-    // 01011110 LD E, (IX+d)
-    |z80, mem| z80.ld_e_mem_ixd(mem),
-    // 01011111
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011
-    // 01100100
-    // 01100101
-    // 01100110 LD H, (IX+d)
-    |z80, mem| z80.ld_h_mem_ixd(mem),
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011
-    // 01101100
-    // 01101101
-    // 01101110 LD L, (IX+d)
-    |z80, mem| z80.ld_l_mem_ixd(mem),
-    // 01101111
-    // 01110000 LD (IX+d), B
-    |z80, mem| z80.ld_mem_ixd_b(mem),
-    // 01110001 LD (IX+d), C
-    |z80, mem| z80.ld_mem_ixd_c(mem),
-    // 01110010 LD (IX+d), D
-    |z80, mem| z80.ld_mem_ixd_d(mem),
-    // 01110011 LD (IX+d), E
-    |z80, mem| z80.ld_mem_ixd_e(mem),
-    // 01110100 LD (IX+d), H
-    |z80, mem| z80.ld_mem_ixd_h(mem),
-    // 01110101 LD (IX+d), L
-    |z80, mem| z80.ld_mem_ixd_l(mem),
-    // 01110110
-    // 01110111 LD (IX+d), A
-    |z80, mem| z80.ld_mem_ixd_a(mem),
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011
-    // 01111100
-    // 01111101
-    // 01111110 LD A, (IX+d)
-    |z80, mem| z80.ld_a_mem_ixd(mem),
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001 POP IX
-    |z80, mem| z80.pop_ix(mem),
-    // 11100010
-    // 11100011 EX (SP), IX
-    Z80::ex_mem_sp_ix,
-    // 11100100
-    // 11100101 PUSH IX
-    Z80::push_ix,
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001 LD SP, IX
-    |z80, _| z80.ld_sp_ix(),
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
-];
-
-// DDCB prefix
-const IX_BIT_INSTRUCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 0] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001
-    // 00100010
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011
-    // 01000100
-    // 01000101
-    // 01000110
-    // 01000111
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011
-    // 01001100
-    // 01001101
-    // 01001110
-    // 01001111
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011
-    // 01010100
-    // 01010101
-    // 01010110
-    // 01010111
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011
-    // 01011100
-    // 01011101
-    // 01011110
-    // 01011111
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011
-    // 01100100
-    // 01100101
-    // 01100110
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011
-    // 01101100
-    // 01101101
-    // 01101110
-    // 01101111
-    // 01110000
-    // 01110001
-    // 01110010
-    // 01110011
-    // 01110100
-    // 01110101
-    // 01110110
-    // 01110111
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011
-    // 01111100
-    // 01111101
-    // 01111110
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001
-    // 11100010
-    // 11100011
-    // 11100100
-    // 11100101
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
+impl<M: Z80Memory + ?Sized, I: Z80Io + ?Sized> Opcodes<M, I> {
+    const IX_FUNCTIONS: [OpcodeFunction<M, I>; 256] = [
+        // 00000000 NOP
+        |_, _, _| 4 + Z80::nop(),
+        // 00000001 LD BC, nn
+        |z80, mem, _| 4 + z80.ld_bc_nn(mem),
+        // 00000010 LD (BC), A
+        |z80, mem, _| 4 + z80.ld_mem_bc_a(mem),
+        // 00000011 INC BC
+        |z80, mem, _| 4 + z80.inc_bc(mem),
+        // 00000100 INC B
+        |z80, _, _| 4 + z80.inc_b(),
+        // 00000101 DEC B
+        |z80, _, _| 4 + z80.dec_b(),
+        // 00000110 LD B, n
+        |z80, mem, _| 4 + z80.ld_b_n(mem),
+        // 00000111 RLCA
+        |z80, _, _| 4 + z80.rlca(),
+        // 00001000 EX AF, AF'
+        |z80, _, _| 4 + z80.ex_af_afp(),
+        // 00001001 ADD IX, BC
+        |z80, mem, _| z80.add_ix_bc(mem),
+        // 00001010 LD A, (BC)
+        |z80, mem, _| 4 + z80.ld_a_mem_bc(mem),
+        // 00001011 DEC BC
+        |z80, mem, _| 4 + z80.dec_bc(mem),
+        // 00001100 INC C
+        |z80, _, _| 4 + z80.inc_c(),
+        // 00001101 DEC C
+        |z80, _, _| 4 + z80.dec_c(),
+        // 00001110 LD C, n
+        |z80, mem, _| 4 + z80.ld_c_n(mem),
+        // 00001111 RRCA
+        |z80, _, _| 4 + z80.rrca(),
+        // 00010000 DJNZ e
+        |z80, mem, _| 4 + z80.djnz_e(mem),
+        // 00010001 LD DE, nn
+        |z80, mem, _| 4 + z80.ld_de_nn(mem),
+        // 00010010 LD (DE), A
+        |z80, mem, _| 4 + z80.ld_mem_de_a(mem),
+        // 00010011 INC DE
+        |z80, mem, _| 4 + z80.inc_de(mem),
+        // 00010100 INC D
+        |z80, _, _| 4 + z80.inc_d(),
+        // 00010101 DEC D
+        |z80, _, _| 4 + z80.dec_d(),
+        // 00010110 LD D, n
+        |z80, mem, _| 4 + z80.ld_d_n(mem),
+        // 00010111 RLA
+        |z80, _, _| 4 + z80.rla(),
+        // 00011000 JR e
+        |z80, mem, _| 4 + z80.jr_e(mem),
+        // 00011001 ADD IX, DE
+        |z80, mem, _| z80.add_ix_de(mem),
+        // 00011010 LD A, (DE)
+        |z80, mem, _| 4 + z80.ld_a_mem_de(mem),
+        // 00011011 DEC DE
+        |z80, mem, _| 4 + z80.dec_de(mem),
+        // 00011100 INC E
+        |z80, _, _| 4 + z80.inc_e(),
+        // 00011101 DEC E
+        |z80, _, _| 4 + z80.dec_e(),
+        // 00011110 LD E, n
+        |z80, mem, _| 4 + z80.ld_e_n(mem),
+        // 00011111 RRA
+        |z80, _, _| 4 + z80.rra(),
+        // 00100000 JR NZ, e
+        |z80, mem, _| 4 + z80.jr_nz_e(mem),
+        // 00100001 LD IX, nn
+        |z80, mem, _| z80.ld_ix_nn(mem),
+        // 00100010 LD (nn), IX
+        |z80, mem, _| z80.ld_mem_nn_ix(mem),
+        // 00100011 INC IX
+        |z80, mem, _| z80.inc_ix(mem),
+        // 00100100 INC IXH
+        |z80, _, _| z80.inc_ixh(),
+        // 00100101 DEC IXH
+        |z80, _, _| z80.dec_ixh(),
+        // 00100110 LD IXH, n
+        |z80, mem, _| z80.ld_ixh_n(mem),
+        // 00100111 DAA
+        |z80, _, _| 4 + z80.daa(),
+        // 00101000 JR Z, e
+        |z80, mem, _| 4 + z80.jr_z_e(mem),
+        // 00101001 ADD IX, IX
+        |z80, mem, _| z80.add_ix_ix(mem),
+        // 00101010 LD IX, (nn)
+        |z80, mem, _| z80.ld_ix_mem_nn(mem),
+        // 00101011 DEC IX
+        |z80, mem, _| z80.dec_ix(mem),
+        // 00101100 INC IXL
+        |z80, _, _| z80.inc_ixl(),
+        // 00101101 DEC IXL
+        |z80, _, _| z80.dec_ixl(),
+        // 00101110 LD IXL, n
+        |z80, mem, _| z80.ld_ixl_n(mem),
+        // 00101111 CPL
+        |z80, _, _| 4 + z80.cpl(),
+        // 00110000 JR NC, e
+        |z80, mem, _| 4 + z80.jr_nc_e(mem),
+        // 00110001 LD SP, nn
+        |z80, mem, _| 4 + z80.ld_sp_nn(mem),
+        // 00110010 LD (nn), A
+        |z80, mem, _| 4 + z80.ld_mem_nn_a(mem),
+        // 00110011 INC SP
+        |z80, mem, _| 4 + z80.inc_sp(mem),
+        // 00110100 INC (IX+d)
+        |z80, mem, _| z80.inc_mem_ixd(mem),
+        // 00110101 DEC (IX+d)
+        |z80, mem, _| z80.dec_mem_ixd(mem),
+        // 00110110 LD (IX+d), n
+        |z80, mem, _| z80.ld_mem_ixd_n(mem),
+        // 00110111 SCF
+        |z80, _, _| 4 + z80.scf(),
+        // 00111000 JR C, e
+        |z80, mem, _| 4 + z80.jr_c_e(mem),
+        // 00111001 ADD IX, SP
+        |z80, mem, _| z80.add_ix_sp(mem),
+        // 00111010 LD A, (nn)
+        |z80, mem, _| 4 + z80.ld_a_mem_nn(mem),
+        // 00111011 DEC SP
+        |z80, mem, _| 4 + z80.dec_sp(mem),
+        // 00111100 INC A
+        |z80, _, _| 4 + z80.inc_a(),
+        // 00111101 DEC A
+        |z80, _, _| 4 + z80.dec_a(),
+        // 00111110 LD A, n
+        |z80, mem, _| 4 + z80.ld_a_n(mem),
+        // 00111111 CCF
+        |z80, _, _| 4 + z80.ccf(),
+        // 01000000 LD B, B
+        |z80, _, _| 4 + z80.ld_b_b(),
+        // 01000001 LD B, C
+        |z80, _, _| 4 + z80.ld_b_c(),
+        // 01000010 LD B, D
+        |z80, _, _| 4 + z80.ld_b_d(),
+        // 01000011 LD B, E
+        |z80, _, _| 4 + z80.ld_b_e(),
+        // 01000100 LD B, IXH
+        |z80, _, _| z80.ld_b_ixh(),
+        // 01000101 LD B, IXL
+        |z80, _, _| z80.ld_b_ixl(),
+        // 01000110 LD B, (IX+d)
+        |z80, mem, _| z80.ld_b_mem_ixd(mem),
+        // 01000111 LD B, A
+        |z80, _, _| 4 + z80.ld_b_a(),
+        // 01001000 LD C, B
+        |z80, _, _| 4 + z80.ld_c_b(),
+        // 01001001 LD C, C
+        |z80, _, _| 4 + z80.ld_c_c(),
+        // 01001010 LD C, D
+        |z80, _, _| 4 + z80.ld_c_d(),
+        // 01001011 LD C, E
+        |z80, _, _| 4 + z80.ld_c_e(),
+        // 01001100 LD C, IXH
+        |z80, _, _| z80.ld_c_ixh(),
+        // 01001101 LD C, IXL
+        |z80, _, _| z80.ld_c_ixl(),
+        // 01001110 LD C, (IX+d)
+        |z80, mem, _| z80.ld_c_mem_ixd(mem),
+        // 01001111 LD C, A
+        |z80, _, _| 4 + z80.ld_c_a(),
+        // 01010000 LD D, B
+        |z80, _, _| 4 + z80.ld_d_b(),
+        // 01010001 LD D, C
+        |z80, _, _| 4 + z80.ld_d_c(),
+        // 01010010 LD D, D
+        |z80, _, _| 4 + z80.ld_d_d(),
+        // 01010011 LD D, E
+        |z80, _, _| 4 + z80.ld_d_e(),
+        // 01010100 LD D, IXH
+        |z80, _, _| z80.ld_d_ixh(),
+        // 01010101 LD D, IXL
+        |z80, _, _| z80.ld_d_ixl(),
+        // 01010110 LD D, (IX+d)
+        |z80, mem, _| z80.ld_d_mem_ixd(mem),
+        // 01010111 LD D, A
+        |z80, _, _| 4 + z80.ld_d_a(),
+        // 01011000 LD E, B
+        |z80, _, _| 4 + z80.ld_e_b(),
+        // 01011001 LD E, C
+        |z80, _, _| 4 + z80.ld_e_c(),
+        // 01011010 LD E, D
+        |z80, _, _| 4 + z80.ld_e_d(),
+        // 01011011 LD E, E
+        |z80, _, _| 4 + z80.ld_e_e(),
+        // 01011100 LD E, IXH
+        |z80, _, _| z80.ld_e_ixh(),
+        // 01011101 LD E, IXL
+        |z80, _, _| z80.ld_e_ixl(),
+        // 01011110 LD E, (IX+d)
+        |z80, mem, _| z80.ld_e_mem_ixd(mem),
+        // 01011111 LD E, A
+        |z80, _, _| 4 + z80.ld_e_a(),
+        // 01100000 LD IXH, B
+        |z80, _, _| z80.ld_ixh_b(),
+        // 01100001 LD IXH, C
+        |z80, _, _| z80.ld_ixh_c(),
+        // 01100010 LD IXH, D
+        |z80, _, _| z80.ld_ixh_d(),
+        // 01100011 LD IXH, E
+        |z80, _, _| z80.ld_ixh_e(),
+        // 01100100 LD IXH, IXH
+        |z80, _, _| z80.ld_ixh_ixh(),
+        // 01100101 LD IXH, IXL
+        |z80, _, _| z80.ld_ixh_ixl(),
+        // 01100110 LD H, (IX+d)
+        |z80, mem, _| z80.ld_h_mem_ixd(mem),
+        // 01100111 LD IXH, A
+        |z80, _, _| z80.ld_ixh_a(),
+        // 01101000 LD IXL, B
+        |z80, _, _| z80.ld_ixl_b(),
+        // 01101001 LD IXL, C
+        |z80, _, _| z80.ld_ixl_c(),
+        // 01101010 LD IXL, D
+        |z80, _, _| z80.ld_ixl_d(),
+        // 01101011 LD IXL, E
+        |z80, _, _| z80.ld_ixl_e(),
+        // 01101100 LD IXL, IXH
+        |z80, _, _| z80.ld_ixl_ixh(),
+        // 01101101 LD IXL, IXL
+        |z80, _, _| z80.ld_ixl_ixl(),
+        // 01101110 LD L, (IX+d)
+        |z80, mem, _| z80.ld_l_mem_ixd(mem),
+        // 01101111 LD IXL, A
+        |z80, _, _| z80.ld_ixl_a(),
+        // 01110000 LD (IX+d), B
+        |z80, mem, _| z80.ld_mem_ixd_b(mem),
+        // 01110001 LD (IX+d), C
+        |z80, mem, _| z80.ld_mem_ixd_c(mem),
+        // 01110010 LD (IX+d), D
+        |z80, mem, _| z80.ld_mem_ixd_d(mem),
+        // 01110011 LD (IX+d), E
+        |z80, mem, _| z80.ld_mem_ixd_e(mem),
+        // 01110100 LD (IX+d), H
+        |z80, mem, _| z80.ld_mem_ixd_h(mem),
+        // 01110101 LD (IX+d), L
+        |z80, mem, _| z80.ld_mem_ixd_l(mem),
+        // 01110110 HALT
+        |z80, _, _| 4 + z80.halt(),
+        // 01110111 LD (IX+d), A
+        |z80, mem, _| z80.ld_mem_ixd_a(mem),
+        // 01111000 LD A, B
+        |z80, _, _| 4 + z80.ld_a_b(),
+        // 01111001 LD A, C
+        |z80, _, _| 4 + z80.ld_a_c(),
+        // 01111010 LD A, D
+        |z80, _, _| 4 + z80.ld_a_d(),
+        // 01111011 LD A, E
+        |z80, _, _| 4 + z80.ld_a_e(),
+        // 01111100 LD A, IXH
+        |z80, _, _| z80.ld_a_ixh(),
+        // 01111101 LD A, IXL
+        |z80, _, _| z80.ld_a_ixl(),
+        // 01111110 LD A, (IX+d)
+        |z80, mem, _| z80.ld_a_mem_ixd(mem),
+        // 01111111 LD A, A
+        |z80, _, _| 4 + z80.ld_a_a(),
+        // 10000000 ADD A, B
+        |z80, _, _| 4 + z80.add_a_b(),
+        // 10000001 ADD A, C
+        |z80, _, _| 4 + z80.add_a_c(),
+        // 10000010 ADD A, D
+        |z80, _, _| 4 + z80.add_a_d(),
+        // 10000011 ADD A, E
+        |z80, _, _| 4 + z80.add_a_e(),
+        // 10000100 ADD A, IXH
+        |z80, _, _| z80.add_a_ixh(),
+        // 10000101 ADD A, IXL
+        |z80, _, _| z80.add_a_ixl(),
+        // 10000110 ADD A, (IX+d)
+        |z80, mem, _| z80.add_a_mem_ixd(mem),
+        // 10000111 ADD A, A
+        |z80, _, _| 4 + z80.add_a_a(),
+        // 10001000 ADC A, B
+        |z80, _, _| 4 + z80.adc_a_b(),
+        // 10001001 ADC A, C
+        |z80, _, _| 4 + z80.adc_a_c(),
+        // 10001010 ADC A, D
+        |z80, _, _| 4 + z80.adc_a_d(),
+        // 10001011 ADC A, E
+        |z80, _, _| 4 + z80.adc_a_e(),
+        // 10001100 ADC A, IXH
+        |z80, _, _| z80.adc_a_ixh(),
+        // 10001101 ADC A, IXL
+        |z80, _, _| z80.adc_a_ixl(),
+        // 10001110 ADC A, (IX+d)
+        |z80, mem, _| z80.adc_a_mem_ixd(mem),
+        // 10001111 ADC A, A
+        |z80, _, _| 4 + z80.adc_a_a(),
+        // 10010000 SUB B
+        |z80, _, _| 4 + z80.sub_b(),
+        // 10010001 SUB C
+        |z80, _, _| 4 + z80.sub_c(),
+        // 10010010 SUB D
+        |z80, _, _| 4 + z80.sub_d(),
+        // 10010011 SUB E
+        |z80, _, _| 4 + z80.sub_e(),
+        // 10010100 SUB IXH
+        |z80, _, _| z80.sub_ixh(),
+        // 10010101 SUB IXL
+        |z80, _, _| z80.sub_ixl(),
+        // 10010110 SUB (IX+d)
+        |z80, mem, _| z80.sub_mem_ixd(mem),
+        // 10010111 SUB A
+        |z80, _, _| 4 + z80.sub_a(),
+        // 10011000 SBC A, B
+        |z80, _, _| 4 + z80.sbc_a_b(),
+        // 10011001 SBC A, C
+        |z80, _, _| 4 + z80.sbc_a_c(),
+        // 10011010 SBC A, D
+        |z80, _, _| 4 + z80.sbc_a_d(),
+        // 10011011 SBC A, E
+        |z80, _, _| 4 + z80.sbc_a_e(),
+        // 10011100 SBC A, IXH
+        |z80, _, _| z80.sbc_a_ixh(),
+        // 10011101 SBC A, IXL
+        |z80, _, _| z80.sbc_a_ixl(),
+        // 10011110 SBC A, (IX+d)
+        |z80, mem, _| z80.sbc_a_mem_ixd(mem),
+        // 10011111 SBC A, A
+        |z80, _, _| 4 + z80.sbc_a_a(),
+        // 10100000 AND B
+        |z80, _, _| 4 + z80.and_b(),
+        // 10100001 AND C
+        |z80, _, _| 4 + z80.and_c(),
+        // 10100010 AND D
+        |z80, _, _| 4 + z80.and_d(),
+        // 10100011 AND E
+        |z80, _, _| 4 + z80.and_e(),
+        // 10100100 AND IXH
+        |z80, _, _| z80.and_ixh(),
+        // 10100101 AND IXL
+        |z80, _, _| z80.and_ixl(),
+        // 10100110 AND (IX+d)
+        |z80, mem, _| z80.and_mem_ixd(mem),
+        // 10100111 AND A
+        |z80, _, _| 4 + z80.and_a(),
+        // 10101000 XOR B
+        |z80, _, _| 4 + z80.xor_b(),
+        // 10101001 XOR C
+        |z80, _, _| 4 + z80.xor_c(),
+        // 10101010 XOR D
+        |z80, _, _| 4 + z80.xor_d(),
+        // 10101011 XOR E
+        |z80, _, _| 4 + z80.xor_e(),
+        // 10101100 XOR IXH
+        |z80, _, _| z80.xor_ixh(),
+        // 10101101 XOR IXL
+        |z80, _, _| z80.xor_ixl(),
+        // 10101110 XOR (IX+d)
+        |z80, mem, _| z80.xor_mem_ixd(mem),
+        // 10101111 XOR A
+        |z80, _, _| 4 + z80.xor_a(),
+        // 10110000 OR B
+        |z80, _, _| 4 + z80.or_b(),
+        // 10110001 OR C
+        |z80, _, _| 4 + z80.or_c(),
+        // 10110010 OR D
+        |z80, _, _| 4 + z80.or_d(),
+        // 10110011 OR E
+        |z80, _, _| 4 + z80.or_e(),
+        // 10110100 OR IXH
+        |z80, _, _| z80.or_ixh(),
+        // 10110101 OR IXL
+        |z80, _, _| z80.or_ixl(),
+        // 10110110 OR (IX+d)
+        |z80, mem, _| z80.or_mem_ixd(mem),
+        // 10110111 OR A
+        |z80, _, _| 4 + z80.or_a(),
+        // 10111000 CP B
+        |z80, _, _| 4 + z80.cp_b(),
+        // 10111001 CP C
+        |z80, _, _| 4 + z80.cp_c(),
+        // 10111010 CP D
+        |z80, _, _| 4 + z80.cp_d(),
+        // 10111011 CP E
+        |z80, _, _| 4 + z80.cp_e(),
+        // 10111100 CP IXH
+        |z80, _, _| z80.cp_ixh(),
+        // 10111101 CP IXL
+        |z80, _, _| z80.cp_ixl(),
+        // 10111110 CP (IX+d)
+        |z80, mem, _| z80.cp_mem_ixd(mem),
+        // 10111111 CP A
+        |z80, _, _| 4 + z80.cp_a(),
+        // 11000000 RET NZ
+        |z80, mem, _| 4 + z80.ret_nz(mem),
+        // 11000001 POP BC
+        |z80, mem, _| 4 + z80.pop_qqbc(mem),
+        // 11000010 JP NZ, nn
+        |z80, mem, _| 4 + z80.jp_nz_nn(mem),
+        // 11000011 JP nn
+        |z80, mem, _| 4 + z80.jp_nn(mem),
+        // 11000100 CALL NZ, nn
+        |z80, mem, _| 4 + z80.call_nz_nn(mem),
+        // 11000101 PUSH BC
+        |z80, mem, _| 4 + z80.push_qqbc(mem),
+        // 11000110 ADD A, n
+        |z80, mem, _| 4 + z80.add_a_n(mem),
+        // 11000111 RST 00h
+        |z80, mem, _| 4 + z80.rst_00h(mem),
+        // 11001000 RET Z
+        |z80, mem, _| 4 + z80.ret_z(mem),
+        // 11001001 RET
+        |z80, mem, _| 4 + z80.ret(mem),
+        // 11001010 JP Z, nn
+        |z80, mem, _| 4 + z80.jp_z_nn(mem),
+        // 11001011 DDCB prefix
+        |z80, mem, _| z80.process_index_bit_instruction(z80.ix, mem),
+        // 11001100 CALL Z, nn
+        |z80, mem, _| 4 + z80.call_z_nn(mem),
+        // 11001101 CALL nn
+        |z80, mem, _| 4 + z80.call_nn(mem),
+        // 11001110 ADC A, n
+        |z80, mem, _| 4 + z80.adc_a_n(mem),
+        // 11001111 RST 08h
+        |z80, mem, _| 4 + z80.rst_08h(mem),
+        // 11010000 RET NC
+        |z80, mem, _| 4 + z80.ret_nc(mem),
+        // 11010001 POP DE
+        |z80, mem, _| 4 + z80.pop_qqde(mem),
+        // 11010010 JP NC, nn
+        |z80, mem, _| 4 + z80.jp_nc_nn(mem),
+        // 11010011 OUT (n), A
+        |z80, mem, io| 4 + z80.out_n_a(mem, io),
+        // 11010100 CALL NC, nn
+        |z80, mem, _| 4 + z80.call_nc_nn(mem),
+        // 11010101 PUSH DE
+        |z80, mem, _| 4 + z80.push_qqde(mem),
+        // 11010110 SUB n
+        |z80, mem, _| 4 + z80.sub_n(mem),
+        // 11010111 RST 10h
+        |z80, mem, _| 4 + z80.rst_10h(mem),
+        // 11011000 RET C
+        |z80, mem, _| 4 + z80.ret_c(mem),
+        // 11011001 EXX
+        |z80, _, _| 4 + z80.exx(),
+        // 11011010 JP C, nn
+        |z80, mem, _| 4 + z80.jp_c_nn(mem),
+        // 11011011 IN A, (n)
+        |z80, mem, io| 4 + z80.in_a_n(mem, io),
+        // 11011100 CALL C, nn
+        |z80, mem, _| 4 + z80.call_c_nn(mem),
+        // 11011101 DD prefix, which starts a new instruction
+        |z80, mem, io| 4u8.saturating_add(z80.process_ix_instruction(mem, io)),
+        // 11011110 SBC A, n
+        |z80, mem, _| 4 + z80.sbc_a_n(mem),
+        // 11011111 RST 18h
+        |z80, mem, _| 4 + z80.rst_18h(mem),
+        // 11100000 RET PO
+        |z80, mem, _| 4 + z80.ret_po(mem),
+        // 11100001 POP IX
+        |z80, mem, _| z80.pop_ix(mem),
+        // 11100010 JP PO, nn
+        |z80, mem, _| 4 + z80.jp_po_nn(mem),
+        // 11100011 EX (SP), IX
+        |z80, mem, _| z80.ex_mem_sp_ix(mem),
+        // 11100100 CALL PO, nn
+        |z80, mem, _| 4 + z80.call_po_nn(mem),
+        // 11100101 PUSH IX
+        |z80, mem, _| z80.push_ix(mem),
+        // 11100110 AND n
+        |z80, mem, _| 4 + z80.and_n(mem),
+        // 11100111 RST 20h
+        |z80, mem, _| 4 + z80.rst_20h(mem),
+        // 11101000 RET PE
+        |z80, mem, _| 4 + z80.ret_pe(mem),
+        // 11101001 JP (IX)
+        |z80, _, _| z80.jp_mem_ix(),
+        // 11101010 JP PE, nn
+        |z80, mem, _| 4 + z80.jp_pe_nn(mem),
+        // 11101011 EX DE, HL
+        |z80, _, _| 4 + z80.ex_de_hl(),
+        // 11101100 CALL PE, nn
+        |z80, mem, _| 4 + z80.call_pe_nn(mem),
+        // 11101101 ED prefix
+        |z80, mem, io| 4u8.saturating_add(z80.process_misc_instruction(mem, io)),
+        // 11101110 XOR n
+        |z80, mem, _| 4 + z80.xor_n(mem),
+        // 11101111 RST 28h
+        |z80, mem, _| 4 + z80.rst_28h(mem),
+        // 11110000 RET P
+        |z80, mem, _| 4 + z80.ret_p(mem),
+        // 11110001 POP AF
+        |z80, mem, _| 4 + z80.pop_qqaf(mem),
+        // 11110010 JP P, nn
+        |z80, mem, _| 4 + z80.jp_p_nn(mem),
+        // 11110011 DI
+        |z80, _, _| 4 + z80.di(),
+        // 11110100 CALL P, nn
+        |z80, mem, _| 4 + z80.call_p_nn(mem),
+        // 11110101 PUSH AF
+        |z80, mem, _| 4 + z80.push_qqaf(mem),
+        // 11110110 OR n
+        |z80, mem, _| 4 + z80.or_n(mem),
+        // 11110111 RST 30h
+        |z80, mem, _| 4 + z80.rst_30h(mem),
+        // 11111000 RET M
+        |z80, mem, _| 4 + z80.ret_m(mem),
+        // 11111001 LD SP, IX
+        |z80, mem, _| z80.ld_sp_ix(mem),
+        // 11111010 JP M, nn
+        |z80, mem, _| 4 + z80.jp_m_nn(mem),
+        // 11111011 EI
+        |z80, _, _| 4 + z80.ei(),
+        // 11111100 CALL M, nn
+        |z80, mem, _| 4 + z80.call_m_nn(mem),
+        // 11111101 FD prefix, which starts a new instruction
+        |z80, mem, io| 4u8.saturating_add(z80.process_iy_instruction(mem, io)),
+        // 11111110 CP n
+        |z80, mem, _| 4 + z80.cp_n(mem),
+        // 11111111 RST 38h
+        |z80, mem, _| 4 + z80.rst_38h(mem),
+    ];
+}
+
+// DDCB and FDCB prefixes, where (IX+d) stands for (IY+d) after FD. The
+// address is computed into MEMPTR before dispatch.
+impl<M: Z80Memory + ?Sized> BitOpcodes<M> {
+    const INDEX_BIT_INSTRUCTIONS: [BitOpcodeFunction<M>; 256] = [
+        // 00000000 LD B, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00000001 LD C, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00000010 LD D, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00000011 LD E, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00000100 LD H, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00000101 LD L, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00000110 RLC (IX+d)
+        |z80, mem| z80.rlc_mem_index_d(mem),
+        // 00000111 LD A, RLC (IX+d) (undocumented)
+        |z80, mem| z80.rlc_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00001000 LD B, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00001001 LD C, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00001010 LD D, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00001011 LD E, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00001100 LD H, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00001101 LD L, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00001110 RRC (IX+d)
+        |z80, mem| z80.rrc_mem_index_d(mem),
+        // 00001111 LD A, RRC (IX+d) (undocumented)
+        |z80, mem| z80.rrc_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00010000 LD B, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00010001 LD C, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00010010 LD D, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00010011 LD E, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00010100 LD H, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00010101 LD L, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00010110 RL (IX+d)
+        |z80, mem| z80.rl_mem_index_d(mem),
+        // 00010111 LD A, RL (IX+d) (undocumented)
+        |z80, mem| z80.rl_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00011000 LD B, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00011001 LD C, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00011010 LD D, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00011011 LD E, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00011100 LD H, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00011101 LD L, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00011110 RR (IX+d)
+        |z80, mem| z80.rr_mem_index_d(mem),
+        // 00011111 LD A, RR (IX+d) (undocumented)
+        |z80, mem| z80.rr_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00100000 LD B, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00100001 LD C, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00100010 LD D, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00100011 LD E, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00100100 LD H, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00100101 LD L, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00100110 SLA (IX+d)
+        |z80, mem| z80.sla_mem_index_d(mem),
+        // 00100111 LD A, SLA (IX+d) (undocumented)
+        |z80, mem| z80.sla_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00101000 LD B, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00101001 LD C, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00101010 LD D, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00101011 LD E, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00101100 LD H, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00101101 LD L, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00101110 SRA (IX+d)
+        |z80, mem| z80.sra_mem_index_d(mem),
+        // 00101111 LD A, SRA (IX+d) (undocumented)
+        |z80, mem| z80.sra_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00110000 LD B, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00110001 LD C, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00110010 LD D, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00110011 LD E, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00110100 LD H, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00110101 LD L, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00110110 SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d(mem),
+        // 00110111 LD A, SLL (IX+d) (undocumented)
+        |z80, mem| z80.sll_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 00111000 LD B, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.b),
+        // 00111001 LD C, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.c),
+        // 00111010 LD D, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.d),
+        // 00111011 LD E, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.e),
+        // 00111100 LD H, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.h),
+        // 00111101 LD L, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.l),
+        // 00111110 SRL (IX+d)
+        |z80, mem| z80.srl_mem_index_d(mem),
+        // 00111111 LD A, SRL (IX+d) (undocumented)
+        |z80, mem| z80.srl_mem_index_d_r(mem, |z80| &mut z80.a),
+        // 01000000 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000001 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000010 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000011 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000100 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000101 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000110 BIT 0, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01000111 BIT 0, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(0, mem),
+        // 01001000 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001001 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001010 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001011 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001100 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001101 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001110 BIT 1, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01001111 BIT 1, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(1, mem),
+        // 01010000 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010001 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010010 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010011 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010100 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010101 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010110 BIT 2, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01010111 BIT 2, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(2, mem),
+        // 01011000 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011001 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011010 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011011 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011100 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011101 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011110 BIT 3, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01011111 BIT 3, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(3, mem),
+        // 01100000 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100001 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100010 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100011 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100100 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100101 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100110 BIT 4, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01100111 BIT 4, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(4, mem),
+        // 01101000 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101001 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101010 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101011 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101100 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101101 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101110 BIT 5, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01101111 BIT 5, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(5, mem),
+        // 01110000 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110001 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110010 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110011 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110100 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110101 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110110 BIT 6, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01110111 BIT 6, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(6, mem),
+        // 01111000 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111001 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111010 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111011 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111100 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111101 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111110 BIT 7, (IX+d)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 01111111 BIT 7, (IX+d) (undocumented)
+        |z80, mem| z80.bit_b_mem_index_d(7, mem),
+        // 10000000 LD B, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.b),
+        // 10000001 LD C, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.c),
+        // 10000010 LD D, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.d),
+        // 10000011 LD E, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.e),
+        // 10000100 LD H, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.h),
+        // 10000101 LD L, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.l),
+        // 10000110 RES 0, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(0, mem),
+        // 10000111 LD A, RES 0, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(0, mem, |z80| &mut z80.a),
+        // 10001000 LD B, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.b),
+        // 10001001 LD C, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.c),
+        // 10001010 LD D, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.d),
+        // 10001011 LD E, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.e),
+        // 10001100 LD H, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.h),
+        // 10001101 LD L, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.l),
+        // 10001110 RES 1, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(1, mem),
+        // 10001111 LD A, RES 1, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(1, mem, |z80| &mut z80.a),
+        // 10010000 LD B, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.b),
+        // 10010001 LD C, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.c),
+        // 10010010 LD D, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.d),
+        // 10010011 LD E, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.e),
+        // 10010100 LD H, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.h),
+        // 10010101 LD L, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.l),
+        // 10010110 RES 2, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(2, mem),
+        // 10010111 LD A, RES 2, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(2, mem, |z80| &mut z80.a),
+        // 10011000 LD B, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.b),
+        // 10011001 LD C, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.c),
+        // 10011010 LD D, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.d),
+        // 10011011 LD E, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.e),
+        // 10011100 LD H, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.h),
+        // 10011101 LD L, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.l),
+        // 10011110 RES 3, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(3, mem),
+        // 10011111 LD A, RES 3, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(3, mem, |z80| &mut z80.a),
+        // 10100000 LD B, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.b),
+        // 10100001 LD C, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.c),
+        // 10100010 LD D, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.d),
+        // 10100011 LD E, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.e),
+        // 10100100 LD H, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.h),
+        // 10100101 LD L, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.l),
+        // 10100110 RES 4, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(4, mem),
+        // 10100111 LD A, RES 4, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(4, mem, |z80| &mut z80.a),
+        // 10101000 LD B, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.b),
+        // 10101001 LD C, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.c),
+        // 10101010 LD D, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.d),
+        // 10101011 LD E, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.e),
+        // 10101100 LD H, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.h),
+        // 10101101 LD L, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.l),
+        // 10101110 RES 5, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(5, mem),
+        // 10101111 LD A, RES 5, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(5, mem, |z80| &mut z80.a),
+        // 10110000 LD B, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.b),
+        // 10110001 LD C, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.c),
+        // 10110010 LD D, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.d),
+        // 10110011 LD E, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.e),
+        // 10110100 LD H, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.h),
+        // 10110101 LD L, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.l),
+        // 10110110 RES 6, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(6, mem),
+        // 10110111 LD A, RES 6, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(6, mem, |z80| &mut z80.a),
+        // 10111000 LD B, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.b),
+        // 10111001 LD C, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.c),
+        // 10111010 LD D, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.d),
+        // 10111011 LD E, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.e),
+        // 10111100 LD H, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.h),
+        // 10111101 LD L, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.l),
+        // 10111110 RES 7, (IX+d)
+        |z80, mem| z80.res_b_mem_index_d(7, mem),
+        // 10111111 LD A, RES 7, (IX+d) (undocumented)
+        |z80, mem| z80.res_b_mem_index_d_r(7, mem, |z80| &mut z80.a),
+        // 11000000 LD B, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.b),
+        // 11000001 LD C, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.c),
+        // 11000010 LD D, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.d),
+        // 11000011 LD E, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.e),
+        // 11000100 LD H, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.h),
+        // 11000101 LD L, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.l),
+        // 11000110 SET 0, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(0, mem),
+        // 11000111 LD A, SET 0, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(0, mem, |z80| &mut z80.a),
+        // 11001000 LD B, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.b),
+        // 11001001 LD C, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.c),
+        // 11001010 LD D, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.d),
+        // 11001011 LD E, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.e),
+        // 11001100 LD H, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.h),
+        // 11001101 LD L, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.l),
+        // 11001110 SET 1, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(1, mem),
+        // 11001111 LD A, SET 1, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(1, mem, |z80| &mut z80.a),
+        // 11010000 LD B, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.b),
+        // 11010001 LD C, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.c),
+        // 11010010 LD D, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.d),
+        // 11010011 LD E, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.e),
+        // 11010100 LD H, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.h),
+        // 11010101 LD L, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.l),
+        // 11010110 SET 2, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(2, mem),
+        // 11010111 LD A, SET 2, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(2, mem, |z80| &mut z80.a),
+        // 11011000 LD B, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.b),
+        // 11011001 LD C, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.c),
+        // 11011010 LD D, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.d),
+        // 11011011 LD E, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.e),
+        // 11011100 LD H, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.h),
+        // 11011101 LD L, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.l),
+        // 11011110 SET 3, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(3, mem),
+        // 11011111 LD A, SET 3, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(3, mem, |z80| &mut z80.a),
+        // 11100000 LD B, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.b),
+        // 11100001 LD C, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.c),
+        // 11100010 LD D, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.d),
+        // 11100011 LD E, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.e),
+        // 11100100 LD H, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.h),
+        // 11100101 LD L, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.l),
+        // 11100110 SET 4, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(4, mem),
+        // 11100111 LD A, SET 4, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(4, mem, |z80| &mut z80.a),
+        // 11101000 LD B, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.b),
+        // 11101001 LD C, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.c),
+        // 11101010 LD D, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.d),
+        // 11101011 LD E, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.e),
+        // 11101100 LD H, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.h),
+        // 11101101 LD L, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.l),
+        // 11101110 SET 5, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(5, mem),
+        // 11101111 LD A, SET 5, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(5, mem, |z80| &mut z80.a),
+        // 11110000 LD B, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.b),
+        // 11110001 LD C, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.c),
+        // 11110010 LD D, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.d),
+        // 11110011 LD E, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.e),
+        // 11110100 LD H, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.h),
+        // 11110101 LD L, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.l),
+        // 11110110 SET 6, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(6, mem),
+        // 11110111 LD A, SET 6, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(6, mem, |z80| &mut z80.a),
+        // 11111000 LD B, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.b),
+        // 11111001 LD C, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.c),
+        // 11111010 LD D, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.d),
+        // 11111011 LD E, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.e),
+        // 11111100 LD H, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.h),
+        // 11111101 LD L, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.l),
+        // 11111110 SET 7, (IX+d)
+        |z80, mem| z80.set_b_mem_index_d(7, mem),
+        // 11111111 LD A, SET 7, (IX+d) (undocumented)
+        |z80, mem| z80.set_b_mem_index_d_r(7, mem, |z80| &mut z80.a),
     ];
+}
 
 // ED prefix
-const MISC_INSTRUCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 17] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001
-    // 00100010
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011 LD (nn), BC
-    Z80::ld_mem_nn_ddbc,
-    // 01000100
-    // 01000101
-    // 01000110
-    // 01000111 LD I, A
-    |z80, _| z80.ld_i_a(),
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011 LD BC, (nn)
-    |z80, mem| z80.ld_ddbc_mem_nn(mem),
-    // 01001100
-    // 01001101
-    // 01001110
-    // 01001111 LD R, A
-    |z80, _| z80.ld_r_a(),
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011 LD (nn), DE
-    Z80::ld_mem_nn_ddde,
-    // 01010100
-    // 01010101
-    // 01010110
-    // 01010111 LD A, I
-    |z80, _| z80.ld_a_i(),
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011 LD DE, (nn)
-    |z80, mem| z80.ld_ddde_mem_nn(mem),
-    // 01011100
-    // 01011101
-    // 01011110
-    // 01011111 LD A, R
-    |z80, _| z80.ld_a_r(),
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011 LD (nn), HL
-    Z80::ld_mem_nn_ddhl,
-    // 01100100
-    // 01100101
-    // 01100110
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011 LD HL, (nn)
-    |z80, mem| z80.ld_ddhl_mem_nn(mem),
-    // 01101100
-    // 01101101
-    // 01101110
-    // 01101111
-    // 01110000
-    // 01110001
-    // 01110010
-    // 01110011 LD (nn), SP
-    Z80::ld_mem_nn_ddsp,
-    // 01110100
-    // 01110101
-    // 01110110
-    // 01110111
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011 LD SP, (nn)
-    |z80, mem| z80.ld_ddsp_mem_nn(mem),
-    // 01111100
-    // 01111101
-    // 01111110
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000 LDI
-    Z80::ldi,
-    // 10100001 CPI
-    |z80, mem| z80.cpi(mem),
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000 LDD
-    Z80::ldd,
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000 LDIR
-    Z80::ldir,
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000 LDDR
-    Z80::lddr,
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001
-    // 11100010
-    // 11100011
-    // 11100100
-    // 11100101
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
-];
+impl<M: Z80Memory + ?Sized, I: Z80Io + ?Sized> Opcodes<M, I> {
+    const MISC_INSTRUCTIONS: [OpcodeFunction<M, I>; 256] = [
+        // 00000000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00000111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00001111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00010111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00011111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00100111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00101111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00110111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 00111111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 01000000 IN B, (C)
+        |z80, _, io| z80.in_b_c(io),
+        // 01000001 OUT (C), B
+        |z80, _, io| z80.out_c_b(io),
+        // 01000010 SBC HL, BC
+        |z80, mem, _| z80.sbc_hl_bc(mem),
+        // 01000011 LD (nn), BC
+        |z80, mem, _| z80.ld_mem_nn_ddbc(mem),
+        // 01000100 NEG
+        |z80, _, _| z80.neg(),
+        // 01000101 RETN
+        |z80, mem, _| z80.retn(mem),
+        // 01000110 IM 0
+        |z80, _, _| z80.im_0(),
+        // 01000111 LD I, A
+        |z80, mem, _| z80.ld_i_a(mem),
+        // 01001000 IN C, (C)
+        |z80, _, io| z80.in_c_c(io),
+        // 01001001 OUT (C), C
+        |z80, _, io| z80.out_c_c(io),
+        // 01001010 ADC HL, BC
+        |z80, mem, _| z80.adc_hl_bc(mem),
+        // 01001011 LD BC, (nn)
+        |z80, mem, _| z80.ld_ddbc_mem_nn(mem),
+        // 01001100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01001101 RETI
+        |z80, mem, _| z80.reti(mem),
+        // 01001110 IM 0/1 (undocumented)
+        |z80, _, _| z80.im_0(),
+        // 01001111 LD R, A
+        |z80, mem, _| z80.ld_r_a(mem),
+        // 01010000 IN D, (C)
+        |z80, _, io| z80.in_d_c(io),
+        // 01010001 OUT (C), D
+        |z80, _, io| z80.out_c_d(io),
+        // 01010010 SBC HL, DE
+        |z80, mem, _| z80.sbc_hl_de(mem),
+        // 01010011 LD (nn), DE
+        |z80, mem, _| z80.ld_mem_nn_ddde(mem),
+        // 01010100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01010101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01010110 IM 1
+        |z80, _, _| z80.im_1(),
+        // 01010111 LD A, I
+        |z80, mem, _| z80.ld_a_i(mem),
+        // 01011000 IN E, (C)
+        |z80, _, io| z80.in_e_c(io),
+        // 01011001 OUT (C), E
+        |z80, _, io| z80.out_c_e(io),
+        // 01011010 ADC HL, DE
+        |z80, mem, _| z80.adc_hl_de(mem),
+        // 01011011 LD DE, (nn)
+        |z80, mem, _| z80.ld_ddde_mem_nn(mem),
+        // 01011100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01011101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01011110 IM 2
+        |z80, _, _| z80.im_2(),
+        // 01011111 LD A, R
+        |z80, mem, _| z80.ld_a_r(mem),
+        // 01100000 IN H, (C)
+        |z80, _, io| z80.in_h_c(io),
+        // 01100001 OUT (C), H
+        |z80, _, io| z80.out_c_h(io),
+        // 01100010 SBC HL, HL
+        |z80, mem, _| z80.sbc_hl_hl(mem),
+        // 01100011 LD (nn), HL
+        |z80, mem, _| z80.ld_mem_nn_ddhl(mem),
+        // 01100100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01100101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01100110 IM 0 (undocumented)
+        |z80, _, _| z80.im_0(),
+        // 01100111 RRD
+        |z80, mem, _| z80.rrd(mem),
+        // 01101000 IN L, (C)
+        |z80, _, io| z80.in_l_c(io),
+        // 01101001 OUT (C), L
+        |z80, _, io| z80.out_c_l(io),
+        // 01101010 ADC HL, HL
+        |z80, mem, _| z80.adc_hl_hl(mem),
+        // 01101011 LD HL, (nn)
+        |z80, mem, _| z80.ld_ddhl_mem_nn(mem),
+        // 01101100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01101101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01101110 IM 0/1 (undocumented)
+        |z80, _, _| z80.im_0(),
+        // 01101111 RLD
+        |z80, mem, _| z80.rld(mem),
+        // 01110000 IN (C) (undocumented)
+        |z80, _, io| z80.in_c(io),
+        // 01110001 OUT (C), 0 (undocumented)
+        |z80, _, io| z80.out_c_0(io),
+        // 01110010 SBC HL, SP
+        |z80, mem, _| z80.sbc_hl_sp(mem),
+        // 01110011 LD (nn), SP
+        |z80, mem, _| z80.ld_mem_nn_ddsp(mem),
+        // 01110100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01110101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01110110 IM 1 (undocumented)
+        |z80, _, _| z80.im_1(),
+        // 01110111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 01111000 IN A, (C)
+        |z80, _, io| z80.in_a_c(io),
+        // 01111001 OUT (C), A
+        |z80, _, io| z80.out_c_a(io),
+        // 01111010 ADC HL, SP
+        |z80, mem, _| z80.adc_hl_sp(mem),
+        // 01111011 LD SP, (nn)
+        |z80, mem, _| z80.ld_ddsp_mem_nn(mem),
+        // 01111100 NEG (undocumented)
+        |z80, _, _| z80.neg(),
+        // 01111101 RETN (undocumented)
+        |z80, mem, _| z80.retn(mem),
+        // 01111110 IM 2 (undocumented)
+        |z80, _, _| z80.im_2(),
+        // 01111111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10000111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10001111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10010111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10011111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10100000 LDI
+        |z80, mem, _| z80.ldi(mem),
+        // 10100001 CPI
+        |z80, mem, _| z80.cpi(mem),
+        // 10100010 INI
+        |z80, mem, io| z80.ini(mem, io),
+        // 10100011 OUTI
+        |z80, mem, io| z80.outi(mem, io),
+        // 10100100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10100101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10100110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10100111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10101000 LDD
+        |z80, mem, _| z80.ldd(mem),
+        // 10101001 CPD
+        |z80, mem, _| z80.cpd(mem),
+        // 10101010 IND
+        |z80, mem, io| z80.ind(mem, io),
+        // 10101011 OUTD
+        |z80, mem, io| z80.outd(mem, io),
+        // 10101100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10101101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10101110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10101111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10110000 LDIR
+        |z80, mem, _| z80.ldir(mem),
+        // 10110001 CPIR
+        |z80, mem, _| z80.cpir(mem),
+        // 10110010 INIR
+        |z80, mem, io| z80.inir(mem, io),
+        // 10110011 OTIR
+        |z80, mem, io| z80.otir(mem, io),
+        // 10110100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10110101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10110110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10110111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10111000 LDDR
+        |z80, mem, _| z80.lddr(mem),
+        // 10111001 CPDR
+        |z80, mem, _| z80.cpdr(mem),
+        // 10111010 INDR
+        |z80, mem, io| z80.indr(mem, io),
+        // 10111011 OTDR
+        |z80, mem, io| z80.otdr(mem, io),
+        // 10111100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10111101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10111110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 10111111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11000111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11001111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11010111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11011111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11100111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11101111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11110111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111000 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111001 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111010 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111011 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111100 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111101 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111110 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+        // 11111111 NOP (undocumented)
+        |z80, mem, io| z80.invalid_misc_opcode(mem, io),
+    ];
+}
 
 // FD prefix
-const IY_FUNCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 22] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001 LD IY, nn
-    |z80, mem| z80.ld_iy_nn(mem),
-    // 00100010 LD (nn), IY
-    Z80::ld_mem_nn_iy,
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010 LD IY, (nn)
-    |z80, mem| z80.ld_iy_mem_nn(mem),
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110 LD (IY+d), n
-    |z80, mem| z80.ld_mem_iyd_n(mem),
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011
-    // 01000100
-    // 01000101
-    // 01000110 LD B, (IY+d)
-    |z80, mem| z80.ld_b_mem_iyd(mem),
-    // 01000111
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011
-    // 01001100
-    // 01001101
-    // 01001110 LD C, (IY+d)
-    |z80, mem| z80.ld_c_mem_iyd(mem),
-    // 01001111
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011
-    // 01010100
-    // 01010101
-    // 01010110 LD D, (IY+d)
-    |z80, mem| z80.ld_d_mem_iyd(mem),
-    // 01010111
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011
-    // 01011100
-    // 01011101
-    // 01011110 LD E, (IY+d)
-    |z80, mem| z80.ld_e_mem_iyd(mem),
-    // 01011111
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011
-    // 01100100
-    // 01100101
-    // 01100110 LD H, (IY+d)
-    |z80, mem| z80.ld_h_mem_iyd(mem),
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011
-    // 01101100
-    // 01101101
-    // 01101110 LD L, (IY+d)
-    |z80, mem| z80.ld_l_mem_iyd(mem),
-    // 01101111
-    // 01110000 LD (IY+d), B
-    |z80, mem| z80.ld_mem_iyd_b(mem),
-    // 01110001 LD (IY+d), C
-    |z80, mem| z80.ld_mem_iyd_c(mem),
-    // 01110010 LD (IY+d), D
-    |z80, mem| z80.ld_mem_iyd_d(mem),
-    // 01110011 LD (IY+d), E
-    |z80, mem| z80.ld_mem_iyd_e(mem),
-    // 01110100 LD (IY+d), H
-    |z80, mem| z80.ld_mem_iyd_h(mem),
-    // 01110101 LD (IY+d), L
-    |z80, mem| z80.ld_mem_iyd_l(mem),
-    // 01110110
-    // 01110111 LD (IY+d), A
-    |z80, mem| z80.ld_mem_iyd_a(mem),
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011
-    // 01111100
-    // 01111101
-    // 01111110 LD A, (IY+d)
-    |z80, mem| z80.ld_a_mem_iyd(mem),
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001 POP IY
-    |z80, mem| z80.pop_iy(mem),
-    // 11100010
-    // 11100011 EX (SP), IY
-    Z80::ex_mem_sp_iy,
-    // 11100100
-    // 11100101 PUSH IY
-    Z80::push_iy,
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001 LD SP, IY
-    |z80, _| z80.ld_sp_iy(),
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
-];
-
-// FDCB prefix
-const IY_BIT_INSTRUCTIONS: [fn(&mut Z80, &mut dyn Z80Memory) -> u8; 0] = [
-    // 00000000
-    // 00000001
-    // 00000010
-    // 00000011
-    // 00000100
-    // 00000101
-    // 00000110
-    // 00000111
-    // 00001000
-    // 00001001
-    // 00001010
-    // 00001011
-    // 00001100
-    // 00001101
-    // 00001110
-    // 00001111
-    // 00010000
-    // 00010001
-    // 00010010
-    // 00010011
-    // 00010100
-    // 00010101
-    // 00010110
-    // 00010111
-    // 00011000
-    // 00011001
-    // 00011010
-    // 00011011
-    // 00011100
-    // 00011101
-    // 00011110
-    // 00011111
-    // 00100000
-    // 00100001
-    // 00100010
-    // 00100011
-    // 00100100
-    // 00100101
-    // 00100110
-    // 00100111
-    // 00101000
-    // 00101001
-    // 00101010
-    // 00101011
-    // 00101100
-    // 00101101
-    // 00101110
-    // 00101111
-    // 00110000
-    // 00110001
-    // 00110010
-    // 00110011
-    // 00110100
-    // 00110101
-    // 00110110
-    // 00110111
-    // 00111000
-    // 00111001
-    // 00111010
-    // 00111011
-    // 00111100
-    // 00111101
-    // 00111110
-    // 00111111
-    // 01000000
-    // 01000001
-    // 01000010
-    // 01000011
-    // 01000100
-    // 01000101
-    // 01000110
-    // 01000111
-    // 01001000
-    // 01001001
-    // 01001010
-    // 01001011
-    // 01001100
-    // 01001101
-    // 01001110
-    // 01001111
-    // 01010000
-    // 01010001
-    // 01010010
-    // 01010011
-    // 01010100
-    // 01010101
-    // 01010110
-    // 01010111
-    // 01011000
-    // 01011001
-    // 01011010
-    // 01011011
-    // 01011100
-    // 01011101
-    // 01011110
-    // 01011111
-    // 01100000
-    // 01100001
-    // 01100010
-    // 01100011
-    // 01100100
-    // 01100101
-    // 01100110
-    // 01100111
-    // 01101000
-    // 01101001
-    // 01101010
-    // 01101011
-    // 01101100
-    // 01101101
-    // 01101110
-    // 01101111
-    // 01110000
-    // 01110001
-    // 01110010
-    // 01110011
-    // 01110100
-    // 01110101
-    // 01110110
-    // 01110111
-    // 01111000
-    // 01111001
-    // 01111010
-    // 01111011
-    // 01111100
-    // 01111101
-    // 01111110
-    // 01111111
-    // 10000000
-    // 10000001
-    // 10000010
-    // 10000011
-    // 10000100
-    // 10000101
-    // 10000110
-    // 10000111
-    // 10001000
-    // 10001001
-    // 10001010
-    // 10001011
-    // 10001100
-    // 10001101
-    // 10001110
-    // 10001111
-    // 10010000
-    // 10010001
-    // 10010010
-    // 10010011
-    // 10010100
-    // 10010101
-    // 10010110
-    // 10010111
-    // 10011000
-    // 10011001
-    // 10011010
-    // 10011011
-    // 10011100
-    // 10011101
-    // 10011110
-    // 10011111
-    // 10100000
-    // 10100001
-    // 10100010
-    // 10100011
-    // 10100100
-    // 10100101
-    // 10100110
-    // 10100111
-    // 10101000
-    // 10101001
-    // 10101010
-    // 10101011
-    // 10101100
-    // 10101101
-    // 10101110
-    // 10101111
-    // 10110000
-    // 10110001
-    // 10110010
-    // 10110011
-    // 10110100
-    // 10110101
-    // 10110110
-    // 10110111
-    // 10111000
-    // 10111001
-    // 10111010
-    // 10111011
-    // 10111100
-    // 10111101
-    // 10111110
-    // 10111111
-    // 11000000
-    // 11000001
-    // 11000010
-    // 11000011
-    // 11000100
-    // 11000101
-    // 11000110
-    // 11000111
-    // 11001000
-    // 11001001
-    // 11001010
-    // 11001011
-    // 11001100
-    // 11001101
-    // 11001110
-    // 11001111
-    // 11010000
-    // 11010001
-    // 11010010
-    // 11010011
-    // 11010100
-    // 11010101
-    // 11010110
-    // 11010111
-    // 11011000
-    // 11011001
-    // 11011010
-    // 11011011
-    // 11011100
-    // 11011101
-    // 11011110
-    // 11011111
-    // 11100000
-    // 11100001
-    // 11100010
-    // 11100011
-    // 11100100
-    // 11100101
-    // 11100110
-    // 11100111
-    // 11101000
-    // 11101001
-    // 11101010
-    // 11101011
-    // 11101100
-    // 11101101
-    // 11101110
-    // 11101111
-    // 11110000
-    // 11110001
-    // 11110010
-    // 11110011
-    // 11110100
-    // 11110101
-    // 11110110
-    // 11110111
-    // 11111000
-    // 11111001
-    // 11111010
-    // 11111011
-    // 11111100
-    // 11111101
-    // 11111110
-    // 11111111
+impl<M: Z80Memory + ?Sized, I: Z80Io + ?Sized> Opcodes<M, I> {
+    const IY_FUNCTIONS: [OpcodeFunction<M, I>; 256] = [
+        // 00000000 NOP
+        |_, _, _| 4 + Z80::nop(),
+        // 00000001 LD BC, nn
+        |z80, mem, _| 4 + z80.ld_bc_nn(mem),
+        // 00000010 LD (BC), A
+        |z80, mem, _| 4 + z80.ld_mem_bc_a(mem),
+        // 00000011 INC BC
+        |z80, mem, _| 4 + z80.inc_bc(mem),
+        // 00000100 INC B
+        |z80, _, _| 4 + z80.inc_b(),
+        // 00000101 DEC B
+        |z80, _, _| 4 + z80.dec_b(),
+        // 00000110 LD B, n
+        |z80, mem, _| 4 + z80.ld_b_n(mem),
+        // 00000111 RLCA
+        |z80, _, _| 4 + z80.rlca(),
+        // 00001000 EX AF, AF'
+        |z80, _, _| 4 + z80.ex_af_afp(),
+        // 00001001 ADD IY, BC
+        |z80, mem, _| z80.add_iy_bc(mem),
+        // 00001010 LD A, (BC)
+        |z80, mem, _| 4 + z80.ld_a_mem_bc(mem),
+        // 00001011 DEC BC
+        |z80, mem, _| 4 + z80.dec_bc(mem),
+        // 00001100 INC C
+        |z80, _, _| 4 + z80.inc_c(),
+        // 00001101 DEC C
+        |z80, _, _| 4 + z80.dec_c(),
+        // 00001110 LD C, n
+        |z80, mem, _| 4 + z80.ld_c_n(mem),
+        // 00001111 RRCA
+        |z80, _, _| 4 + z80.rrca(),
+        // 00010000 DJNZ e
+        |z80, mem, _| 4 + z80.djnz_e(mem),
+        // 00010001 LD DE, nn
+        |z80, mem, _| 4 + z80.ld_de_nn(mem),
+        // 00010010 LD (DE), A
+        |z80, mem, _| 4 + z80.ld_mem_de_a(mem),
+        // 00010011 INC DE
+        |z80, mem, _| 4 + z80.inc_de(mem),
+        // 00010100 INC D
+        |z80, _, _| 4 + z80.inc_d(),
+        // 00010101 DEC D
+        |z80, _, _| 4 + z80.dec_d(),
+        // 00010110 LD D, n
+        |z80, mem, _| 4 + z80.ld_d_n(mem),
+        // 00010111 RLA
+        |z80, _, _| 4 + z80.rla(),
+        // 00011000 JR e
+        |z80, mem, _| 4 + z80.jr_e(mem),
+        // 00011001 ADD IY, DE
+        |z80, mem, _| z80.add_iy_de(mem),
+        // 00011010 LD A, (DE)
+        |z80, mem, _| 4 + z80.ld_a_mem_de(mem),
+        // 00011011 DEC DE
+        |z80, mem, _| 4 + z80.dec_de(mem),
+        // 00011100 INC E
+        |z80, _, _| 4 + z80.inc_e(),
+        // 00011101 DEC E
+        |z80, _, _| 4 + z80.dec_e(),
+        // 00011110 LD E, n
+        |z80, mem, _| 4 + z80.ld_e_n(mem),
+        // 00011111 RRA
+        |z80, _, _| 4 + z80.rra(),
+        // 00100000 JR NZ, e
+        |z80, mem, _| 4 + z80.jr_nz_e(mem),
+        // 00100001 LD IY, nn
+        |z80, mem, _| z80.ld_iy_nn(mem),
+        // 00100010 LD (nn), IY
+        |z80, mem, _| z80.ld_mem_nn_iy(mem),
+        // 00100011 INC IY
+        |z80, mem, _| z80.inc_iy(mem),
+        // 00100100 INC IYH
+        |z80, _, _| z80.inc_iyh(),
+        // 00100101 DEC IYH
+        |z80, _, _| z80.dec_iyh(),
+        // 00100110 LD IYH, n
+        |z80, mem, _| z80.ld_iyh_n(mem),
+        // 00100111 DAA
+        |z80, _, _| 4 + z80.daa(),
+        // 00101000 JR Z, e
+        |z80, mem, _| 4 + z80.jr_z_e(mem),
+        // 00101001 ADD IY, IY
+        |z80, mem, _| z80.add_iy_iy(mem),
+        // 00101010 LD IY, (nn)
+        |z80, mem, _| z80.ld_iy_mem_nn(mem),
+        // 00101011 DEC IY
+        |z80, mem, _| z80.dec_iy(mem),
+        // 00101100 INC IYL
+        |z80, _, _| z80.inc_iyl(),
+        // 00101101 DEC IYL
+        |z80, _, _| z80.dec_iyl(),
+        // 00101110 LD IYL, n
+        |z80, mem, _| z80.ld_iyl_n(mem),
+        // 00101111 CPL
+        |z80, _, _| 4 + z80.cpl(),
+        // 00110000 JR NC, e
+        |z80, mem, _| 4 + z80.jr_nc_e(mem),
+        // 00110001 LD SP, nn
+        |z80, mem, _| 4 + z80.ld_sp_nn(mem),
+        // 00110010 LD (nn), A
+        |z80, mem, _| 4 + z80.ld_mem_nn_a(mem),
+        // 00110011 INC SP
+        |z80, mem, _| 4 + z80.inc_sp(mem),
+        // 00110100 INC (IY+d)
+        |z80, mem, _| z80.inc_mem_iyd(mem),
+        // 00110101 DEC (IY+d)
+        |z80, mem, _| z80.dec_mem_iyd(mem),
+        // 00110110 LD (IY+d), n
+        |z80, mem, _| z80.ld_mem_iyd_n(mem),
+        // 00110111 SCF
+        |z80, _, _| 4 + z80.scf(),
+        // 00111000 JR C, e
+        |z80, mem, _| 4 + z80.jr_c_e(mem),
+        // 00111001 ADD IY, SP
+        |z80, mem, _| z80.add_iy_sp(mem),
+        // 00111010 LD A, (nn)
+        |z80, mem, _| 4 + z80.ld_a_mem_nn(mem),
+        // 00111011 DEC SP
+        |z80, mem, _| 4 + z80.dec_sp(mem),
+        // 00111100 INC A
+        |z80, _, _| 4 + z80.inc_a(),
+        // 00111101 DEC A
+        |z80, _, _| 4 + z80.dec_a(),
+        // 00111110 LD A, n
+        |z80, mem, _| 4 + z80.ld_a_n(mem),
+        // 00111111 CCF
+        |z80, _, _| 4 + z80.ccf(),
+        // 01000000 LD B, B
+        |z80, _, _| 4 + z80.ld_b_b(),
+        // 01000001 LD B, C
+        |z80, _, _| 4 + z80.ld_b_c(),
+        // 01000010 LD B, D
+        |z80, _, _| 4 + z80.ld_b_d(),
+        // 01000011 LD B, E
+        |z80, _, _| 4 + z80.ld_b_e(),
+        // 01000100 LD B, IYH
+        |z80, _, _| z80.ld_b_iyh(),
+        // 01000101 LD B, IYL
+        |z80, _, _| z80.ld_b_iyl(),
+        // 01000110 LD B, (IY+d)
+        |z80, mem, _| z80.ld_b_mem_iyd(mem),
+        // 01000111 LD B, A
+        |z80, _, _| 4 + z80.ld_b_a(),
+        // 01001000 LD C, B
+        |z80, _, _| 4 + z80.ld_c_b(),
+        // 01001001 LD C, C
+        |z80, _, _| 4 + z80.ld_c_c(),
+        // 01001010 LD C, D
+        |z80, _, _| 4 + z80.ld_c_d(),
+        // 01001011 LD C, E
+        |z80, _, _| 4 + z80.ld_c_e(),
+        // 01001100 LD C, IYH
+        |z80, _, _| z80.ld_c_iyh(),
+        // 01001101 LD C, IYL
+        |z80, _, _| z80.ld_c_iyl(),
+        // 01001110 LD C, (IY+d)
+        |z80, mem, _| z80.ld_c_mem_iyd(mem),
+        // 01001111 LD C, A
+        |z80, _, _| 4 + z80.ld_c_a(),
+        // 01010000 LD D, B
+        |z80, _, _| 4 + z80.ld_d_b(),
+        // 01010001 LD D, C
+        |z80, _, _| 4 + z80.ld_d_c(),
+        // 01010010 LD D, D
+        |z80, _, _| 4 + z80.ld_d_d(),
+        // 01010011 LD D, E
+        |z80, _, _| 4 + z80.ld_d_e(),
+        // 01010100 LD D, IYH
+        |z80, _, _| z80.ld_d_iyh(),
+        // 01010101 LD D, IYL
+        |z80, _, _| z80.ld_d_iyl(),
+        // 01010110 LD D, (IY+d)
+        |z80, mem, _| z80.ld_d_mem_iyd(mem),
+        // 01010111 LD D, A
+        |z80, _, _| 4 + z80.ld_d_a(),
+        // 01011000 LD E, B
+        |z80, _, _| 4 + z80.ld_e_b(),
+        // 01011001 LD E, C
+        |z80, _, _| 4 + z80.ld_e_c(),
+        // 01011010 LD E, D
+        |z80, _, _| 4 + z80.ld_e_d(),
+        // 01011011 LD E, E
+        |z80, _, _| 4 + z80.ld_e_e(),
+        // 01011100 LD E, IYH
+        |z80, _, _| z80.ld_e_iyh(),
+        // 01011101 LD E, IYL
+        |z80, _, _| z80.ld_e_iyl(),
+        // 01011110 LD E, (IY+d)
+        |z80, mem, _| z80.ld_e_mem_iyd(mem),
+        // 01011111 LD E, A
+        |z80, _, _| 4 + z80.ld_e_a(),
+        // 01100000 LD IYH, B
+        |z80, _, _| z80.ld_iyh_b(),
+        // 01100001 LD IYH, C
+        |z80, _, _| z80.ld_iyh_c(),
+        // 01100010 LD IYH, D
+        |z80, _, _| z80.ld_iyh_d(),
+        // 01100011 LD IYH, E
+        |z80, _, _| z80.ld_iyh_e(),
+        // 01100100 LD IYH, IYH
+        |z80, _, _| z80.ld_iyh_iyh(),
+        // 01100101 LD IYH, IYL
+        |z80, _, _| z80.ld_iyh_iyl(),
+        // 01100110 LD H, (IY+d)
+        |z80, mem, _| z80.ld_h_mem_iyd(mem),
+        // 01100111 LD IYH, A
+        |z80, _, _| z80.ld_iyh_a(),
+        // 01101000 LD IYL, B
+        |z80, _, _| z80.ld_iyl_b(),
+        // 01101001 LD IYL, C
+        |z80, _, _| z80.ld_iyl_c(),
+        // 01101010 LD IYL, D
+        |z80, _, _| z80.ld_iyl_d(),
+        // 01101011 LD IYL, E
+        |z80, _, _| z80.ld_iyl_e(),
+        // 01101100 LD IYL, IYH
+        |z80, _, _| z80.ld_iyl_iyh(),
+        // 01101101 LD IYL, IYL
+        |z80, _, _| z80.ld_iyl_iyl(),
+        // 01101110 LD L, (IY+d)
+        |z80, mem, _| z80.ld_l_mem_iyd(mem),
+        // 01101111 LD IYL, A
+        |z80, _, _| z80.ld_iyl_a(),
+        // 01110000 LD (IY+d), B
+        |z80, mem, _| z80.ld_mem_iyd_b(mem),
+        // 01110001 LD (IY+d), C
+        |z80, mem, _| z80.ld_mem_iyd_c(mem),
+        // 01110010 LD (IY+d), D
+        |z80, mem, _| z80.ld_mem_iyd_d(mem),
+        // 01110011 LD (IY+d), E
+        |z80, mem, _| z80.ld_mem_iyd_e(mem),
+        // 01110100 LD (IY+d), H
+        |z80, mem, _| z80.ld_mem_iyd_h(mem),
+        // 01110101 LD (IY+d), L
+        |z80, mem, _| z80.ld_mem_iyd_l(mem),
+        // 01110110 HALT
+        |z80, _, _| 4 + z80.halt(),
+        // 01110111 LD (IY+d), A
+        |z80, mem, _| z80.ld_mem_iyd_a(mem),
+        // 01111000 LD A, B
+        |z80, _, _| 4 + z80.ld_a_b(),
+        // 01111001 LD A, C
+        |z80, _, _| 4 + z80.ld_a_c(),
+        // 01111010 LD A, D
+        |z80, _, _| 4 + z80.ld_a_d(),
+        // 01111011 LD A, E
+        |z80, _, _| 4 + z80.ld_a_e(),
+        // 01111100 LD A, IYH
+        |z80, _, _| z80.ld_a_iyh(),
+        // 01111101 LD A, IYL
+        |z80, _, _| z80.ld_a_iyl(),
+        // 01111110 LD A, (IY+d)
+        |z80, mem, _| z80.ld_a_mem_iyd(mem),
+        // 01111111 LD A, A
+        |z80, _, _| 4 + z80.ld_a_a(),
+        // 10000000 ADD A, B
+        |z80, _, _| 4 + z80.add_a_b(),
+        // 10000001 ADD A, C
+        |z80, _, _| 4 + z80.add_a_c(),
+        // 10000010 ADD A, D
+        |z80, _, _| 4 + z80.add_a_d(),
+        // 10000011 ADD A, E
+        |z80, _, _| 4 + z80.add_a_e(),
+        // 10000100 ADD A, IYH
+        |z80, _, _| z80.add_a_iyh(),
+        // 10000101 ADD A, IYL
+        |z80, _, _| z80.add_a_iyl(),
+        // 10000110 ADD A, (IY+d)
+        |z80, mem, _| z80.add_a_mem_iyd(mem),
+        // 10000111 ADD A, A
+        |z80, _, _| 4 + z80.add_a_a(),
+        // 10001000 ADC A, B
+        |z80, _, _| 4 + z80.adc_a_b(),
+        // 10001001 ADC A, C
+        |z80, _, _| 4 + z80.adc_a_c(),
+        // 10001010 ADC A, D
+        |z80, _, _| 4 + z80.adc_a_d(),
+        // 10001011 ADC A, E
+        |z80, _, _| 4 + z80.adc_a_e(),
+        // 10001100 ADC A, IYH
+        |z80, _, _| z80.adc_a_iyh(),
+        // 10001101 ADC A, IYL
+        |z80, _, _| z80.adc_a_iyl(),
+        // 10001110 ADC A, (IY+d)
+        |z80, mem, _| z80.adc_a_mem_iyd(mem),
+        // 10001111 ADC A, A
+        |z80, _, _| 4 + z80.adc_a_a(),
+        // 10010000 SUB B
+        |z80, _, _| 4 + z80.sub_b(),
+        // 10010001 SUB C
+        |z80, _, _| 4 + z80.sub_c(),
+        // 10010010 SUB D
+        |z80, _, _| 4 + z80.sub_d(),
+        // 10010011 SUB E
+        |z80, _, _| 4 + z80.sub_e(),
+        // 10010100 SUB IYH
+        |z80, _, _| z80.sub_iyh(),
+        // 10010101 SUB IYL
+        |z80, _, _| z80.sub_iyl(),
+        // 10010110 SUB (IY+d)
+        |z80, mem, _| z80.sub_mem_iyd(mem),
+        // 10010111 SUB A
+        |z80, _, _| 4 + z80.sub_a(),
+        // 10011000 SBC A, B
+        |z80, _, _| 4 + z80.sbc_a_b(),
+        // 10011001 SBC A, C
+        |z80, _, _| 4 + z80.sbc_a_c(),
+        // 10011010 SBC A, D
+        |z80, _, _| 4 + z80.sbc_a_d(),
+        // 10011011 SBC A, E
+        |z80, _, _| 4 + z80.sbc_a_e(),
+        // 10011100 SBC A, IYH
+        |z80, _, _| z80.sbc_a_iyh(),
+        // 10011101 SBC A, IYL
+        |z80, _, _| z80.sbc_a_iyl(),
+        // 10011110 SBC A, (IY+d)
+        |z80, mem, _| z80.sbc_a_mem_iyd(mem),
+        // 10011111 SBC A, A
+        |z80, _, _| 4 + z80.sbc_a_a(),
+        // 10100000 AND B
+        |z80, _, _| 4 + z80.and_b(),
+        // 10100001 AND C
+        |z80, _, _| 4 + z80.and_c(),
+        // 10100010 AND D
+        |z80, _, _| 4 + z80.and_d(),
+        // 10100011 AND E
+        |z80, _, _| 4 + z80.and_e(),
+        // 10100100 AND IYH
+        |z80, _, _| z80.and_iyh(),
+        // 10100101 AND IYL
+        |z80, _, _| z80.and_iyl(),
+        // 10100110 AND (IY+d)
+        |z80, mem, _| z80.and_mem_iyd(mem),
+        // 10100111 AND A
+        |z80, _, _| 4 + z80.and_a(),
+        // 10101000 XOR B
+        |z80, _, _| 4 + z80.xor_b(),
+        // 10101001 XOR C
+        |z80, _, _| 4 + z80.xor_c(),
+        // 10101010 XOR D
+        |z80, _, _| 4 + z80.xor_d(),
+        // 10101011 XOR E
+        |z80, _, _| 4 + z80.xor_e(),
+        // 10101100 XOR IYH
+        |z80, _, _| z80.xor_iyh(),
+        // 10101101 XOR IYL
+        |z80, _, _| z80.xor_iyl(),
+        // 10101110 XOR (IY+d)
+        |z80, mem, _| z80.xor_mem_iyd(mem),
+        // 10101111 XOR A
+        |z80, _, _| 4 + z80.xor_a(),
+        // 10110000 OR B
+        |z80, _, _| 4 + z80.or_b(),
+        // 10110001 OR C
+        |z80, _, _| 4 + z80.or_c(),
+        // 10110010 OR D
+        |z80, _, _| 4 + z80.or_d(),
+        // 10110011 OR E
+        |z80, _, _| 4 + z80.or_e(),
+        // 10110100 OR IYH
+        |z80, _, _| z80.or_iyh(),
+        // 10110101 OR IYL
+        |z80, _, _| z80.or_iyl(),
+        // 10110110 OR (IY+d)
+        |z80, mem, _| z80.or_mem_iyd(mem),
+        // 10110111 OR A
+        |z80, _, _| 4 + z80.or_a(),
+        // 10111000 CP B
+        |z80, _, _| 4 + z80.cp_b(),
+        // 10111001 CP C
+        |z80, _, _| 4 + z80.cp_c(),
+        // 10111010 CP D
+        |z80, _, _| 4 + z80.cp_d(),
+        // 10111011 CP E
+        |z80, _, _| 4 + z80.cp_e(),
+        // 10111100 CP IYH
+        |z80, _, _| z80.cp_iyh(),
+        // 10111101 CP IYL
+        |z80, _, _| z80.cp_iyl(),
+        // 10111110 CP (IY+d)
+        |z80, mem, _| z80.cp_mem_iyd(mem),
+        // 10111111 CP A
+        |z80, _, _| 4 + z80.cp_a(),
+        // 11000000 RET NZ
+        |z80, mem, _| 4 + z80.ret_nz(mem),
+        // 11000001 POP BC
+        |z80, mem, _| 4 + z80.pop_qqbc(mem),
+        // 11000010 JP NZ, nn
+        |z80, mem, _| 4 + z80.jp_nz_nn(mem),
+        // 11000011 JP nn
+        |z80, mem, _| 4 + z80.jp_nn(mem),
+        // 11000100 CALL NZ, nn
+        |z80, mem, _| 4 + z80.call_nz_nn(mem),
+        // 11000101 PUSH BC
+        |z80, mem, _| 4 + z80.push_qqbc(mem),
+        // 11000110 ADD A, n
+        |z80, mem, _| 4 + z80.add_a_n(mem),
+        // 11000111 RST 00h
+        |z80, mem, _| 4 + z80.rst_00h(mem),
+        // 11001000 RET Z
+        |z80, mem, _| 4 + z80.ret_z(mem),
+        // 11001001 RET
+        |z80, mem, _| 4 + z80.ret(mem),
+        // 11001010 JP Z, nn
+        |z80, mem, _| 4 + z80.jp_z_nn(mem),
+        // 11001011 FDCB prefix
+        |z80, mem, _| z80.process_index_bit_instruction(z80.iy, mem),
+        // 11001100 CALL Z, nn
+        |z80, mem, _| 4 + z80.call_z_nn(mem),
+        // 11001101 CALL nn
+        |z80, mem, _| 4 + z80.call_nn(mem),
+        // 11001110 ADC A, n
+        |z80, mem, _| 4 + z80.adc_a_n(mem),
+        // 11001111 RST 08h
+        |z80, mem, _| 4 + z80.rst_08h(mem),
+        // 11010000 RET NC
+        |z80, mem, _| 4 + z80.ret_nc(mem),
+        // 11010001 POP DE
+        |z80, mem, _| 4 + z80.pop_qqde(mem),
+        // 11010010 JP NC, nn
+        |z80, mem, _| 4 + z80.jp_nc_nn(mem),
+        // 11010011 OUT (n), A
+        |z80, mem, io| 4 + z80.out_n_a(mem, io),
+        // 11010100 CALL NC, nn
+        |z80, mem, _| 4 + z80.call_nc_nn(mem),
+        // 11010101 PUSH DE
+        |z80, mem, _| 4 + z80.push_qqde(mem),
+        // 11010110 SUB n
+        |z80, mem, _| 4 + z80.sub_n(mem),
+        // 11010111 RST 10h
+        |z80, mem, _| 4 + z80.rst_10h(mem),
+        // 11011000 RET C
+        |z80, mem, _| 4 + z80.ret_c(mem),
+        // 11011001 EXX
+        |z80, _, _| 4 + z80.exx(),
+        // 11011010 JP C, nn
+        |z80, mem, _| 4 + z80.jp_c_nn(mem),
+        // 11011011 IN A, (n)
+        |z80, mem, io| 4 + z80.in_a_n(mem, io),
+        // 11011100 CALL C, nn
+        |z80, mem, _| 4 + z80.call_c_nn(mem),
+        // 11011101 DD prefix, which starts a new instruction
+        |z80, mem, io| 4u8.saturating_add(z80.process_ix_instruction(mem, io)),
+        // 11011110 SBC A, n
+        |z80, mem, _| 4 + z80.sbc_a_n(mem),
+        // 11011111 RST 18h
+        |z80, mem, _| 4 + z80.rst_18h(mem),
+        // 11100000 RET PO
+        |z80, mem, _| 4 + z80.ret_po(mem),
+        // 11100001 POP IY
+        |z80, mem, _| z80.pop_iy(mem),
+        // 11100010 JP PO, nn
+        |z80, mem, _| 4 + z80.jp_po_nn(mem),
+        // 11100011 EX (SP), IY
+        |z80, mem, _| z80.ex_mem_sp_iy(mem),
+        // 11100100 CALL PO, nn
+        |z80, mem, _| 4 + z80.call_po_nn(mem),
+        // 11100101 PUSH IY
+        |z80, mem, _| z80.push_iy(mem),
+        // 11100110 AND n
+        |z80, mem, _| 4 + z80.and_n(mem),
+        // 11100111 RST 20h
+        |z80, mem, _| 4 + z80.rst_20h(mem),
+        // 11101000 RET PE
+        |z80, mem, _| 4 + z80.ret_pe(mem),
+        // 11101001 JP (IY)
+        |z80, _, _| z80.jp_mem_iy(),
+        // 11101010 JP PE, nn
+        |z80, mem, _| 4 + z80.jp_pe_nn(mem),
+        // 11101011 EX DE, HL
+        |z80, _, _| 4 + z80.ex_de_hl(),
+        // 11101100 CALL PE, nn
+        |z80, mem, _| 4 + z80.call_pe_nn(mem),
+        // 11101101 ED prefix
+        |z80, mem, io| 4u8.saturating_add(z80.process_misc_instruction(mem, io)),
+        // 11101110 XOR n
+        |z80, mem, _| 4 + z80.xor_n(mem),
+        // 11101111 RST 28h
+        |z80, mem, _| 4 + z80.rst_28h(mem),
+        // 11110000 RET P
+        |z80, mem, _| 4 + z80.ret_p(mem),
+        // 11110001 POP AF
+        |z80, mem, _| 4 + z80.pop_qqaf(mem),
+        // 11110010 JP P, nn
+        |z80, mem, _| 4 + z80.jp_p_nn(mem),
+        // 11110011 DI
+        |z80, _, _| 4 + z80.di(),
+        // 11110100 CALL P, nn
+        |z80, mem, _| 4 + z80.call_p_nn(mem),
+        // 11110101 PUSH AF
+        |z80, mem, _| 4 + z80.push_qqaf(mem),
+        // 11110110 OR n
+        |z80, mem, _| 4 + z80.or_n(mem),
+        // 11110111 RST 30h
+        |z80, mem, _| 4 + z80.rst_30h(mem),
+        // 11111000 RET M
+        |z80, mem, _| 4 + z80.ret_m(mem),
+        // 11111001 LD SP, IY
+        |z80, mem, _| z80.ld_sp_iy(mem),
+        // 11111010 JP M, nn
+        |z80, mem, _| 4 + z80.jp_m_nn(mem),
+        // 11111011 EI
+        |z80, _, _| 4 + z80.ei(),
+        // 11111100 CALL M, nn
+        |z80, mem, _| 4 + z80.call_m_nn(mem),
+        // 11111101 FD prefix, which starts a new instruction
+        |z80, mem, io| 4u8.saturating_add(z80.process_iy_instruction(mem, io)),
+        // 11111110 CP n
+        |z80, mem, _| 4 + z80.cp_n(mem),
+        // 11111111 RST 38h
+        |z80, mem, _| 4 + z80.rst_38h(mem),
     ];
+}
 
 pub trait Z80Memory {
     fn read(&self, address: u16) -> u8;
     fn write(&mut self, address: u16, data: u8);
+
+    /// Reads a byte for the processor in the machine cycle starting at
+    /// `t_state` on its clock. Override this rather than `read` for memory
+    /// whose behaviour depends on timing.
+    fn read_at(&self, address: u16, _t_state: u64) -> u8 {
+        self.read(address)
+    }
+
+    /// Writes a byte for the processor in the machine cycle starting at
+    /// `t_state` on its clock.
+    fn write_at(&mut self, address: u16, data: u8, _t_state: u64) {
+        self.write(address, data);
+    }
+
+    /// Told about every machine cycle other than I/O before it starts at
+    /// `t_state`, when the processor runs in [`ExecutionMode::MachineCycle`].
+    /// Returns the number of wait states to hold the cycle off by.
+    fn machine_cycle(&self, _cycle: MachineCycle, _t_state: u64) -> u8 {
+        0
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The I/O port bus. Ports are addressed with the full 16 bits that the CPU
+/// places on the address bus, since some hardware, such as the Spectrum
+/// keyboard, decodes the high byte as well.
+pub trait Z80Io {
+    fn read_port(&mut self, port: u16) -> u8;
+    fn write_port(&mut self, port: u16, data: u8);
+
+    /// Reads a port for the processor in the I/O cycle starting at `t_state`
+    /// on its clock.
+    fn read_port_at(&mut self, port: u16, _t_state: u64) -> u8 {
+        self.read_port(port)
+    }
+
+    /// Writes a port for the processor in the I/O cycle starting at
+    /// `t_state` on its clock.
+    fn write_port_at(&mut self, port: u16, data: u8, _t_state: u64) {
+        self.write_port(port, data);
+    }
+
+    /// Told about every I/O cycle before it starts at `t_state`, when the
+    /// processor runs in [`ExecutionMode::MachineCycle`]. Returns the number
+    /// of wait states to hold the cycle off by.
+    fn machine_cycle(&mut self, _cycle: MachineCycle, _t_state: u64) -> u8 {
+        0
+    }
+}
+
+/// Forwards to the memory behind a reference, so that a reference to a bus
+/// can stand in for it where a sized bus is needed, such as when handing an
+/// unsized bus to an [`InvalidOpcodeHook`].
+impl<M: Z80Memory + ?Sized> Z80Memory for &mut M {
+    fn read(&self, address: u16) -> u8 {
+        (**self).read(address)
+    }
+
+    fn write(&mut self, address: u16, data: u8) {
+        (**self).write(address, data);
+    }
+
+    fn read_at(&self, address: u16, t_state: u64) -> u8 {
+        (**self).read_at(address, t_state)
+    }
+
+    fn write_at(&mut self, address: u16, data: u8, t_state: u64) {
+        (**self).write_at(address, data, t_state);
+    }
+
+    fn machine_cycle(&self, cycle: MachineCycle, t_state: u64) -> u8 {
+        (**self).machine_cycle(cycle, t_state)
+    }
+}
+
+/// Forwards to the I/O bus behind a reference, like the memory bus.
+impl<I: Z80Io + ?Sized> Z80Io for &mut I {
+    fn read_port(&mut self, port: u16) -> u8 {
+        (**self).read_port(port)
+    }
+
+    fn write_port(&mut self, port: u16, data: u8) {
+        (**self).write_port(port, data);
+    }
+
+    fn read_port_at(&mut self, port: u16, t_state: u64) -> u8 {
+        (**self).read_port_at(port, t_state)
+    }
+
+    fn write_port_at(&mut self, port: u16, data: u8, t_state: u64) {
+        (**self).write_port_at(port, data, t_state);
+    }
+
+    fn machine_cycle(&mut self, cycle: MachineCycle, t_state: u64) -> u8 {
+        (**self).machine_cycle(cycle, t_state)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct Register {
     value: u8,
+    /// Set by the helpers in `register_flags` when they write to the
+    /// register, which is how F records whether an instruction updated the
+    /// flags.
+    flags_written: bool,
+}
+
+impl PartialEq for Register {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
 }
 
+impl Eq for Register {}
+
 impl Register {
     pub fn new() -> Register {
-        Register { value: 0 }
+        Register::from(0)
     }
     pub fn from(value: u8) -> Register {
-        Register { value }
+        Register {
+            value,
+            flags_written: false,
+        }
     }
     pub fn inc(&mut self) {
         self.value = self.value.wrapping_add(1);
@@ -2024,6 +3302,128 @@ impl Register {
     }
 }
 
+/// The Z80 was second sourced by several manufacturers in both NMOS and CMOS,
+/// and the parts differ in a few undocumented behaviours: the X and Y flags of
+/// SCF and CCF, the value written by OUT (C), 0, and whether P/V is lost when
+/// an interrupt is accepted straight after LD A, I or LD A, R.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpuVariant {
+    /// Zilog NMOS, as fitted to the ZX Spectrum.
+    #[default]
+    ZilogNmos,
+    /// Zilog CMOS, the Z84C00.
+    ZilogCmos,
+    /// NEC NMOS, the μPD780C.
+    NecNmos,
+    /// Toshiba CMOS, the TMPZ84C00.
+    Toshiba,
+}
+
+impl CpuVariant {
+    pub fn is_cmos(self) -> bool {
+        matches!(self, CpuVariant::ZilogCmos | CpuVariant::Toshiba)
+    }
+}
+
+/// An opcode the processor does not define, such as ED 00.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpcodeError {
+    /// The address of the instruction.
+    pub program_counter: u16,
+    /// The bytes of the instruction, including any prefixes.
+    pub bytes: Vec<u8>,
+    pub prefix: Prefix,
+}
+
+impl fmt::Display for OpcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes: Vec<String> = self
+            .bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect();
+        write!(
+            f,
+            "invalid opcode {} at address {:#06X}",
+            bytes.join(" "),
+            self.program_counter
+        )
+    }
+}
+
+impl Error for OpcodeError {}
+
+/// Called for an invalid opcode under [`InvalidOpcodePolicy::Hook`], with the
+/// program counter already past the instruction. The hook can carry on from
+/// anywhere by setting the program counter and the other registers through
+/// their setters. Returns the number of T states the instruction took.
+pub type InvalidOpcodeHook = fn(&mut Z80, &mut dyn Z80Memory, &mut dyn Z80Io, &OpcodeError) -> u8;
+
+/// What to do with an opcode the processor does not define.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum InvalidOpcodePolicy {
+    /// Execute it as the hardware does, as a NOP.
+    #[default]
+    Nop,
+    /// Stop before the instruction, leaving the program counter on it, and
+    /// return an [`OpcodeError`] from [`Z80::step`].
+    Trap,
+    /// Hand it to a function, for example to emulate a ROM trap.
+    Hook(InvalidOpcodeHook),
+}
+
+/// How closely the processor models its bus activity.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Memory and I/O accesses are passed the T state they happen at, but
+    /// nothing else is seen of the bus. This is the fastest mode.
+    #[default]
+    Instruction,
+    /// Each instruction is run as its sequence of machine cycles, and the bus
+    /// is told about every cycle before it starts, down to the single T
+    /// states of internal operation. It can stretch any of them with wait
+    /// states, as contended memory does.
+    MachineCycle,
+}
+
+/// A machine cycle as seen on the bus, with the address the processor puts
+/// on the address bus for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MachineCycle {
+    /// An M1 cycle, reading an opcode and then refreshing memory. It takes
+    /// four T states.
+    OpcodeFetch(u16),
+    /// The M1 cycle that acknowledges a maskable interrupt, which reads the
+    /// data bus rather than memory. It takes six T states.
+    InterruptAcknowledge(u16),
+    /// A three T state memory read.
+    MemoryRead(u16),
+    /// A three T state memory write.
+    MemoryWrite(u16),
+    /// A four T state I/O read.
+    PortRead(u16),
+    /// A four T state I/O write.
+    PortWrite(u16),
+    /// A single T state of internal operation. The address bus keeps the
+    /// address of the cycle before, or IR after an opcode fetch.
+    Internal(u16),
+}
+
+impl MachineCycle {
+    /// The address on the address bus during the cycle.
+    pub fn address(&self) -> u16 {
+        match *self {
+            MachineCycle::OpcodeFetch(address)
+            | MachineCycle::InterruptAcknowledge(address)
+            | MachineCycle::MemoryRead(address)
+            | MachineCycle::MemoryWrite(address)
+            | MachineCycle::PortRead(address)
+            | MachineCycle::PortWrite(address)
+            | MachineCycle::Internal(address) => address,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Z80 {
     program_counter: u16,
@@ -2063,10 +3463,73 @@ pub struct Z80 {
 
     /// Interrupt enable flip flop 2
     iff2: bool,
+
+    /// Interrupt mode set by IM 0, IM 1 or IM 2
+    interrupt_mode: u8,
+
+    /// The byte on the data bus while the INT line is asserted
+    interrupt_request: Option<u8>,
+
+    /// Set when the NMI line has seen a falling edge that has not yet been
+    /// accepted
+    nmi_request: bool,
+
+    /// Set by EI so that no maskable interrupt is accepted until the
+    /// following instruction has executed
+    interrupt_delay: bool,
+
+    /// Set by HALT until an interrupt is accepted
+    halted: bool,
+
+    /// Set by LD A, I and LD A, R for the following instruction boundary
+    after_ld_a_ir: bool,
+
+    /// The flags latched by the last instruction if it updated F, otherwise
+    /// 0. It is only observable through the undocumented X and Y flags of SCF
+    /// and CCF.
+    q: u8,
+
+    /// The silicon being emulated
+    variant: CpuVariant,
+
+    /// Internal address register, also known as WZ. It is only observable
+    /// through the undocumented X and Y flags of BIT n, (HL), but is exposed
+    /// so that test suites and snapshots can check and restore it.
+    memptr: u16,
+
+    /// The address the instruction being executed started at
+    instruction_address: u16,
+
+    invalid_opcode_policy: InvalidOpcodePolicy,
+
+    /// Set when an invalid opcode is trapped, until it is returned by step
+    opcode_error: Option<OpcodeError>,
+
+    /// T states run past the end of the last run_for budget, which are
+    /// taken off the next one
+    overshoot: u64,
+
+    /// T states run since the processor was created
+    t_states: u64,
+
+    /// The value of t_states at the start of the current frame
+    frame_start: u64,
+
+    execution_mode: ExecutionMode,
+
+    /// The address last put on the address bus
+    address_bus: u16,
+
+    /// Wait states the bus has added to the current instruction
+    wait_states: u32,
 }
 
 impl Z80 {
     pub fn new() -> Z80 {
+        Z80::with_variant(CpuVariant::default())
+    }
+
+    pub fn with_variant(variant: CpuVariant) -> Z80 {
         Z80 {
             program_counter: 0,
             stack_pointer: 0,
@@ -2090,11 +3553,51 @@ impl Z80 {
             e_prime: Register::new(),
             h_prime: Register::new(),
             l_prime: Register::new(),
-            iff1: true,
-            iff2: true,
+            iff1: false,
+            iff2: false,
+            interrupt_mode: 0,
+            interrupt_request: None,
+            nmi_request: false,
+            interrupt_delay: false,
+            halted: false,
+            after_ld_a_ir: false,
+            q: 0,
+            variant,
+            memptr: 0,
+            instruction_address: 0,
+            invalid_opcode_policy: InvalidOpcodePolicy::default(),
+            opcode_error: None,
+            overshoot: 0,
+            t_states: 0,
+            frame_start: 0,
+            execution_mode: ExecutionMode::default(),
+            address_bus: 0,
+            wait_states: 0,
         }
     }
 
+    pub fn variant(&self) -> CpuVariant {
+        self.variant
+    }
+
+    pub fn iff1(&self) -> bool {
+        self.iff1
+    }
+
+    pub fn iff2(&self) -> bool {
+        self.iff2
+    }
+
+    pub fn interrupt_mode(&self) -> u8 {
+        self.interrupt_mode
+    }
+
+    /// Whether the processor is in the HALT state, executing NOPs until an
+    /// interrupt arrives.
+    pub fn is_halted(&self) -> bool {
+        self.halted
+    }
+
     fn load_register_pair(high: &mut Register, low: &mut Register, value: u16) {
         high.set_value((value >> 8) as u8);
         low.set_value(value as u8);
@@ -2192,186 +3695,523 @@ impl Z80 {
         Z80::read_register_pair(&self.h, &self.l)
     }
 
-    pub fn fetch_next_opcode(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let opcode = mem.read(self.program_counter);
+    pub fn program_counter(&self) -> u16 {
+        self.program_counter
+    }
+
+    pub fn set_program_counter(&mut self, value: u16) {
+        self.program_counter = value;
+    }
+
+    pub fn stack_pointer(&self) -> u16 {
+        self.stack_pointer
+    }
+
+    pub fn set_stack_pointer(&mut self, value: u16) {
+        self.stack_pointer = value;
+    }
+
+    pub fn ix(&self) -> u16 {
+        self.ix
+    }
+
+    pub fn set_ix(&mut self, value: u16) {
+        self.ix = value;
+    }
+
+    pub fn iy(&self) -> u16 {
+        self.iy
+    }
+
+    pub fn set_iy(&mut self, value: u16) {
+        self.iy = value;
+    }
+
+    pub fn i(&self) -> u8 {
+        self.i.value()
+    }
+
+    pub fn set_i(&mut self, value: u8) {
+        self.i.set_value(value);
+    }
+
+    pub fn r(&self) -> u8 {
+        self.r.value()
+    }
+
+    pub fn set_r(&mut self, value: u8) {
+        self.r.set_value(value);
+    }
+
+    /// The number of T states run since the processor was created. Memory
+    /// and I/O accesses are passed this clock as it stands at the start of
+    /// their machine cycle.
+    pub fn t_states(&self) -> u64 {
+        self.t_states
+    }
+
+    /// The number of T states since the start of the current frame.
+    pub fn frame_t_states(&self) -> u64 {
+        self.t_states - self.frame_start
+    }
+
+    /// Starts the next frame `length` T states after the start of the
+    /// current one. Anything run past the end of the current frame counts
+    /// towards the next.
+    pub fn end_frame(&mut self, length: u64) {
+        self.frame_start += length;
+    }
+
+    pub fn memptr(&self) -> u16 {
+        self.memptr
+    }
+
+    pub fn set_memptr(&mut self, value: u16) {
+        self.memptr = value;
+    }
+
+    /// Increments the seven bit memory refresh counter in R, leaving bit 7 as
+    /// it was last loaded by LD R, A.
+    fn refresh(&mut self) {
+        let r = self.r.value();
+        self.r.set_value((r & 0x80) | (r.wrapping_add(1) & 0x7F));
+    }
+
+    /// Fetches an opcode or prefix byte in an M1 cycle, which also refreshes
+    /// memory and so increments R.
+    pub fn fetch_next_opcode(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let opcode = self.opcode_fetch_cycle(mem, self.program_counter);
         self.program_counter = self.program_counter.wrapping_add(1);
         opcode
     }
 
-    pub fn process_next_instruction(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let mut t_states: u8 = 0;
+    /// An M1 cycle, which reads the opcode in its first two T states and
+    /// refreshes memory in the other two.
+    fn opcode_fetch_cycle(&mut self, mem: &(impl Z80Memory + ?Sized), address: u16) -> u8 {
+        self.begin_machine_cycle(mem, MachineCycle::OpcodeFetch(address));
+        let opcode = mem.read_at(address, self.t_states);
+        self.refresh_cycle();
+        self.t_states += 4;
+        opcode
+    }
 
-        // Spend 4 ticks fetching the next instruction
-        let opcode = self.fetch_next_opcode(mem);
-        t_states += 4;
+    /// Puts the refresh address from I and R on the address bus, where it
+    /// stays until the next machine cycle, and increments R.
+    fn refresh_cycle(&mut self) {
+        self.address_bus = u16::from_be_bytes([self.i.value(), self.r.value()]);
+        self.refresh();
+    }
 
-        let opcode_function = MAIN_FUNCTIONS[opcode as usize];
-        t_states += opcode_function(self, mem);
+    /// Fetches an operand or displacement byte. These are ordinary memory
+    /// reads rather than M1 cycles, so R is left alone.
+    fn fetch_next_byte(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let byte = self.read_memory(mem, self.program_counter);
+        self.program_counter = self.program_counter.wrapping_add(1);
+        byte
+    }
 
-        return t_states;
+    /// A three T state memory read cycle.
+    fn read_memory(&mut self, mem: &(impl Z80Memory + ?Sized), address: u16) -> u8 {
+        self.begin_machine_cycle(mem, MachineCycle::MemoryRead(address));
+        let data = mem.read_at(address, self.t_states);
+        self.t_states += 3;
+        data
     }
 
-    // General-Purpose Arithmetic and CPU Control Groups
+    /// A three T state memory write cycle.
+    fn write_memory(&mut self, mem: &mut (impl Z80Memory + ?Sized), address: u16, data: u8) {
+        self.begin_machine_cycle(mem, MachineCycle::MemoryWrite(address));
+        mem.write_at(address, data, self.t_states);
+        self.t_states += 3;
+    }
 
-    /// ### Operation
-    ///
-    /// @
-    ///
-    /// ### Op Code
-    ///
-    /// DAA: `0 0 1 0 0 1 1 1` (0x27)
-    ///
-    /// ### Operands
-    ///
-    /// None.
-    ///
-    /// ### Description
-    ///
-    /// This instruction conditionally adjusts the Accumulator for BCD addition
-    /// and subtraction operations. For addition (ADD, ADC, INC) or subtraction
-    /// (SUB, SBC, DEC, NEG), the following table indicates the operation being
-    /// performed:
-    ///
-    /// | Operation | C Before DAA | Hex Value In Upper Digit (Bits 7–4) | H Before DAA | Hex Value In Lower Digit (Bits 3–0) | Number Added To Byte | C After DAA |
-    /// | --------- | ------------ | ----------------------------------- | ------------ | ----------------------------------- | -------------------- | ----------- |
-    /// |           | 0            | 9–0                                 | 0            | 0–9                                 | 00                   | 0           |
-    /// |           | 0            | 0–8                                 | 0            | A–F                                 | 06                   | 0           |
-    /// |           | 0            | 0–9                                 | 1            | 0–3                                 | 06                   | 0           |
-    /// | ADD       | 0            | A–F                                 | 0            | 0–9                                 | 60                   | 1           |
-    /// | ADC       | 0            | 9–F                                 | 0            | A–F                                 | 66                   | 1           |
-    /// | INC       | 0            | A–F                                 | 1            | 0–3                                 | 66                   | 1           |
-    /// |           | 1            | 0–2                                 | 0            | 0–9                                 | 60                   | 1           |
-    /// |           | 1            | 0–2                                 | 0            | A–F                                 | 66                   | 1           |
-    /// |           | 1            | 0–3                                 | 1            | 0–3                                 | 66                   | 1           |
-    /// | SUB       | 0            | 0–9                                 | 0            | 0–9                                 | 00                   | 0           |
-    /// | SBC       | 0            | 0–8                                 | 1            | 6–F                                 | FA                   | 0           |
-    /// | DEC       | 1            | 7–F                                 | 0            | 0–9                                 | A0                   | 1           |
-    /// | NEG       | 1            | 6–7                                 | 1            | 6–F                                 | 9A                   | 1           |
-    ///
-    /// ### Condition Bits Affected
-    ///
-    /// S is set if most-significant bit of the
-    /// Accumulator is 1 after an operation; otherwise, it is reset.
-    ///
-    /// Z is set if the Accumulator is 0 after an operation; otherwise, it is
-    /// reset.
-    ///
-    /// H: see the DAA instruction table on the previous page.
-    ///
-    /// P/V is set if the Accumulator is at even parity after an operation;
-    /// otherwise, it is reset.
+    /// A four T state I/O read cycle, which includes the wait state the
+    /// processor always inserts.
+    fn read_io(&mut self, io: &mut (impl Z80Io + ?Sized), port: u16) -> u8 {
+        self.begin_io_cycle(io, MachineCycle::PortRead(port));
+        let data = io.read_port_at(port, self.t_states);
+        self.t_states += 4;
+        data
+    }
+
+    /// A four T state I/O write cycle.
+    fn write_io(&mut self, io: &mut (impl Z80Io + ?Sized), port: u16, data: u8) {
+        self.begin_io_cycle(io, MachineCycle::PortWrite(port));
+        io.write_port_at(port, data, self.t_states);
+        self.t_states += 4;
+    }
+
+    /// T states the processor spends on internal operations, which either
+    /// make up machine cycles of their own or extend the one before. Memory
+    /// still sees the address bus during them.
+    fn internal_cycles(&mut self, mem: &(impl Z80Memory + ?Sized), t_states: u8) {
+        if self.execution_mode == ExecutionMode::Instruction {
+            self.t_states += t_states as u64;
+            return;
+        }
+
+        for _ in 0..t_states {
+            self.begin_machine_cycle(mem, MachineCycle::Internal(self.address_bus));
+            self.t_states += 1;
+        }
+    }
+
+    /// Tells memory about a machine cycle that is about to start and adds
+    /// any wait states it asks for.
+    fn begin_machine_cycle(&mut self, mem: &(impl Z80Memory + ?Sized), cycle: MachineCycle) {
+        if self.execution_mode == ExecutionMode::MachineCycle {
+            let wait_states = mem.machine_cycle(cycle, self.t_states);
+            self.add_wait_states(wait_states);
+        }
+        self.address_bus = cycle.address();
+    }
+
+    /// Tells the I/O bus about an I/O cycle that is about to start and adds
+    /// any wait states it asks for.
+    fn begin_io_cycle(&mut self, io: &mut (impl Z80Io + ?Sized), cycle: MachineCycle) {
+        if self.execution_mode == ExecutionMode::MachineCycle {
+            let wait_states = io.machine_cycle(cycle, self.t_states);
+            self.add_wait_states(wait_states);
+        }
+        self.address_bus = cycle.address();
+    }
+
+    fn add_wait_states(&mut self, wait_states: u8) {
+        self.t_states += wait_states as u64;
+        self.wait_states += wait_states as u32;
+    }
+
+    /// Fetches a little-endian 16-bit operand, low byte first.
+    fn fetch_next_address(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u16 {
+        let low = self.fetch_next_byte(mem);
+        let high = self.fetch_next_byte(mem);
+        ((high as u16) << 8) | low as u16
+    }
+
+    /// Fetches the displacement byte of an (IX+d) or (IY+d) operand and
+    /// returns the address it points to, which is also left in MEMPTR. The
+    /// processor spends five T states adding the displacement.
+    fn fetch_index_d_address(&mut self, index: u16, mem: &(impl Z80Memory + ?Sized)) -> u16 {
+        let d = self.fetch_next_byte(mem);
+        self.internal_cycles(mem, 5);
+        self.index_d_address(index, d)
+    }
+
+    /// Computes the address an (IX+d) or (IY+d) operand with displacement `d`
+    /// points to, leaving it in MEMPTR.
+    fn index_d_address(&mut self, index: u16, d: u8) -> u16 {
+        let displacement = i8::from_ne_bytes(d.to_ne_bytes());
+        self.memptr = index.wrapping_add_signed(displacement as i16);
+        self.memptr
+    }
+
+    /// Fetches, decodes and executes the instruction at the program counter,
+    /// returning the number of T states it took. Each instruction handler
+    /// accounts for all of its own T states, including the opcode fetch.
+    /// The clock advances by the same amount a machine cycle at a time, so
+    /// every memory and I/O access is passed the T state it happens at. Any
+    /// wait states added in [`ExecutionMode::MachineCycle`] are included. If
+    /// the total is past 255 T states, as it can be with wait states or a
+    /// long run of index prefixes, then 255 is returned, while the clock still
+    /// advances by the full amount.
     ///
-    /// N is not affected.
+    /// The processor is compiled for the types of the buses, so that their
+    /// accesses can be inlined. Buses only known at run time can be passed as
+    /// a `&mut dyn Z80Memory` and a `&mut dyn Z80Io`, and are then accessed
+    /// through their vtables.
     ///
-    /// C: see the DAA instruction table on the previous page.
+    /// When an interrupt is accepted at this instruction boundary the
+    /// acknowledge cycle is performed instead, and its T states returned.
     ///
-    /// ### Example
+    /// Afterwards Q holds F if the instruction updated the flags, or 0 if it
+    /// did not.
     ///
-    /// An addition operation is performed between 15 (BCD) and 27 (BCD); simple
-    /// decimal arithmetic provides the following result:
-    /// ```
-    ///   15
-    /// + 27
-    ///   --
-    ///   42
-    /// ```
+    /// An invalid opcode trapped by [`InvalidOpcodePolicy::Trap`] is rolled
+    /// back, leaving the program counter on it and R and the clock as they
+    /// were, and 0 is returned. It is then trapped the same way on every call,
+    /// although the buses have seen its opcode fetches each time. Use
+    /// [`Z80::step`] to find out about it.
+    pub fn process_next_instruction<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        self.f.flags_written = false;
+        self.opcode_error = None;
+        self.wait_states = 0;
+
+        let start = self.t_states;
+        let r = self.r.value();
+        let reported = self.execute_next_instruction(mem, io);
+        if self.opcode_error.is_some() {
+            self.r.set_value(r);
+            self.t_states = start;
+        }
+        let t_states = self.t_states - start;
+        // A long run of index prefixes can take more T states than the
+        // handlers can report, in which case they report 255
+        debug_assert!(
+            self.opcode_error.is_some()
+                || reported == u8::MAX
+                || reported as u64 + self.wait_states as u64 == t_states,
+            "clock out of step with the instruction at {:04X}h",
+            self.instruction_address
+        );
+
+        self.q = if self.f.flags_written {
+            self.f.value()
+        } else {
+            0
+        };
+
+        u8::try_from(t_states).unwrap_or(u8::MAX)
+    }
+
+    /// Processes the next instruction like
+    /// [`Z80::process_next_instruction`], but returns an error for an invalid
+    /// opcode trapped by [`InvalidOpcodePolicy::Trap`].
+    pub fn step<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> Result<u8, OpcodeError> {
+        let t_states = self.process_next_instruction(mem, io);
+
+        match self.opcode_error.take() {
+            Some(error) => Err(error),
+            None => Ok(t_states),
+        }
+    }
+
+    /// Runs instructions for `budget` T states, returning how many were
+    /// actually run. The last instruction usually finishes past the end of the
+    /// budget, and the overshoot is taken off the budget of the next call, so
+    /// that consecutive calls stay in step with the clock. If the overshoot
+    /// is larger than the budget, no instruction is run.
     ///
-    /// The binary representations are added in the Accumulator according to
-    /// standard binary arithmetic, as follows:
-    /// ```
-    ///   0001 0101
-    /// + 0010 0111
-    ///   ---- ----
-    ///   0011 1100 = 3C
-    /// ```
+    /// An invalid opcode trapped by [`InvalidOpcodePolicy::Trap`] ends the
+    /// run before the budget is used up, so nothing is carried over to the
+    /// next call. The T states run before it can be found from
+    /// [`Z80::t_states`].
+    pub fn run_for<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        budget: u64,
+        mem: &mut M,
+        io: &mut I,
+    ) -> Result<u64, OpcodeError> {
+        let carried = self.overshoot;
+        let mut elapsed = carried;
+        while elapsed < budget {
+            let start = self.t_states;
+            if let Err(error) = self.step(mem, io) {
+                self.overshoot = 0;
+                return Err(error);
+            }
+            elapsed += self.t_states - start;
+        }
+        self.overshoot = elapsed - budget;
+
+        Ok(elapsed.saturating_sub(carried))
+    }
+
+    /// Runs instructions until `predicate` holds at an instruction boundary,
+    /// returning the number of T states they took. The predicate is checked
+    /// before the first instruction, so nothing is run if it already holds.
     ///
-    /// The sum is ambiguous. The DAA instruction adjusts this result so that
-    /// the correct BCD representation is obtained, as follows:
-    /// ```
-    ///   0011 1100
-    /// + 0000 0110
-    ///   ---- ----
-    ///   0100 0010 = 42
-    /// ```
-    fn daa(&mut self) -> u8 {
-        let a_high = self.a.value() & 0xF0;
-        let a_low = self.a.value() & 0x0F;
-        let diff: u8 = match (c_flag(&self.f), a_high, h_flag(&self.f), a_low) {
-            (false, a_high, false, a_low) if a_high <= 0x09 && a_low <= 0x09 => 0x00,
-            (false, a_high, true, a_low) if a_high <= 0x09 && a_low <= 0x09 => 0x06,
-            (false, a_high, _, a_low) if a_high <= 0x08 && a_low >= 0x0A => 0x06,
-            (false, a_high, false, a_low) if a_high >= 0x0A && a_low <= 0x09 => 0x60,
-            (true, _, false, a_low) if a_low <= 0x09 => 0x60,
-            (true, _, true, a_low) if a_low <= 0x09 => 0x66,
-            (true, _, _, a_low) if a_low >= 0x0A => 0x66,
-            (false, a_high, _, a_low) if a_high >= 0x09 && a_low >= 0x0A => 0x66,
-            (false, a_high, true, a_low) if a_high >= 0x0A && a_low <= 0x09 => 0x66,
-            _ => 0x00,
-        };
+    /// An invalid opcode trapped by [`InvalidOpcodePolicy::Trap`] ends the
+    /// run with its error, and the T states run before it can be found from
+    /// [`Z80::t_states`].
+    pub fn run_until<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+        mut predicate: impl FnMut(&Z80) -> bool,
+    ) -> Result<u64, OpcodeError> {
+        let mut elapsed = 0;
+        while !predicate(self) {
+            let start = self.t_states;
+            self.step(mem, io)?;
+            elapsed += self.t_states - start;
+        }
 
-        let new_c_flag: bool = match (c_flag(&self.f), a_high, a_low) {
-            (false, a_high, a_low) if a_high <= 0x09 && a_low <= 0x09 => false,
-            (false, a_high, a_low) if a_high <= 0x08 && a_low >= 0x0A => false,
-            (false, a_high, a_low) if a_high >= 0x09 && a_low >= 0x0A => true,
-            (false, a_high, a_low) if a_high >= 0x0A && a_low <= 0x09 => true,
-            (true, _, _) => true,
-            _ => c_flag(&self.f),
-        };
+        Ok(elapsed)
+    }
 
-        let new_h_flag: bool = match (n_flag(&self.f), h_flag(&self.f), a_low) {
-            (false, _, a_low) if a_low <= 0x09 => false,
-            (false, _, a_low) if a_low >= 0x0A => true,
-            (true, false, _) => false,
-            (true, true, a_low) if a_low >= 0x06 => false,
-            (true, true, a_low) if a_low <= 0x05 => true,
-            _ => h_flag(&self.f),
-        };
+    pub fn execution_mode(&self) -> ExecutionMode {
+        self.execution_mode
+    }
 
-        self.a.add(diff);
+    pub fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.execution_mode = mode;
+    }
 
-        if new_c_flag {
-            set_c_flag(&mut self.f);
-        } else {
-            unset_c_flag(&mut self.f);
+    pub fn invalid_opcode_policy(&self) -> InvalidOpcodePolicy {
+        self.invalid_opcode_policy
+    }
+
+    pub fn set_invalid_opcode_policy(&mut self, policy: InvalidOpcodePolicy) {
+        self.invalid_opcode_policy = policy;
+    }
+
+    fn execute_next_instruction<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        if let Some(t_states) = self.accept_interrupt(mem, io) {
+            return t_states;
         }
 
-        if new_h_flag {
-            set_h_flag(&mut self.f);
-        } else {
-            unset_h_flag(&mut self.f);
+        if self.halted {
+            // While halted the processor executes NOPs without advancing the
+            // program counter, to keep the memory refresh going.
+            self.opcode_fetch_cycle(mem, self.program_counter);
+
+            // T states
+            return 4;
         }
 
-        // SF, YF, XF are copies of bit 7,5,3 of the result respectively;
-        let new_f = (self.f.value() & 0b10101011u8) | (self.a.value() & 0b01010100u8);
-        self.f.set_value(new_f);
+        self.instruction_address = self.program_counter;
+        let opcode = self.fetch_next_opcode(mem);
+
+        let opcode_function = Opcodes::<M, I>::MAIN_FUNCTIONS[opcode as usize];
+        opcode_function(self, mem, io)
+    }
+
+    /// Fetches and executes the CB prefixed instruction that follows the
+    /// prefix byte.
+    fn process_bit_instruction<M: Z80Memory + ?Sized>(&mut self, mem: &mut M) -> u8 {
+        let opcode = self.fetch_next_opcode(mem);
+
+        let opcode_function = BitOpcodes::<M>::BIT_INSTRUCTIONS[opcode as usize];
+        opcode_function(self, mem)
+    }
+
+    /// Fetches and executes the ED prefixed instruction that follows the
+    /// prefix byte.
+    fn process_misc_instruction<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        let opcode = self.fetch_next_opcode(mem);
+
+        let opcode_function = Opcodes::<M, I>::MISC_INSTRUCTIONS[opcode as usize];
+        opcode_function(self, mem, io)
+    }
+
+    /// Fetches and executes the DD prefixed instruction that follows the
+    /// prefix byte, which uses IX in place of HL.
+    ///
+    /// When a DD or FD prefix is followed by another DD or FD prefix, only the
+    /// last one has an effect. The earlier ones behave as 4 T state NOPs, but
+    /// no interrupt is accepted after a prefix, so the whole run of prefixes
+    /// is executed as part of the instruction that ends it.
+    fn process_ix_instruction<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        let opcode = self.fetch_next_opcode(mem);
+
+        let opcode_function = Opcodes::<M, I>::IX_FUNCTIONS[opcode as usize];
+        opcode_function(self, mem, io)
+    }
+
+    /// Fetches and executes the FD prefixed instruction that follows the
+    /// prefix byte, which uses IY in place of HL.
+    fn process_iy_instruction<M: Z80Memory + ?Sized, I: Z80Io + ?Sized>(
+        &mut self,
+        mem: &mut M,
+        io: &mut I,
+    ) -> u8 {
+        let opcode = self.fetch_next_opcode(mem);
+
+        let opcode_function = Opcodes::<M, I>::IY_FUNCTIONS[opcode as usize];
+        opcode_function(self, mem, io)
+    }
 
-        // ZF is set according to the result and NF is always unchanged.
-        if self.a.value() == 0 {
-            set_z_flag(&mut self.f);
+    /// Fetches the displacement and opcode of a DDCB or FDCB prefixed
+    /// instruction, computes the indexed address into MEMPTR and executes the
+    /// instruction. Neither byte is read in an M1 cycle, so only the two
+    /// prefixes increment R.
+    fn process_index_bit_instruction<M: Z80Memory + ?Sized>(
+        &mut self,
+        index: u16,
+        mem: &mut M,
+    ) -> u8 {
+        let d = self.fetch_next_byte(mem);
+        let opcode = self.fetch_next_byte(mem);
+        self.internal_cycles(mem, 2);
+        self.index_d_address(index, d);
+
+        let opcode_function = BitOpcodes::<M>::INDEX_BIT_INSTRUCTIONS[opcode as usize];
+        opcode_function(self, mem)
+    }
+
+    /// Handles the ED prefixed opcodes that do not decode to an instruction,
+    /// according to the invalid opcode policy.
+    fn invalid_misc_opcode(
+        &mut self,
+        mut mem: &mut (impl Z80Memory + ?Sized),
+        mut io: &mut (impl Z80Io + ?Sized),
+    ) -> u8 {
+        if let InvalidOpcodePolicy::Nop = self.invalid_opcode_policy {
+            return Z80::ed_nop();
         }
 
-        // T states
-        4
+        let length = self.program_counter.wrapping_sub(self.instruction_address);
+        let bytes: Vec<u8> = (0..length)
+            .map(|offset| mem.read(self.instruction_address.wrapping_add(offset)))
+            .collect();
+        let error = OpcodeError {
+            program_counter: self.instruction_address,
+            bytes,
+            prefix: Prefix::Ed,
+        };
+
+        match self.invalid_opcode_policy {
+            InvalidOpcodePolicy::Hook(hook) => {
+                // The ED prefix and opcode were fetched in two M1 cycles, which
+                // the clock gives back in favour of the T states reported
+                // instead
+                let fetched = 8;
+                // The buses may be unsized, so the hook is handed the
+                // references to them, which are buses themselves
+                let t_states = hook(self, &mut mem, &mut io, &error);
+                self.t_states = self.t_states - fetched + t_states as u64;
+                t_states
+            }
+            _ => {
+                self.program_counter = self.instruction_address;
+                self.opcode_error = Some(error);
+
+                // T states
+                0
+            }
+        }
     }
+}
 
-    /// Hex: 00
-    /// Op Code: NOP
-    /// T-States: 4
-    fn nop() -> u8 {
-        // T states
-        4
+impl Default for Z80 {
+    fn default() -> Self {
+        Z80::new()
     }
 }
 
+#[cfg(test)]
 mod tests {
+    use std::{cell::RefCell, collections::VecDeque, ops::Range};
+
     use super::*;
+    use crate::z80::register_flags::{x_flag, y_flag, z_flag};
 
     pub struct Ram<'a> {
         bytes: &'a mut [u8],
     }
 
     impl<'a> Ram<'a> {
-        pub fn new(bytes: &'a mut [u8]) -> Ram {
+        pub fn new(bytes: &'a mut [u8]) -> Ram<'a> {
             Ram { bytes }
         }
     }
@@ -2386,11 +4226,98 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_fetch_next_opcode() {
-        let mut bytes = [0x00, 0x01, 0x02];
+    /// Answers port reads with the given bytes in turn, then with FFh, and
+    /// records every access with its full 16-bit port address.
+    pub struct Ports {
+        input: VecDeque<u8>,
+        pub reads: Vec<u16>,
+        pub writes: Vec<(u16, u8)>,
+    }
 
-        let ram = Ram::new(&mut bytes);
+    impl Ports {
+        pub fn new(input: &[u8]) -> Ports {
+            Ports {
+                input: input.iter().copied().collect(),
+                reads: Vec::new(),
+                writes: Vec::new(),
+            }
+        }
+    }
+
+    impl Z80Io for Ports {
+        fn read_port(&mut self, port: u16) -> u8 {
+            self.reads.push(port);
+            self.input.pop_front().unwrap_or(0xFF)
+        }
+
+        fn write_port(&mut self, port: u16, data: u8) {
+            self.writes.push((port, data));
+        }
+    }
+
+    #[test]
+    fn test_refresh_keeps_bit_7() {
+        let z80 = &mut Z80::new();
+        z80.r.set_value(0xFF);
+
+        z80.refresh();
+
+        assert_eq!(0x80, z80.r.value());
+    }
+
+    #[test]
+    fn test_refresh_on_each_m1() {
+        let mut bytes = [
+            0x00, // NOP
+            0x3E, 0x12, // LD A, 12h
+            0xCB, 0x47, // BIT 0, A
+            0xED, 0x44, // NEG
+            0xDD, 0x21, 0x00, 0x00, // LD IX, 0000h
+            0xDD, 0xCB, 0x00, 0x46, // BIT 0, (IX+0)
+            0xDD, 0xDD, 0x23, // INC IX
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.r.set_value(0x80);
+
+        let expected = [0x81, 0x82, 0x84, 0x86, 0x88, 0x8A, 0x8D];
+        for r in expected {
+            z80.process_next_instruction(ram, io);
+            assert_eq!(r, z80.r.value());
+        }
+    }
+
+    #[test]
+    fn test_memptr_shows_in_bit_mem_hl_flags() {
+        let mut bytes = [0x00; 0x2800];
+        bytes[0..5].copy_from_slice(&[
+            0x3A, 0xFF, 0x27, // LD A, (27FFh)
+            0xCB, 0x46, // BIT 0, (HL)
+        ]);
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        z80.process_next_instruction(ram, io);
+        assert_eq!(0x2800, z80.memptr());
+
+        z80.process_next_instruction(ram, io);
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+
+        z80.set_memptr(0x0000);
+        z80.program_counter = 3;
+        z80.process_next_instruction(ram, io);
+        assert_eq!(false, y_flag(&z80.f));
+        assert_eq!(false, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_fetch_next_opcode() {
+        let mut bytes = [0x00, 0x01, 0x02];
+
+        let ram = Ram::new(&mut bytes);
 
         let mut z80 = Z80::new();
 
@@ -2412,20 +4339,796 @@ mod tests {
     #[test]
     fn test_process_next_instruction() {
         let mut bytes = [
-            // 0x26, // LD H, n
-            // Until all instructions are populated, the opcode array will have
-            // LD H, n at the wrong location.
-            14, // LD H, n
+            0x26, // LD H, n
             0xDD,
         ];
 
         let ram = &mut Ram::new(&mut bytes);
 
+        let io = &mut Ports::new(&[]);
+
         let mut z80 = Z80::new();
 
-        let t_states = z80.process_next_instruction(ram);
+        let t_states = z80.process_next_instruction(ram, io);
 
-        assert_eq!(11, t_states);
+        assert_eq!(7, t_states);
         assert_eq!(bytes[1], z80.h.value());
     }
+
+    #[test]
+    fn test_main_functions_decode_by_opcode() {
+        let mut bytes = [0x00; 0x20];
+        bytes[..10].copy_from_slice(&[
+            0x3E, 0x12, // LD A, n
+            0x47, // LD B, A
+            0x21, 0x14, 0x00, // LD HL, nn
+            0x36, 0x56, // LD (HL), n
+            0x7E, // LD A, (HL)
+            0xF9, // LD SP, HL
+        ]);
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(7, z80.process_next_instruction(ram, io));
+        assert_eq!(0x12, z80.a.value());
+
+        assert_eq!(4, z80.process_next_instruction(ram, io));
+        assert_eq!(0x12, z80.b.value());
+
+        assert_eq!(10, z80.process_next_instruction(ram, io));
+        assert_eq!(0x0014, z80.hl());
+
+        assert_eq!(10, z80.process_next_instruction(ram, io));
+        assert_eq!(7, z80.process_next_instruction(ram, io));
+        assert_eq!(0x56, z80.a.value());
+
+        assert_eq!(6, z80.process_next_instruction(ram, io));
+        assert_eq!(0x0014, z80.stack_pointer);
+
+        assert_eq!(10, z80.program_counter);
+    }
+
+    #[test]
+    fn test_process_bit_instruction() {
+        let mut bytes = [
+            0xCB, 0x06, // RLC (HL)
+            0xCB, 0x7E, // BIT 7, (HL)
+            0xCB, 0xF8, // SET 7, B
+            0b10000001,
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0006);
+
+        assert_eq!(15, z80.process_next_instruction(ram, io));
+        assert_eq!(0b00000011, ram.read(6));
+
+        assert_eq!(12, z80.process_next_instruction(ram, io));
+        assert_eq!(true, z_flag(&z80.f));
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(0b10000000, z80.b.value());
+
+        assert_eq!(6, z80.program_counter);
+    }
+
+    #[test]
+    fn test_process_misc_instruction() {
+        let mut bytes = [
+            0xED, 0x5E, // IM 2
+            0xED, 0x44, // NEG
+            0xED, 0x00, // NOP (undocumented)
+            0xED, 0xB0, // LDIR
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0x01);
+        z80.set_bc(0x0001);
+        z80.set_hl(0x0000);
+        z80.set_de(0x0001);
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(2, z80.interrupt_mode());
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(0xFF, z80.a.value());
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(6, z80.program_counter);
+
+        assert_eq!(16, z80.process_next_instruction(ram, io));
+        assert_eq!(0xED, ram.read(1));
+        assert_eq!(8, z80.program_counter);
+    }
+
+    #[test]
+    fn test_process_ix_instruction() {
+        let mut bytes = [0x00; 0x20];
+        bytes[..17].copy_from_slice(&[
+            0xDD, 0x21, 0x10, 0x00, // LD IX, nn
+            0xDD, 0x26, 0x12, // LD IXH, n
+            0xDD, 0x45, // LD B, IXL
+            0xDD, 0x36, 0xFF, 0x34, // LD (IX-1), n
+            0xDD, 0x41, // LD B, C
+            0xDD, 0xED, // LD A, I
+        ]);
+        bytes[17] = 0x57;
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.c.set_value(0x56);
+        z80.i.set_value(0x78);
+
+        assert_eq!(14, z80.process_next_instruction(ram, io));
+        assert_eq!(0x0010, z80.ix);
+
+        assert_eq!(11, z80.process_next_instruction(ram, io));
+        assert_eq!(0x1210, z80.ix);
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(0x10, z80.b.value());
+
+        z80.ix = 0x0019;
+        assert_eq!(19, z80.process_next_instruction(ram, io));
+        assert_eq!(0x34, ram.read(0x0018));
+
+        assert_eq!(8, z80.process_next_instruction(ram, io));
+        assert_eq!(0x56, z80.b.value());
+
+        assert_eq!(13, z80.process_next_instruction(ram, io));
+        assert_eq!(0x78, z80.a.value());
+        assert_eq!(18, z80.program_counter);
+    }
+
+    #[test]
+    fn test_process_index_instruction_repeated_prefixes() {
+        let mut bytes = [
+            0xDD, 0xFD, 0xDD, 0xFD, 0x21, 0x34, 0x12, // LD IY, nn
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(26, z80.process_next_instruction(ram, io));
+        assert_eq!(0x1234, z80.iy);
+        assert_eq!(0x0000, z80.ix);
+        assert_eq!(7, z80.program_counter);
+        assert_eq!(5, z80.r.value());
+    }
+
+    #[test]
+    fn test_process_index_instruction_long_run_of_prefixes() {
+        let mut bytes = [0xDD; 0x100];
+        bytes[70] = 0x00; // NOP
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(255, z80.process_next_instruction(ram, io));
+        assert_eq!(284, z80.t_states());
+        assert_eq!(71, z80.program_counter);
+    }
+
+    /// Memory that reads back differently at a given T state, as memory
+    /// behind a timing dependent bus can.
+    struct TimedMemory {
+        bytes: Vec<u8>,
+    }
+
+    impl Z80Memory for TimedMemory {
+        fn read(&self, _address: u16) -> u8 {
+            0xDD
+        }
+
+        fn write(&mut self, address: u16, data: u8) {
+            self.bytes[address as usize] = data;
+        }
+
+        fn read_at(&self, address: u16, _t_state: u64) -> u8 {
+            self.bytes[address as usize]
+        }
+    }
+
+    #[test]
+    fn test_process_index_instruction_fetches_opcode_once() {
+        let mem = &mut TimedMemory {
+            bytes: vec![
+                0xFD, 0x21, 0x34, 0x12, // LD IY, nn
+            ],
+        };
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(14, z80.process_next_instruction(mem, io));
+        assert_eq!(0x1234, z80.iy);
+        assert_eq!(4, z80.program_counter);
+    }
+
+    #[test]
+    fn test_process_index_bit_instruction() {
+        let mut bytes = [
+            0xDD, 0xCB, 0x02, 0xC6, // SET 0, (IX+2)
+            0xFD, 0xCB, 0xFF, 0x00, // LD B, RLC (IY-1)
+            0xDD, 0xCB, 0x01, 0x7E, // BIT 7, (IX+1)
+            0b00000000, 0b00000000,
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.ix = 0x000B;
+        z80.iy = 0x000E;
+
+        assert_eq!(23, z80.process_next_instruction(ram, io));
+        assert_eq!(0b00000001, ram.read(0x000D));
+
+        assert_eq!(23, z80.process_next_instruction(ram, io));
+        assert_eq!(0b00000010, ram.read(0x000D));
+        assert_eq!(0b00000010, z80.b.value());
+
+        assert_eq!(20, z80.process_next_instruction(ram, io));
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(0x000C, z80.memptr);
+        assert_eq!(12, z80.program_counter);
+    }
+
+    #[test]
+    fn test_invalid_opcode_policy_nop() {
+        let mut bytes = [
+            0xED, 0x00, // NOP (undocumented)
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(Ok(8), z80.step(ram, io));
+        assert_eq!(2, z80.program_counter);
+    }
+
+    #[test]
+    fn test_invalid_opcode_policy_trap() {
+        let mut bytes = [
+            0x00, // NOP
+            0xDD, 0xED, 0xFF, // NOP (undocumented)
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Trap);
+
+        assert_eq!(Ok(4), z80.step(ram, io));
+        let error = z80.step(ram, io).unwrap_err();
+
+        assert_eq!(
+            OpcodeError {
+                program_counter: 0x0001,
+                bytes: vec![0xDD, 0xED, 0xFF],
+                prefix: Prefix::Ed,
+            },
+            error
+        );
+        assert_eq!(
+            "invalid opcode DD ED FF at address 0x0001",
+            error.to_string()
+        );
+        assert_eq!(0x0001, z80.program_counter);
+        assert_eq!(1, z80.r.value());
+        assert_eq!(4, z80.t_states());
+
+        // The processor stays on the instruction until it is dealt with
+        assert_eq!(Err(error), z80.step(ram, io));
+        assert_eq!(0x0001, z80.program_counter);
+        assert_eq!(1, z80.r.value());
+        assert_eq!(4, z80.t_states());
+    }
+
+    #[test]
+    fn test_invalid_opcode_policy_hook() {
+        let mut bytes = [
+            0xED, 0xFB, // NOP (undocumented)
+            0x42, // Operand read by the hook
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let hook: InvalidOpcodeHook = |z80, mem, _, error| {
+            assert_eq!(vec![0xED, 0xFB], error.bytes);
+            z80.set_a(mem.read(z80.program_counter()));
+            z80.set_program_counter(z80.program_counter() + 1);
+
+            // T states
+            20
+        };
+
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Hook(hook));
+        assert_eq!(Ok(20), z80.step(ram, io));
+        assert_eq!(0x42, z80.a.value());
+        assert_eq!(3, z80.program_counter);
+
+        // The hook is handed buses that are only known at run time too
+        let mem: &mut dyn Z80Memory = ram;
+        let io: &mut dyn Z80Io = io;
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Hook(hook));
+        assert_eq!(Ok(20), z80.step(mem, io));
+        assert_eq!(0x42, z80.a.value());
+        assert_eq!(3, z80.program_counter);
+    }
+
+    #[test]
+    fn test_invalid_opcode_policy_hook_after_index_prefix() {
+        let mut bytes = [
+            0xDD, 0xED, 0xFB, // NOP (undocumented)
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let hook: InvalidOpcodeHook = |_, _, _, _| {
+            // T states
+            252
+        };
+
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Hook(hook));
+        assert_eq!(Ok(255), z80.step(ram, io));
+        assert_eq!(256, z80.t_states());
+        assert_eq!(3, z80.program_counter);
+    }
+
+    #[test]
+    fn test_run_for_carries_overshoot() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        // Three 4 T state NOPs overshoot by 2, which the next call makes up
+        assert_eq!(Ok(12), z80.run_for(10, ram, io));
+        assert_eq!(3, z80.program_counter);
+        assert_eq!(Ok(8), z80.run_for(10, ram, io));
+        assert_eq!(5, z80.program_counter);
+
+        assert_eq!(Ok(4), z80.run_for(3, ram, io));
+        assert_eq!(Ok(0), z80.run_for(1, ram, io));
+        assert_eq!(6, z80.program_counter);
+    }
+
+    #[test]
+    fn test_run_for_stops_at_trapped_opcode() {
+        let mut bytes = [
+            0x00, // NOP
+            0xED, 0x00, // NOP (undocumented)
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Trap);
+
+        let error = z80.run_for(100, ram, io).unwrap_err();
+
+        assert_eq!(0x0001, error.program_counter);
+        assert_eq!(0x0001, z80.program_counter);
+        assert_eq!(4, z80.t_states());
+    }
+
+    #[test]
+    fn test_run_for_drops_overshoot_at_trapped_opcode() {
+        let mut bytes = [
+            0x00, 0x00, 0x00, // NOP
+            0xED, 0x00, // NOP (undocumented)
+            0x00, 0x00, // NOP
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Trap);
+
+        assert_eq!(Ok(12), z80.run_for(10, ram, io));
+        assert!(z80.run_for(10, ram, io).is_err());
+        assert_eq!(12, z80.t_states());
+
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Nop);
+        assert_eq!(Ok(12), z80.run_for(10, ram, io));
+        assert_eq!(6, z80.program_counter);
+    }
+
+    #[test]
+    fn test_run_until_stops_at_trapped_opcode() {
+        let mut bytes = [
+            0x00, // NOP
+            0xED, 0x00, // NOP (undocumented)
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_invalid_opcode_policy(InvalidOpcodePolicy::Trap);
+
+        let error = z80.run_until(ram, io, |_| false).unwrap_err();
+
+        assert_eq!(0x0001, error.program_counter);
+        assert_eq!(4, z80.t_states());
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut bytes = [
+            0x06, 0x03, // LD B, 3
+            0x10, 0xFE, // DJNZ $
+            0x76, // HALT
+        ];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        assert_eq!(
+            Ok(7 + 13 + 13 + 8),
+            z80.run_until(ram, io, |z80| z80.program_counter() == 4)
+        );
+        assert_eq!(0, z80.b.value());
+        assert_eq!(
+            Ok(0),
+            z80.run_until(ram, io, |z80| z80.program_counter() == 4)
+        );
+    }
+
+    #[test]
+    fn test_register_accessors() {
+        let z80 = &mut Z80::new();
+        z80.set_program_counter(0x1234);
+        z80.set_stack_pointer(0x5678);
+        z80.set_ix(0x9ABC);
+        z80.set_iy(0xDEF0);
+        z80.set_i(0x12);
+        z80.set_r(0x34);
+
+        assert_eq!(0x1234, z80.program_counter());
+        assert_eq!(0x5678, z80.stack_pointer());
+        assert_eq!(0x9ABC, z80.ix());
+        assert_eq!(0xDEF0, z80.iy());
+        assert_eq!(0x12, z80.i());
+        assert_eq!(0x34, z80.r());
+    }
+
+    /// Records the clock at every memory and port access.
+    struct TimedBus {
+        bytes: Vec<u8>,
+        accesses: RefCell<Vec<(&'static str, u16, u64)>>,
+    }
+
+    impl TimedBus {
+        fn new(bytes: &[u8]) -> TimedBus {
+            TimedBus {
+                bytes: bytes.to_vec(),
+                accesses: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Z80Memory for TimedBus {
+        fn read(&self, address: u16) -> u8 {
+            self.bytes[address as usize]
+        }
+
+        fn write(&mut self, address: u16, data: u8) {
+            self.bytes[address as usize] = data;
+        }
+
+        fn read_at(&self, address: u16, t_state: u64) -> u8 {
+            self.accesses.borrow_mut().push(("read", address, t_state));
+            self.read(address)
+        }
+
+        fn write_at(&mut self, address: u16, data: u8, t_state: u64) {
+            self.accesses.borrow_mut().push(("write", address, t_state));
+            self.write(address, data);
+        }
+    }
+
+    impl Z80Io for TimedBus {
+        fn read_port(&mut self, _port: u16) -> u8 {
+            0xFF
+        }
+
+        fn write_port(&mut self, _port: u16, _data: u8) {}
+
+        fn write_port_at(&mut self, port: u16, _data: u8, t_state: u64) {
+            self.accesses.borrow_mut().push(("out", port, t_state));
+        }
+    }
+
+    #[test]
+    fn test_clock_matches_t_states() {
+        // A DDCB or FDCB opcode follows its displacement
+        let prefixes: [&[u8]; 7] = [
+            &[],
+            &[0xCB],
+            &[0xED],
+            &[0xDD],
+            &[0xFD],
+            &[0xDD, 0xCB, 0x05],
+            &[0xFD, 0xCB, 0x05],
+        ];
+
+        for prefix in prefixes {
+            for opcode in 0x00..=0xFF {
+                // Both ways through conditional and repeating instructions
+                for (f, bc) in [(0x00, 0x0102), (0xFF, 0x0201)] {
+                    let mut bytes = vec![0x00; 0x10000];
+                    let start = 0x8000;
+                    let instruction_bytes = [prefix, &[opcode]].concat();
+                    bytes[start..start + instruction_bytes.len()]
+                        .copy_from_slice(&instruction_bytes);
+                    let ram = &mut Ram::new(&mut bytes);
+                    let io = &mut Ports::new(&[]);
+                    let z80 = &mut Z80::new();
+                    z80.program_counter = start as u16;
+                    z80.stack_pointer = 0x4000;
+                    z80.f.set_value(f);
+                    z80.set_bc(bc);
+
+                    let t_states = z80.process_next_instruction(ram, io);
+
+                    assert_eq!(
+                        t_states as u64,
+                        z80.t_states(),
+                        "{:02X?} with F = {:02X}h",
+                        instruction_bytes,
+                        f
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_access_t_states() {
+        let mut bytes = [0x00; 0x20];
+        bytes[0..5].copy_from_slice(&[
+            0x34, // INC (HL)
+            0xE3, // EX (SP), HL
+            0xD3, 0xFE, // OUT (FEh), A
+            0x76, // HALT
+        ]);
+        let bus = &mut TimedBus::new(&bytes);
+        let io = &mut TimedBus::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0010);
+        z80.stack_pointer = 0x0018;
+
+        for _ in 0..5 {
+            z80.process_next_instruction(bus, io);
+        }
+
+        assert_eq!(
+            vec![
+                ("read", 0x0000, 0),
+                ("read", 0x0010, 4),
+                ("write", 0x0010, 8),
+                ("read", 0x0001, 11),
+                ("read", 0x0018, 15),
+                ("read", 0x0019, 18),
+                ("write", 0x0019, 22),
+                ("write", 0x0018, 25),
+                ("read", 0x0002, 30),
+                ("read", 0x0003, 34),
+                ("read", 0x0004, 41),
+                // Halted, fetching the next opcode without running it
+                ("read", 0x0005, 45),
+            ],
+            bus.accesses.take()
+        );
+        assert_eq!(vec![("out", 0x00FE, 37)], io.accesses.take());
+        assert_eq!(49, z80.t_states());
+    }
+
+    #[test]
+    fn test_frame_t_states() {
+        let mut bytes = [0x00; 0x10];
+        let ram = &mut Ram::new(&mut bytes);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+
+        z80.run_until(ram, io, |z80| z80.frame_t_states() >= 10)
+            .unwrap();
+        assert_eq!(12, z80.frame_t_states());
+
+        // The 2 T states past the end of the frame count towards the next
+        z80.end_frame(10);
+        assert_eq!(2, z80.frame_t_states());
+        assert_eq!(12, z80.t_states());
+    }
+
+    /// Records every machine cycle, holding off those that address the slow
+    /// range by one wait state.
+    struct CycleBus {
+        bytes: Vec<u8>,
+        slow: Range<u16>,
+        wait_states: u8,
+        cycles: RefCell<Vec<(MachineCycle, u64)>>,
+    }
+
+    impl CycleBus {
+        fn new(bytes: &[u8], slow: Range<u16>) -> CycleBus {
+            CycleBus {
+                bytes: bytes.to_vec(),
+                slow,
+                wait_states: 1,
+                cycles: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl Z80Memory for CycleBus {
+        fn read(&self, address: u16) -> u8 {
+            self.bytes[address as usize]
+        }
+
+        fn write(&mut self, address: u16, data: u8) {
+            self.bytes[address as usize] = data;
+        }
+
+        fn machine_cycle(&self, cycle: MachineCycle, t_state: u64) -> u8 {
+            self.cycles.borrow_mut().push((cycle, t_state));
+            if self.slow.contains(&cycle.address()) {
+                self.wait_states
+            } else {
+                0
+            }
+        }
+    }
+
+    impl Z80Io for CycleBus {
+        fn read_port(&mut self, _port: u16) -> u8 {
+            0xFF
+        }
+
+        fn write_port(&mut self, _port: u16, _data: u8) {}
+
+        fn machine_cycle(&mut self, cycle: MachineCycle, t_state: u64) -> u8 {
+            self.cycles.borrow_mut().push((cycle, t_state));
+            if self.slow.contains(&cycle.address()) {
+                self.wait_states
+            } else {
+                0
+            }
+        }
+    }
+
+    #[test]
+    fn test_machine_cycles() {
+        let mut bytes = [0x00; 0x20];
+        bytes[0..4].copy_from_slice(&[
+            0x34, // INC (HL)
+            0xC5, // PUSH BC
+            0xD3, 0xFE, // OUT (FEh), A
+        ]);
+        let bus = &mut CycleBus::new(&bytes, 0..0);
+        let io = &mut CycleBus::new(&[], 0..0);
+        let z80 = &mut Z80::new();
+        z80.set_execution_mode(ExecutionMode::MachineCycle);
+        z80.i.set_value(0x3F);
+        z80.set_hl(0x0010);
+        z80.stack_pointer = 0x0018;
+
+        for _ in 0..3 {
+            z80.process_next_instruction(bus, io);
+        }
+
+        assert_eq!(
+            vec![
+                (MachineCycle::OpcodeFetch(0x0000), 0),
+                (MachineCycle::MemoryRead(0x0010), 4),
+                (MachineCycle::Internal(0x0010), 7),
+                (MachineCycle::MemoryWrite(0x0010), 8),
+                (MachineCycle::OpcodeFetch(0x0001), 11),
+                (MachineCycle::Internal(0x3F01), 15),
+                (MachineCycle::MemoryWrite(0x0017), 16),
+                (MachineCycle::MemoryWrite(0x0016), 19),
+                (MachineCycle::OpcodeFetch(0x0002), 22),
+                (MachineCycle::MemoryRead(0x0003), 26),
+            ],
+            bus.cycles.take()
+        );
+        assert_eq!(
+            vec![(MachineCycle::PortWrite(0x00FE), 29)],
+            io.cycles.take()
+        );
+    }
+
+    #[test]
+    fn test_machine_cycle_wait_states() {
+        let mut bytes = [0x00; 0x20];
+        bytes[0..2].copy_from_slice(&[
+            0x34, // INC (HL)
+            0x00, // NOP
+        ]);
+        let bus = &mut CycleBus::new(&bytes, 0x0010..0x0020);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_execution_mode(ExecutionMode::MachineCycle);
+        z80.set_hl(0x0010);
+
+        // The read, the internal T state and the write are each held off
+        assert_eq!(11 + 3, z80.process_next_instruction(bus, io));
+        assert_eq!(4, z80.process_next_instruction(bus, io));
+        assert_eq!(
+            vec![
+                (MachineCycle::OpcodeFetch(0x0000), 0),
+                (MachineCycle::MemoryRead(0x0010), 4),
+                (MachineCycle::Internal(0x0010), 8),
+                (MachineCycle::MemoryWrite(0x0010), 10),
+                (MachineCycle::OpcodeFetch(0x0001), 14),
+            ],
+            bus.cycles.take()
+        );
+        assert_eq!(18, z80.t_states());
+    }
+
+    #[test]
+    fn test_wait_states_past_255() {
+        let mut bytes = [0x00; 0x20];
+        for address in [0x00, 0x04, 0x08] {
+            bytes[address..address + 4].copy_from_slice(&[
+                0xDD, 0xCB, 0x00, 0xC6, // SET 0, (IX+0)
+            ]);
+        }
+        let bus = &mut CycleBus::new(&bytes, 0x0000..0x0020);
+        bus.wait_states = 0xFF;
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_execution_mode(ExecutionMode::MachineCycle);
+        z80.ix = 0x0010;
+
+        assert_eq!(0xFF, z80.process_next_instruction(bus, io));
+        let t_states = 23 + 0xFF * bus.cycles.take().len() as u64;
+        assert_eq!(t_states, z80.t_states());
+
+        // The budgeted calls count the clock rather than the T states
+        // returned
+        assert_eq!(Ok(t_states), z80.run_for(1, bus, io));
+        assert_eq!(
+            Ok(t_states),
+            z80.run_until(bus, io, |z80| z80.program_counter() == 0x0C)
+        );
+        assert_eq!(3 * t_states, z80.t_states());
+        assert_eq!(0x01, bus.bytes[0x10]);
+    }
+
+    #[test]
+    fn test_instruction_mode_hides_machine_cycles() {
+        let bytes = [0x34, 0x00]; // INC (HL)
+        let bus = &mut CycleBus::new(&bytes, 0x0000..0x0002);
+        let io = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+
+        assert_eq!(11, z80.process_next_instruction(bus, io));
+        assert!(bus.cycles.take().is_empty());
+        assert_eq!(1, bus.bytes[1]);
+    }
+
+    #[test]
+    fn test_dyn_buses() {
+        let mut bytes = [0x00; 0x20];
+        bytes[0..3].copy_from_slice(&[
+            0x34, // INC (HL)
+            0xD3, 0xFE, // OUT (FEh), A
+        ]);
+        let bus = &mut CycleBus::new(&bytes, 0x0010..0x0020);
+        let ports = &mut Ports::new(&[]);
+        let z80 = &mut Z80::new();
+        z80.set_execution_mode(ExecutionMode::MachineCycle);
+        z80.set_hl(0x0010);
+        z80.a.set_value(0x07);
+
+        let mem: &mut dyn Z80Memory = bus;
+        let io: &mut dyn Z80Io = ports;
+        assert_eq!(11 + 3, z80.process_next_instruction(mem, io));
+        assert_eq!(11, z80.process_next_instruction(mem, io));
+
+        assert_eq!(0x01, bus.bytes[0x10]);
+        assert_eq!(6, bus.cycles.take().len());
+        assert_eq!(vec![(0x07FE, 0x07)], ports.writes);
+    }
 }
@@ -37,10 +37,12 @@ fn flags_set(flag_bitmask: u8, register: &Register) -> bool {
 
 fn unset_flags(flag_bitmask: u8, register: &mut Register) {
     register.set_value(register.value() & flag_bitmask.not());
+    register.flags_written = true;
 }
 
 fn set_flags(flag_bitmask: u8, register: &mut Register) {
     register.set_value(register.value() | flag_bitmask);
+    register.flags_written = true;
 }
 
 fn set_flags_with(flag_bitmask: u8, register: &mut Register, value: bool) {
@@ -191,6 +193,13 @@ pub fn set_c_flag_with(register: &mut Register, value: bool) {
     set_flags_with(C_FLAG_BITMASK, register, value);
 }
 
+/// True if the value has an even number of set bits, which is how the P/V flag
+/// reports parity.
+pub fn parity(value: u8) -> bool {
+    value.count_ones() & 1 == 0
+}
+
+#[cfg(test)]
 mod tests {
     use crate::z80::Register;
 
@@ -780,4 +789,13 @@ mod tests {
         assert_eq!(false, n_flag(&register));
         assert_eq!(false, c_flag(&register));
     }
+
+    #[test]
+    fn test_parity() {
+        assert_eq!(true, parity(0b00000000));
+        assert_eq!(false, parity(0b00000001));
+        assert_eq!(true, parity(0b10000001));
+        assert_eq!(false, parity(0b01110000));
+        assert_eq!(true, parity(0b11111111));
+    }
 }
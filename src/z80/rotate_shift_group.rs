@@ -0,0 +1,1903 @@
+use super::{
+    register_flags::{
+        c_flag, parity, set_c_flag_with, set_p_flag_with, set_s_flag_with, set_x_flag_with,
+        set_y_flag_with, set_z_flag_with, unset_h_flag, unset_n_flag, S_FLAG_BITMASK,
+        X_FLAG_BITMASK, Y_FLAG_BITMASK,
+    },
+    Register, Z80Memory, Z80,
+};
+
+impl Z80 {
+    // Rotate and Shift Group
+
+    /// ## RLCA
+    ///
+    /// ### Operation
+    ///
+    /// CY ← A7, A ← A << 1 | A7
+    ///
+    /// ### Op Code
+    ///
+    /// RLCA: `0 0 0 0 0 1 1 1` (07)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator (Register A) are rotated left 1 bit
+    /// position. The sign bit (bit 7) is copied to the Carry flag and also to
+    /// bit 0. Bit 0 is the least-significant bit.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7 of Accumulator.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator contains the following data: 1000 1000. Upon the
+    /// execution of an RLCA instruction, the Accumulator and Carry flag
+    /// contain: 0001 0001 and 1.
+    pub fn rlca(&mut self) -> u8 {
+        let a = self.a.value();
+        let result = a.rotate_left(1);
+        self.rotate_a_result(result, a & 0b10000000 != 0)
+    }
+
+    /// ## RLA
+    ///
+    /// ### Operation
+    ///
+    /// CY ← A7, A ← A << 1 | CY
+    ///
+    /// ### Op Code
+    ///
+    /// RLA: `0 0 0 1 0 1 1 1` (17)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator (Register A) are rotated left 1 bit
+    /// position through the Carry flag. The previous contents of the Carry
+    /// flag are copied to bit 0. Bit 0 is the least-significant bit.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7 of Accumulator.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator and the Carry flag contains the following data: 0111
+    /// 0110 and 1. Upon the execution of an RLA instruction, the Accumulator
+    /// and the Carry flag contain: 1110 1101 and 0.
+    pub fn rla(&mut self) -> u8 {
+        let a = self.a.value();
+        let result = (a << 1) | c_flag(&self.f) as u8;
+        self.rotate_a_result(result, a & 0b10000000 != 0)
+    }
+
+    /// ## RRCA
+    ///
+    /// ### Operation
+    ///
+    /// CY ← A0, A ← A >> 1 | A0 << 7
+    ///
+    /// ### Op Code
+    ///
+    /// RRCA: `0 0 0 0 1 1 1 1` (0F)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator (Register A) are rotated right 1 bit
+    /// position. Bit 0 is copied to the Carry flag and also to bit 7. Bit 0 is
+    /// the least-significant bit.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of Accumulator.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator contains the following data: 0001 0001. Upon the
+    /// execution of an RRCA instruction, the Accumulator and the Carry flag
+    /// now contain: 1000 1000 and 1.
+    pub fn rrca(&mut self) -> u8 {
+        let a = self.a.value();
+        let result = a.rotate_right(1);
+        self.rotate_a_result(result, a & 0b00000001 != 0)
+    }
+
+    /// ## RRA
+    ///
+    /// ### Operation
+    ///
+    /// CY ← A0, A ← A >> 1 | CY << 7
+    ///
+    /// ### Op Code
+    ///
+    /// RRA: `0 0 0 1 1 1 1 1` (1F)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the Accumulator (Register A) are rotated right 1 bit
+    /// position through the Carry flag. The previous contents of the Carry
+    /// flag are copied to bit 7. Bit 0 is the least-significant bit.
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 4        | 1.00       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of Accumulator.
+    ///
+    /// ### Example
+    ///
+    /// The Accumulator and the Carry Flag contain the following data: 1110
+    /// 0001 and 0. Upon the execution of an RRA instruction, the Accumulator
+    /// and the Carry flag now contain: 0111 0000 and 1.
+    pub fn rra(&mut self) -> u8 {
+        let a = self.a.value();
+        let result = (a >> 1) | ((c_flag(&self.f) as u8) << 7);
+        self.rotate_a_result(result, a & 0b00000001 != 0)
+    }
+
+    /// Stores the result of one of the Accumulator rotates and sets the flags
+    /// they share.
+    fn rotate_a_result(&mut self, result: u8, carry: bool) -> u8 {
+        self.a.set_value(result);
+
+        unset_h_flag(&mut self.f);
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, carry);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        // T states
+        4
+    }
+
+    /// ## RLC m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m7, m ← m << 1 | m7
+    ///
+    /// ### Op Code
+    ///
+    /// RLC r: `1 1 0 0 1 0 1 1` (CB) `0 0 0 0 0 r r r`
+    ///
+    /// RLC (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 0 0 0 1 1 0`
+    ///
+    /// RLC (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 0 0 1 1 0`
+    ///
+    /// RLC (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 0 0 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// The contents of operand m are rotated left 1 bit position. The contents of
+    /// bit 7 are copied to the Carry flag and also to bit 0.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If Register R contains 1000 1000, then upon the execution of an RLC r
+    /// instruction, register r and the Carry flag contain: 0001 0001 and 1.
+    fn rlc_m(&mut self, m: u8) -> u8 {
+        let result = m.rotate_left(1);
+        self.rotate_shift_result(result, m & 0b10000000 != 0)
+    }
+
+    pub fn rlc_a(&mut self) -> u8 {
+        let a = self.rlc_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_b(&mut self) -> u8 {
+        let b = self.rlc_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_c(&mut self) -> u8 {
+        let c = self.rlc_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_d(&mut self) -> u8 {
+        let d = self.rlc_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_e(&mut self) -> u8 {
+        let e = self.rlc_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_h(&mut self) -> u8 {
+        let h = self.rlc_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_l(&mut self) -> u8 {
+        let l = self.rlc_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn rlc_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.rlc_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## RRC m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m0, m ← m >> 1 | m0 << 7
+    ///
+    /// ### Op Code
+    ///
+    /// RRC r: `1 1 0 0 1 0 1 1` (CB) `0 0 0 0 1 r r r`
+    ///
+    /// RRC (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 0 0 1 1 1 0`
+    ///
+    /// RRC (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 0 1 1 1 0`
+    ///
+    /// RRC (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 0 1 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// The contents of operand m are rotated right 1 bit position. The contents
+    /// of bit 0 are copied to the Carry flag and also to bit 7. Bit 0 is the
+    /// least-significant bit.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If Register A contains 0011 0001, then upon the execution of an RRC A
+    /// instruction, register A and the Carry flag contain: 1001 1000 and 1.
+    fn rrc_m(&mut self, m: u8) -> u8 {
+        let result = m.rotate_right(1);
+        self.rotate_shift_result(result, m & 0b00000001 != 0)
+    }
+
+    pub fn rrc_a(&mut self) -> u8 {
+        let a = self.rrc_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_b(&mut self) -> u8 {
+        let b = self.rrc_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_c(&mut self) -> u8 {
+        let c = self.rrc_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_d(&mut self) -> u8 {
+        let d = self.rrc_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_e(&mut self) -> u8 {
+        let e = self.rrc_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_h(&mut self) -> u8 {
+        let h = self.rrc_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_l(&mut self) -> u8 {
+        let l = self.rrc_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn rrc_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.rrc_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## RL m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m7, m ← m << 1 | CY
+    ///
+    /// ### Op Code
+    ///
+    /// RL r: `1 1 0 0 1 0 1 1` (CB) `0 0 0 1 0 r r r`
+    ///
+    /// RL (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 0 1 0 1 1 0`
+    ///
+    /// RL (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 1 0 1 1 0`
+    ///
+    /// RL (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 1 0 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// The contents of the m operand are rotated left 1 bit position. The
+    /// contents of bit 7 are copied to the Carry flag, and the previous contents
+    /// of the Carry flag are copied to bit 0.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If the D Register contains 1000 1111 and the Carry flag is reset, then
+    /// upon the execution of an RL D instruction, the D Register and the Carry
+    /// flag contain: 0001 1110 and 1.
+    fn rl_m(&mut self, m: u8) -> u8 {
+        let result = (m << 1) | c_flag(&self.f) as u8;
+        self.rotate_shift_result(result, m & 0b10000000 != 0)
+    }
+
+    pub fn rl_a(&mut self) -> u8 {
+        let a = self.rl_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn rl_b(&mut self) -> u8 {
+        let b = self.rl_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn rl_c(&mut self) -> u8 {
+        let c = self.rl_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn rl_d(&mut self) -> u8 {
+        let d = self.rl_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn rl_e(&mut self) -> u8 {
+        let e = self.rl_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn rl_h(&mut self) -> u8 {
+        let h = self.rl_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn rl_l(&mut self) -> u8 {
+        let l = self.rl_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn rl_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.rl_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## RR m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m0, m ← m >> 1 | CY << 7
+    ///
+    /// ### Op Code
+    ///
+    /// RR r: `1 1 0 0 1 0 1 1` (CB) `0 0 0 1 1 r r r`
+    ///
+    /// RR (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 0 1 1 1 1 0`
+    ///
+    /// RR (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 1 1 1 1 0`
+    ///
+    /// RR (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 0 1 1 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// The contents of operand m are rotated right 1 bit position through the
+    /// carry flag. The contents of bit 0 are copied to the carry flag and the
+    /// previous contents of the carry flag are copied to bit 7. Bit 0 is the
+    /// least-significant bit.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 4343h and memory location 4343h contains
+    /// 1101 1101, and the Carry flag is reset, then upon the execution of an RR
+    /// (HL) instruction, location 4343h and the Carry flag contain: 0110 1110
+    /// and 1.
+    fn rr_m(&mut self, m: u8) -> u8 {
+        let result = (m >> 1) | ((c_flag(&self.f) as u8) << 7);
+        self.rotate_shift_result(result, m & 0b00000001 != 0)
+    }
+
+    pub fn rr_a(&mut self) -> u8 {
+        let a = self.rr_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn rr_b(&mut self) -> u8 {
+        let b = self.rr_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn rr_c(&mut self) -> u8 {
+        let c = self.rr_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn rr_d(&mut self) -> u8 {
+        let d = self.rr_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn rr_e(&mut self) -> u8 {
+        let e = self.rr_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn rr_h(&mut self) -> u8 {
+        let h = self.rr_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn rr_l(&mut self) -> u8 {
+        let l = self.rr_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn rr_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.rr_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## SLA m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m7, m ← m << 1
+    ///
+    /// ### Op Code
+    ///
+    /// SLA r: `1 1 0 0 1 0 1 1` (CB) `0 0 1 0 0 r r r`
+    ///
+    /// SLA (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 1 0 0 1 1 0`
+    ///
+    /// SLA (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 0 0 1 1 0`
+    ///
+    /// SLA (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 0 0 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// An arithmetic shift left 1 bit position is performed on the contents of
+    /// operand m. The contents of bit 7 are copied to the Carry flag. Bit 0 is
+    /// the least-significant bit.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7.
+    ///
+    /// ### Example
+    ///
+    /// If Register L contains 1011 0001, then upon the execution of an SLA L
+    /// instruction, Register L and the Carry flag contain: 0110 0010 and 1.
+    fn sla_m(&mut self, m: u8) -> u8 {
+        let result = m << 1;
+        self.rotate_shift_result(result, m & 0b10000000 != 0)
+    }
+
+    pub fn sla_a(&mut self) -> u8 {
+        let a = self.sla_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn sla_b(&mut self) -> u8 {
+        let b = self.sla_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn sla_c(&mut self) -> u8 {
+        let c = self.sla_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn sla_d(&mut self) -> u8 {
+        let d = self.sla_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn sla_e(&mut self) -> u8 {
+        let e = self.sla_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn sla_h(&mut self) -> u8 {
+        let h = self.sla_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn sla_l(&mut self) -> u8 {
+        let l = self.sla_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn sla_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.sla_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## SRA m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m0, m ← m >> 1 | m7
+    ///
+    /// ### Op Code
+    ///
+    /// SRA r: `1 1 0 0 1 0 1 1` (CB) `0 0 1 0 1 r r r`
+    ///
+    /// SRA (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 1 0 1 1 1 0`
+    ///
+    /// SRA (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 0 1 1 1 0`
+    ///
+    /// SRA (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 0 1 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// An arithmetic shift right 1 bit position is performed on the contents of
+    /// operand m. The contents of bit 0 are copied to the Carry flag and the
+    /// previous contents of bit 7 remain unchanged. Bit 0 is the
+    /// least-significant bit.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If the Index Register IX contains 1000h and memory location 1003h contains
+    /// 1011 1000, then upon the execution of an SRA (IX+3h) instruction, memory
+    /// location 1003h and the Carry flag contain: 1101 1100 and 0.
+    fn sra_m(&mut self, m: u8) -> u8 {
+        let result = (m >> 1) | (m & 0b10000000);
+        self.rotate_shift_result(result, m & 0b00000001 != 0)
+    }
+
+    pub fn sra_a(&mut self) -> u8 {
+        let a = self.sra_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn sra_b(&mut self) -> u8 {
+        let b = self.sra_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn sra_c(&mut self) -> u8 {
+        let c = self.sra_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn sra_d(&mut self) -> u8 {
+        let d = self.sra_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn sra_e(&mut self) -> u8 {
+        let e = self.sra_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn sra_h(&mut self) -> u8 {
+        let h = self.sra_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn sra_l(&mut self) -> u8 {
+        let l = self.sra_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn sra_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.sra_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## SLL m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m7, m ← m << 1 | 1
+    ///
+    /// ### Op Code
+    ///
+    /// SLL r: `1 1 0 0 1 0 1 1` (CB) `0 0 1 1 0 r r r`
+    ///
+    /// SLL (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 1 1 0 1 1 0`
+    ///
+    /// SLL (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 1 0 1 1 0`
+    ///
+    /// SLL (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 1 0 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// Undocumented. The contents of operand m are shifted left 1 bit position
+    /// and bit 0 is set. The contents of bit 7 are copied to the Carry flag.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 7.
+    fn sll_m(&mut self, m: u8) -> u8 {
+        let result = (m << 1) | 0b00000001;
+        self.rotate_shift_result(result, m & 0b10000000 != 0)
+    }
+
+    pub fn sll_a(&mut self) -> u8 {
+        let a = self.sll_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn sll_b(&mut self) -> u8 {
+        let b = self.sll_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn sll_c(&mut self) -> u8 {
+        let c = self.sll_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn sll_d(&mut self) -> u8 {
+        let d = self.sll_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn sll_e(&mut self) -> u8 {
+        let e = self.sll_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn sll_h(&mut self) -> u8 {
+        let h = self.sll_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn sll_l(&mut self) -> u8 {
+        let l = self.sll_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn sll_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.sll_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// ## SRL m
+    ///
+    /// ### Operation
+    ///
+    /// CY ← m0, m ← m >> 1
+    ///
+    /// ### Op Code
+    ///
+    /// SRL r: `1 1 0 0 1 0 1 1` (CB) `0 0 1 1 1 r r r`
+    ///
+    /// SRL (HL): `1 1 0 0 1 0 1 1` (CB) `0 0 1 1 1 1 1 0`
+    ///
+    /// SRL (IX+d): `1 1 0 1 1 1 0 1` (DD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 1 1 1 1 0`
+    ///
+    /// SRL (IY+d): `1 1 1 1 1 1 0 1` (FD) `1 1 0 0 1 0 1 1` (CB) `d d d d d d d d` `0 0 1 1 1 1 1 0`
+    ///
+    /// r identifies registers B, C, D, E, H, L, or A assembled as follows in the
+    /// object code field:
+    ///
+    /// | Register | r   |
+    /// | -------- | --- |
+    /// | B        | 000 |
+    /// | C        | 001 |
+    /// | D        | 010 |
+    /// | E        | 011 |
+    /// | H        | 100 |
+    /// | L        | 101 |
+    /// | A        | 111 |
+    ///
+    /// ### Operands
+    ///
+    /// m
+    ///
+    /// ### Description
+    ///
+    /// The contents of operand m are shifted right 1 bit position. The contents
+    /// of bit 0 are copied to the Carry flag, and bit 7 is reset. Bit 0 is the
+    /// least-significant bit.
+    ///
+    /// | Instruction | M Cycles | T States              | 4 MHz E.T. |
+    /// | ----------- | -------- | --------------------- | ---------- |
+    /// | r           | 2        | 8 (4, 4)              | 2.00       |
+    /// | (HL)        | 4        | 15 (4, 4, 4, 3)       | 3.75       |
+    /// | (IX+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    /// | (IY+d)      | 6        | 23 (4, 4, 3, 5, 4, 3) | 5.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if parity is even; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is data from bit 0 of the source register.
+    ///
+    /// ### Example
+    ///
+    /// If Register B contains 1000 1111, then upon the execution of an SRL B
+    /// instruction, Register B and the Carry flag contain: 0100 0111 and 1.
+    fn srl_m(&mut self, m: u8) -> u8 {
+        let result = m >> 1;
+        self.rotate_shift_result(result, m & 0b00000001 != 0)
+    }
+
+    pub fn srl_a(&mut self) -> u8 {
+        let a = self.srl_m(self.a.value());
+        self.a.set_value(a);
+
+        // T states
+        8
+    }
+
+    pub fn srl_b(&mut self) -> u8 {
+        let b = self.srl_m(self.b.value());
+        self.b.set_value(b);
+
+        // T states
+        8
+    }
+
+    pub fn srl_c(&mut self) -> u8 {
+        let c = self.srl_m(self.c.value());
+        self.c.set_value(c);
+
+        // T states
+        8
+    }
+
+    pub fn srl_d(&mut self) -> u8 {
+        let d = self.srl_m(self.d.value());
+        self.d.set_value(d);
+
+        // T states
+        8
+    }
+
+    pub fn srl_e(&mut self) -> u8 {
+        let e = self.srl_m(self.e.value());
+        self.e.set_value(e);
+
+        // T states
+        8
+    }
+
+    pub fn srl_h(&mut self) -> u8 {
+        let h = self.srl_m(self.h.value());
+        self.h.set_value(h);
+
+        // T states
+        8
+    }
+
+    pub fn srl_l(&mut self) -> u8 {
+        let l = self.srl_m(self.l.value());
+        self.l.set_value(l);
+
+        // T states
+        8
+    }
+
+    pub fn srl_mem_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 1);
+        let data = self.srl_m(data);
+        self.write_memory(mem, hl, data);
+
+        // T states
+        15
+    }
+
+    /// Applies one of the rotate or shift operations to the memory location
+    /// addressed by the index register plus displacement, which has already
+    /// been computed into MEMPTR when the DDCB or FDCB instruction is decoded,
+    /// and returns the result.
+    fn rotate_shift_mem_index_d(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        operation: fn(&mut Z80, u8) -> u8,
+    ) -> u8 {
+        let address = self.memptr;
+        let data = self.read_memory(mem, address);
+        self.internal_cycles(mem, 1);
+        let data = operation(self, data);
+        self.write_memory(mem, address, data);
+        data
+    }
+
+    pub fn rlc_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::rlc_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn rlc_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::rlc_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn rrc_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::rrc_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn rrc_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::rrc_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn rl_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::rl_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn rl_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::rl_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn rr_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::rr_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn rr_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::rr_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn sla_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::sla_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn sla_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::sla_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn sra_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::sra_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn sra_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::sra_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn sll_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::sll_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn sll_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::sll_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    pub fn srl_mem_index_d(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.rotate_shift_mem_index_d(mem, Z80::srl_m);
+
+        // T states
+        23
+    }
+
+    /// Undocumented. The DDCB and FDCB forms with a register in the low three
+    /// bits also copy the result into that register.
+    pub fn srl_mem_index_d_r(
+        &mut self,
+        mem: &mut (impl Z80Memory + ?Sized),
+        r: fn(&mut Z80) -> &mut Register,
+    ) -> u8 {
+        let data = self.rotate_shift_mem_index_d(mem, Z80::srl_m);
+        r(self).set_value(data);
+
+        // T states
+        23
+    }
+
+    /// ## RLD
+    ///
+    /// ### Operation
+    ///
+    /// A3-0 ← (HL)7-4, (HL)7-4 ← (HL)3-0, (HL)3-0 ← A3-0
+    ///
+    /// ### Op Code
+    ///
+    /// RLD: `1 1 1 0 1 1 0 1` (ED) `0 1 1 0 1 1 1 1` (6F)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the low-order four bits (bits 3, 2, 1, and 0) of the
+    /// memory location (HL) are copied to the high-order four bits (7, 6, 5,
+    /// and 4) of that same memory location; the previous contents of those
+    /// high-order four bits are copied to the low-order four bits of the
+    /// Accumulator (Register A); and the previous contents of the low-order
+    /// four bits of the Accumulator are copied to the low-order four bits of
+    /// memory location (HL). The contents of the high-order bits of the
+    /// Accumulator are unaffected.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 5        | 18 (4, 4, 3, 4, 3) | 4.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if the Accumulator is negative after an operation; otherwise,
+    /// it is reset.
+    ///
+    /// Z is set if the Accumulator is 0 after an operation; otherwise, it is
+    /// reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if the parity of the Accumulator is even after an operation;
+    /// otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 5000h and the Accumulator and memory
+    /// location 5000h contain 0111 1010 and 0011 0001 respectively, then upon
+    /// the execution of an RLD instruction, the Accumulator and memory location
+    /// 5000h contain 0111 0011 and 0001 1010.
+    pub fn rld(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 4);
+        let a = self.a.value();
+
+        self.write_memory(mem, hl, (data << 4) | (a & 0x0F));
+        self.a.set_value((a & 0xF0) | (data >> 4));
+
+        self.rotate_digit_flags();
+        self.memptr = hl.wrapping_add(1);
+
+        // T states
+        18
+    }
+
+    /// ## RRD
+    ///
+    /// ### Operation
+    ///
+    /// A3-0 ← (HL)3-0, (HL)7-4 ← A3-0, (HL)3-0 ← (HL)7-4
+    ///
+    /// ### Op Code
+    ///
+    /// RRD: `1 1 1 0 1 1 0 1` (ED) `0 1 1 0 0 1 1 1` (67)
+    ///
+    /// ### Operands
+    ///
+    /// None.
+    ///
+    /// ### Description
+    ///
+    /// The contents of the low-order four bits (bits 3, 2, 1, and 0) of memory
+    /// location (HL) are copied to the low-order four bits of the Accumulator
+    /// (Register A). The previous contents of the low-order four bits of the
+    /// Accumulator are copied to the high-order four bits (7, 6, 5, and 4) of
+    /// location (HL); and the previous contents of the high-order four bits of
+    /// (HL) are copied to the low-order four bits of (HL). The contents of the
+    /// high-order bits of the Accumulator are unaffected.
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 5        | 18 (4, 4, 3, 4, 3) | 4.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if the Accumulator is negative after an operation; otherwise,
+    /// it is reset.
+    ///
+    /// Z is set if the Accumulator is 0 after an operation; otherwise, it is
+    /// reset.
+    ///
+    /// H is reset.
+    ///
+    /// P/V is set if the parity of the Accumulator is even after an operation;
+    /// otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is not affected.
+    ///
+    /// ### Example
+    ///
+    /// If the HL register pair contains 5000h and the Accumulator and memory
+    /// location 5000h contain 1000 0100 and 0010 0000, respectively, then upon
+    /// the execution of an RRD instruction, the Accumulator and memory location
+    /// 5000h contain 1000 0000 and 0100 0010.
+    pub fn rrd(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let hl = self.hl();
+        let data = self.read_memory(mem, hl);
+        self.internal_cycles(mem, 4);
+        let a = self.a.value();
+
+        self.write_memory(mem, hl, (a << 4) | (data >> 4));
+        self.a.set_value((a & 0xF0) | (data & 0x0F));
+
+        self.rotate_digit_flags();
+        self.memptr = hl.wrapping_add(1);
+
+        // T states
+        18
+    }
+
+    /// Sets the flags shared by RLD and RRD from the Accumulator.
+    fn rotate_digit_flags(&mut self) {
+        let a = self.a.value();
+
+        set_s_flag_with(&mut self.f, a & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, a == 0);
+        unset_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(a));
+        unset_n_flag(&mut self.f);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, a & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, a & X_FLAG_BITMASK != 0);
+    }
+
+    /// Sets the flags shared by the CB prefixed rotate and shift instructions
+    /// and returns the result.
+    fn rotate_shift_result(&mut self, result: u8, carry: bool) -> u8 {
+        set_s_flag_with(&mut self.f, result & S_FLAG_BITMASK != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        unset_h_flag(&mut self.f);
+        set_p_flag_with(&mut self.f, parity(result));
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, carry);
+
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        set_y_flag_with(&mut self.f, result & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, result & X_FLAG_BITMASK != 0);
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{c_flag, h_flag, n_flag, p_flag, s_flag, set_c_flag, z_flag},
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_rlca() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b10001000);
+
+        let t_states = z80.rlca();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0b00010001, z80.a.value());
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rla() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b01110110);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.rla();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0b11101101, z80.a.value());
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rrca() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b00010001);
+
+        let t_states = z80.rrca();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0b10001000, z80.a.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rra() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b11100001);
+
+        let t_states = z80.rra();
+        assert_eq!(4, t_states);
+
+        assert_eq!(0b01110000, z80.a.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rlc_r() {
+        let scenarios: [(fn(&mut Z80) -> u8, fn(&mut Z80) -> &mut Register); 7] = [
+            (Z80::rlc_a, |z80: &mut Z80| &mut z80.a),
+            (Z80::rlc_b, |z80: &mut Z80| &mut z80.b),
+            (Z80::rlc_c, |z80: &mut Z80| &mut z80.c),
+            (Z80::rlc_d, |z80: &mut Z80| &mut z80.d),
+            (Z80::rlc_e, |z80: &mut Z80| &mut z80.e),
+            (Z80::rlc_h, |z80: &mut Z80| &mut z80.h),
+            (Z80::rlc_l, |z80: &mut Z80| &mut z80.l),
+        ];
+
+        for (opcode, r_supplier) in scenarios {
+            let z80 = &mut Z80::new();
+            r_supplier(z80).set_value(0b10001000);
+
+            let t_states = opcode(z80);
+            assert_eq!(8, t_states);
+
+            assert_eq!(0b00010001, r_supplier(z80).value());
+            assert_eq!(true, c_flag(&z80.f));
+            assert_eq!(false, s_flag(&z80.f));
+            assert_eq!(false, z_flag(&z80.f));
+            assert_eq!(true, p_flag(&z80.f));
+        }
+    }
+
+    #[test]
+    fn test_rrc_a() {
+        let z80 = &mut Z80::new();
+        z80.a.set_value(0b00110001);
+
+        z80.rrc_a();
+
+        assert_eq!(0b10011000, z80.a.value());
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rl_d() {
+        let z80 = &mut Z80::new();
+        z80.d.set_value(0b10001111);
+
+        z80.rl_d();
+
+        assert_eq!(0b00011110, z80.d.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rr_mem_hl() {
+        let bytes = &mut [0x00, 0b11011101];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+
+        let t_states = z80.rr_mem_hl(ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0b01101110, ram.read(1));
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sla_l() {
+        let z80 = &mut Z80::new();
+        z80.l.set_value(0b10110001);
+
+        z80.sla_l();
+
+        assert_eq!(0b01100010, z80.l.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sra_b() {
+        let z80 = &mut Z80::new();
+        z80.b.set_value(0b10111000);
+
+        z80.sra_b();
+
+        assert_eq!(0b11011100, z80.b.value());
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sll_c() {
+        let z80 = &mut Z80::new();
+        z80.c.set_value(0b10000000);
+
+        z80.sll_c();
+
+        assert_eq!(0b00000001, z80.c.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_srl_b() {
+        let z80 = &mut Z80::new();
+        z80.b.set_value(0b00000001);
+
+        z80.srl_b();
+
+        assert_eq!(0b00000000, z80.b.value());
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rld() {
+        let bytes = &mut [0x00, 0b00110001];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+        z80.a.set_value(0b01111010);
+
+        let t_states = z80.rld(ram);
+        assert_eq!(18, t_states);
+
+        assert_eq!(0b01110011, z80.a.value());
+        assert_eq!(0b00011010, ram.read(1));
+        assert_eq!(0x0002, z80.memptr);
+        assert_eq!(false, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rrd() {
+        let bytes = &mut [0x00, 0b00100000];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x0001);
+        z80.a.set_value(0b10000100);
+
+        let t_states = z80.rrd(ram);
+        assert_eq!(18, t_states);
+
+        assert_eq!(0b10000000, z80.a.value());
+        assert_eq!(0b01000010, ram.read(1));
+        assert_eq!(true, s_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sra_mem_index_d() {
+        let bytes = &mut [0x00, 0x00, 0x00, 0b10111000];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.memptr = 0x0003;
+
+        let t_states = z80.sra_mem_index_d(ram);
+        assert_eq!(23, t_states);
+
+        assert_eq!(0b11011100, ram.read(3));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_rlc_mem_index_d_r() {
+        let bytes = &mut [0x00, 0b10001000];
+        let ram = &mut Ram::new(bytes);
+
+        let z80 = &mut Z80::new();
+        z80.memptr = 0x0001;
+
+        let t_states = z80.rlc_mem_index_d_r(ram, |z80: &mut Z80| &mut z80.h);
+        assert_eq!(23, t_states);
+
+        assert_eq!(0b00010001, ram.read(1));
+        assert_eq!(0b00010001, z80.h.value());
+        assert_eq!(true, c_flag(&z80.f));
+    }
+}
@@ -0,0 +1,873 @@
+use super::{
+    register_flags::{
+        c_flag, set_c_flag_with, set_h_flag_with, set_n_flag, set_p_flag_with, set_s_flag_with,
+        set_x_flag_with, set_y_flag_with, set_z_flag_with, unset_n_flag, X_FLAG_BITMASK,
+        Y_FLAG_BITMASK,
+    },
+    Z80Memory, Z80,
+};
+
+impl Z80 {
+    // 16-Bit Arithmetic Group
+
+    /// ## ADD HL, ss
+    ///
+    /// ### Operation
+    ///
+    /// HL ← HL + ss
+    ///
+    /// ### Op Code
+    ///
+    /// ADD: `0 0 s s 1 0 0 1`
+    ///
+    /// ### Operands
+    ///
+    /// HL, ss
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair ss (any of register pairs BC, DE, HL, or
+    /// SP) are added to the contents of register pair HL and the result is
+    /// stored in HL. Operand ss is specified as follows in the assembled object
+    /// code.
+    ///
+    /// | Register Pair | ss  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | HL            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 3        | 11 (4, 4, 3)    | 2.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is set if carry from bit 11; otherwise, it is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 15; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If register pair HL contains the integer 4242h and register pair DE
+    /// contains 1111h, then upon the execution of an ADD HL, DE instruction,
+    /// the HL register pair contains 5353h.
+    fn add_hl_ss(&mut self, ss: u16, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 7);
+        let hl = self.hl();
+        self.memptr = hl.wrapping_add(1);
+        let result = self.add_16(hl, ss);
+        self.set_hl(result);
+
+        // T states
+        11
+    }
+
+    /// Adds two 16-bit values, setting the flags affected by the ADD HL, ADD IX
+    /// and ADD IY instructions, and returns the result.
+    fn add_16(&mut self, lhs: u16, rhs: u16) -> u16 {
+        let sum = lhs as u32 + rhs as u32;
+        let result = sum as u16;
+
+        set_h_flag_with(&mut self.f, (lhs ^ rhs ^ result) & 0x1000 != 0);
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, sum > 0xFFFF);
+
+        self.set_sixteen_bit_xy_flags(result);
+
+        result
+    }
+
+    pub fn add_hl_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_hl_ss(self.bc(), mem)
+    }
+
+    pub fn add_hl_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_hl_ss(self.de(), mem)
+    }
+
+    pub fn add_hl_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_hl_ss(self.hl(), mem)
+    }
+
+    pub fn add_hl_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_hl_ss(self.stack_pointer, mem)
+    }
+
+    /// ## ADC HL, ss
+    ///
+    /// ### Operation
+    ///
+    /// HL ← HL + ss + CY
+    ///
+    /// ### Op Code
+    ///
+    /// ADC: `1 1 1 0 1 1 0 1` (ED) `0 1 s s 1 0 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// HL, ss
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair ss (any of register pairs BC, DE, HL, or
+    /// SP) are added with the Carry flag (C flag in the F Register) to the
+    /// contents of register pair HL, and the result is stored in HL. Operand ss
+    /// is specified as follows in the assembled object code.
+    ///
+    /// | Register Pair | ss  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | HL            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 4        | 15 (4, 4, 4, 3) | 3.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if carry from bit 11; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 15; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the register pair BC contains 2222h, register pair HL contains 5437h,
+    /// and the Carry Flag is set, then upon the execution of an ADC HL, BC
+    /// instruction, HL contains 765Ah.
+    fn adc_hl_ss(&mut self, ss: u16, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 7);
+        let hl = self.hl();
+        self.memptr = hl.wrapping_add(1);
+        let carry = c_flag(&self.f) as u32;
+        let sum = hl as u32 + ss as u32 + carry;
+        let result = sum as u16;
+        self.set_hl(result);
+
+        set_h_flag_with(&mut self.f, (hl ^ ss ^ result) & 0x1000 != 0);
+        set_p_flag_with(&mut self.f, (hl ^ !ss) & (hl ^ result) & 0x8000 != 0);
+        unset_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, sum > 0xFFFF);
+        self.set_sixteen_bit_result_flags(result);
+
+        // T states
+        15
+    }
+
+    pub fn adc_hl_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.adc_hl_ss(self.bc(), mem)
+    }
+
+    pub fn adc_hl_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.adc_hl_ss(self.de(), mem)
+    }
+
+    pub fn adc_hl_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.adc_hl_ss(self.hl(), mem)
+    }
+
+    pub fn adc_hl_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.adc_hl_ss(self.stack_pointer, mem)
+    }
+
+    /// ## SBC HL, ss
+    ///
+    /// ### Operation
+    ///
+    /// HL ← HL – ss – CY
+    ///
+    /// ### Op Code
+    ///
+    /// SBC: `1 1 1 0 1 1 0 1` (ED) `0 1 s s 0 0 1 0`
+    ///
+    /// ### Operands
+    ///
+    /// HL, ss
+    ///
+    /// ### Description
+    ///
+    /// The contents of the register pair ss (any of register pairs BC, DE, HL,
+    /// or SP) and the Carry Flag (C flag in the F Register) are subtracted from
+    /// the contents of register pair HL, and the result is stored in HL.
+    /// Operand ss is specified as follows in the assembled object code.
+    ///
+    /// | Register Pair | ss  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | HL            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States        | 4 MHz E.T. |
+    /// | -------- | --------------- | ---------- |
+    /// | 4        | 15 (4, 4, 4, 3) | 3.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is set if result is negative; otherwise, it is reset.
+    ///
+    /// Z is set if result is 0; otherwise, it is reset.
+    ///
+    /// H is set if borrow from bit 12; otherwise, it is reset.
+    ///
+    /// P/V is set if overflow; otherwise, it is reset.
+    ///
+    /// N is set.
+    ///
+    /// C is set if borrow; otherwise, it is reset.
+    ///
+    /// ### Example
+    ///
+    /// If the contents of the HL, register pair are 9999h, the contents of
+    /// register pair DE are 1111h, and the Carry flag is set. At the execution
+    /// of an SBC HL, DE instruction, HL contains 8887h.
+    fn sbc_hl_ss(&mut self, ss: u16, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 7);
+        let hl = self.hl();
+        self.memptr = hl.wrapping_add(1);
+        let carry = c_flag(&self.f) as u16;
+        let result = hl.wrapping_sub(ss).wrapping_sub(carry);
+        self.set_hl(result);
+
+        set_h_flag_with(&mut self.f, (hl ^ ss ^ result) & 0x1000 != 0);
+        set_p_flag_with(&mut self.f, (hl ^ ss) & (hl ^ result) & 0x8000 != 0);
+        set_n_flag(&mut self.f);
+        set_c_flag_with(&mut self.f, (hl as u32) < ss as u32 + carry as u32);
+        self.set_sixteen_bit_result_flags(result);
+
+        // T states
+        15
+    }
+
+    pub fn sbc_hl_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.sbc_hl_ss(self.bc(), mem)
+    }
+
+    pub fn sbc_hl_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.sbc_hl_ss(self.de(), mem)
+    }
+
+    pub fn sbc_hl_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.sbc_hl_ss(self.hl(), mem)
+    }
+
+    pub fn sbc_hl_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.sbc_hl_ss(self.stack_pointer, mem)
+    }
+
+    /// Sets the S, Z, Y and X flags from a 16-bit ADC or SBC result.
+    /// ## ADD IX, pp
+    ///
+    /// ### Operation
+    ///
+    /// IX ← IX + pp
+    ///
+    /// ### Op Code
+    ///
+    /// ADD: `1 1 0 1 1 1 0 1` (DD) `0 0 p p 1 0 0 1`
+    ///
+    /// ### Operands
+    ///
+    /// IX, pp
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair pp (any of register pairs BC, DE, IX, or SP)
+    /// are added to the contents of Index Register IX, and the results are
+    /// stored in IX. Operand pp is specified as follows in the assembled
+    /// object code.
+    ///
+    /// | Register Pair | pp  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | IX            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 15 (4, 4, 4, 3)    | 3.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is set if carry from bit 11; otherwise, it is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 15; otherwise, it is reset.
+    fn add_ix_pp(&mut self, pp: u16, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 7);
+        self.memptr = self.ix.wrapping_add(1);
+        self.ix = self.add_16(self.ix, pp);
+
+        // T states
+        15
+    }
+
+    pub fn add_ix_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_ix_pp(self.bc(), mem)
+    }
+
+    pub fn add_ix_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_ix_pp(self.de(), mem)
+    }
+
+    pub fn add_ix_ix(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_ix_pp(self.ix, mem)
+    }
+
+    pub fn add_ix_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_ix_pp(self.stack_pointer, mem)
+    }
+
+    /// ## ADD IY, rr
+    ///
+    /// ### Operation
+    ///
+    /// IY ← IY + rr
+    ///
+    /// ### Op Code
+    ///
+    /// ADD: `1 1 1 1 1 1 0 1` (FD) `0 0 r r 1 0 0 1`
+    ///
+    /// ### Operands
+    ///
+    /// IY, rr
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair rr (any of register pairs BC, DE, IY, or SP)
+    /// are added to the contents of Index Register IY, and the results are
+    /// stored in IY. Operand rr is specified as follows in the assembled
+    /// object code.
+    ///
+    /// | Register Pair | rr  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | IY            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States           | 4 MHz E.T. |
+    /// | -------- | ------------------ | ---------- |
+    /// | 4        | 15 (4, 4, 4, 3)    | 3.75       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// S is not affected.
+    ///
+    /// Z is not affected.
+    ///
+    /// H is set if carry from bit 11; otherwise, it is reset.
+    ///
+    /// P/V is not affected.
+    ///
+    /// N is reset.
+    ///
+    /// C is set if carry from bit 15; otherwise, it is reset.
+    fn add_iy_rr(&mut self, rr: u16, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 7);
+        self.memptr = self.iy.wrapping_add(1);
+        self.iy = self.add_16(self.iy, rr);
+
+        // T states
+        15
+    }
+
+    pub fn add_iy_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_iy_rr(self.bc(), mem)
+    }
+
+    pub fn add_iy_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_iy_rr(self.de(), mem)
+    }
+
+    pub fn add_iy_iy(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_iy_rr(self.iy, mem)
+    }
+
+    pub fn add_iy_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.add_iy_rr(self.stack_pointer, mem)
+    }
+
+    /// ## INC ss
+    ///
+    /// ### Operation
+    ///
+    /// ss ← ss + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `0 0 s s 0 0 1 1`
+    ///
+    /// ### Operands
+    ///
+    /// ss
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair ss (any of register pairs BC, DE, HL, or
+    /// SP) are incremented. Operand ss is specified as follows in the assembled
+    /// object code.
+    ///
+    /// | Register Pair | ss  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | HL            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 6        | 1.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If the register pair contains 1000h, then upon the execution of an INC
+    /// HL instruction, HL contains 1001h.
+    pub fn inc_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_bc(self.bc().wrapping_add(1));
+
+        // T states
+        6
+    }
+
+    pub fn inc_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_de(self.de().wrapping_add(1));
+
+        // T states
+        6
+    }
+
+    pub fn inc_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_hl(self.hl().wrapping_add(1));
+
+        // T states
+        6
+    }
+
+    pub fn inc_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.stack_pointer = self.stack_pointer.wrapping_add(1);
+
+        // T states
+        6
+    }
+
+    /// ## INC IX
+    ///
+    /// ### Operation
+    ///
+    /// IX ← IX + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `1 1 0 1 1 1 0 1` (DD) `0 0 1 0 0 0 1 1` (23)
+    ///
+    /// ### Operands
+    ///
+    /// IX
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IX are incremented.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 10 (4, 6)   | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn inc_ix(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.ix = self.ix.wrapping_add(1);
+
+        // T states
+        10
+    }
+
+    /// ## INC IY
+    ///
+    /// ### Operation
+    ///
+    /// IY ← IY + 1
+    ///
+    /// ### Op Code
+    ///
+    /// INC: `1 1 1 1 1 1 0 1` (FD) `0 0 1 0 0 0 1 1` (23)
+    ///
+    /// ### Operands
+    ///
+    /// IY
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IY are incremented.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 10 (4, 6)   | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn inc_iy(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.iy = self.iy.wrapping_add(1);
+
+        // T states
+        10
+    }
+
+    /// ## DEC ss
+    ///
+    /// ### Operation
+    ///
+    /// ss ← ss – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `0 0 s s 1 0 1 1`
+    ///
+    /// ### Operands
+    ///
+    /// ss
+    ///
+    /// ### Description
+    ///
+    /// The contents of register pair ss (any of the register pairs BC, DE, HL,
+    /// or SP) are decremented. Operand ss is specified as follows in the
+    /// assembled object code.
+    ///
+    /// | Register Pair | ss  |
+    /// | ------------- | --- |
+    /// | BC            | 00  |
+    /// | DE            | 01  |
+    /// | HL            | 10  |
+    /// | SP            | 11  |
+    ///
+    /// | M Cycles | T States | 4 MHz E.T. |
+    /// | -------- | -------- | ---------- |
+    /// | 1        | 6        | 1.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    ///
+    /// ### Example
+    ///
+    /// If register pair H contains 1001h, then upon the execution of an DEC HL
+    /// instruction, HL contains 1000h.
+    pub fn dec_bc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_bc(self.bc().wrapping_sub(1));
+
+        // T states
+        6
+    }
+
+    pub fn dec_de(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_de(self.de().wrapping_sub(1));
+
+        // T states
+        6
+    }
+
+    pub fn dec_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.set_hl(self.hl().wrapping_sub(1));
+
+        // T states
+        6
+    }
+
+    pub fn dec_sp(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.stack_pointer = self.stack_pointer.wrapping_sub(1);
+
+        // T states
+        6
+    }
+    /// ## DEC IX
+    ///
+    /// ### Operation
+    ///
+    /// IX ← IX – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `1 1 0 1 1 1 0 1` (DD) `0 0 1 0 1 0 1 1` (2B)
+    ///
+    /// ### Operands
+    ///
+    /// IX
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IX are decremented.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 10 (4, 6)   | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn dec_ix(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.ix = self.ix.wrapping_sub(1);
+
+        // T states
+        10
+    }
+
+    /// ## DEC IY
+    ///
+    /// ### Operation
+    ///
+    /// IY ← IY – 1
+    ///
+    /// ### Op Code
+    ///
+    /// DEC: `1 1 1 1 1 1 0 1` (FD) `0 0 1 0 1 0 1 1` (2B)
+    ///
+    /// ### Operands
+    ///
+    /// IY
+    ///
+    /// ### Description
+    ///
+    /// The contents of Index Register IY are decremented.
+    ///
+    /// | M Cycles | T States    | 4 MHz E.T. |
+    /// | -------- | ----------- | ---------- |
+    /// | 2        | 10 (4, 6)   | 2.50       |
+    ///
+    /// ### Condition Bits Affected
+    ///
+    /// None.
+    pub fn dec_iy(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
+        self.iy = self.iy.wrapping_sub(1);
+
+        // T states
+        10
+    }
+
+    fn set_sixteen_bit_result_flags(&mut self, result: u16) {
+        set_s_flag_with(&mut self.f, result & 0x8000 != 0);
+        set_z_flag_with(&mut self.f, result == 0);
+        self.set_sixteen_bit_xy_flags(result);
+    }
+
+    fn set_sixteen_bit_xy_flags(&mut self, result: u16) {
+        // Extra behaviour from http://www.z80.info/zip/z80-documented.pdf p.16
+        // The undocumented flags are taken from the high byte of the result.
+        let high = (result >> 8) as u8;
+        set_y_flag_with(&mut self.f, high & Y_FLAG_BITMASK != 0);
+        set_x_flag_with(&mut self.f, high & X_FLAG_BITMASK != 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::z80::{
+        register_flags::{
+            c_flag, h_flag, n_flag, p_flag, s_flag, set_c_flag, set_s_flag, set_z_flag,
+            unset_c_flag, x_flag, y_flag, z_flag,
+        },
+        tests::Ram,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_add_hl_ss() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x4242);
+        z80.set_de(0x1111);
+        set_s_flag(&mut z80.f);
+        set_z_flag(&mut z80.f);
+
+        let t_states = z80.add_hl_de(ram);
+        assert_eq!(11, t_states);
+
+        assert_eq!(0x5353, z80.hl());
+        assert_eq!(0x4243, z80.memptr);
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(true, z_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_add_hl_ss_carries() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0xF800);
+        z80.set_bc(0x0800);
+
+        z80.add_hl_bc(ram);
+
+        assert_eq!(0x0000, z80.hl());
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(true, c_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+
+        z80.set_hl(0x2700);
+        z80.stack_pointer = 0x0100;
+
+        z80.add_hl_sp(ram);
+
+        assert_eq!(0x2800, z80.hl());
+        assert_eq!(false, h_flag(&z80.f));
+        assert_eq!(true, y_flag(&z80.f));
+        assert_eq!(true, x_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_add_index_pp() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.ix = 0x0333;
+        z80.set_bc(0x5555);
+
+        let t_states = z80.add_ix_bc(ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0x5888, z80.ix);
+        assert_eq!(0x0334, z80.memptr);
+
+        z80.iy = 0x8000;
+
+        let t_states = z80.add_iy_iy(ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0x0000, z80.iy);
+        assert_eq!(true, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_inc_dec_ss() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x1000);
+        z80.stack_pointer = 0x0000;
+        z80.ix = 0xFFFF;
+        set_c_flag(&mut z80.f);
+        let f = z80.f.value();
+
+        assert_eq!(6, z80.inc_hl(ram));
+        assert_eq!(0x1001, z80.hl());
+
+        assert_eq!(6, z80.dec_sp(ram));
+        assert_eq!(0xFFFF, z80.stack_pointer);
+
+        assert_eq!(10, z80.inc_ix(ram));
+        assert_eq!(0x0000, z80.ix);
+
+        assert_eq!(10, z80.dec_iy(ram));
+        assert_eq!(0xFFFF, z80.iy);
+
+        assert_eq!(f, z80.f.value());
+    }
+
+    #[test]
+    fn test_adc_hl_ss() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_bc(0x2222);
+        z80.set_hl(0x5437);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.adc_hl_bc(ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0x765A, z80.hl());
+        assert_eq!(false, c_flag(&z80.f));
+        assert_eq!(false, n_flag(&z80.f));
+        assert_eq!(false, p_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_adc_hl_ss_overflow() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_de(0x0001);
+        z80.set_hl(0x7FFF);
+        unset_c_flag(&mut z80.f);
+
+        z80.adc_hl_de(ram);
+
+        assert_eq!(0x8000, z80.hl());
+        assert_eq!(true, s_flag(&z80.f));
+        assert_eq!(true, p_flag(&z80.f));
+        assert_eq!(true, h_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sbc_hl_ss() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_de(0x1111);
+        z80.set_hl(0x9999);
+        set_c_flag(&mut z80.f);
+
+        let t_states = z80.sbc_hl_de(ram);
+        assert_eq!(15, t_states);
+
+        assert_eq!(0x8887, z80.hl());
+        assert_eq!(true, n_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+
+    #[test]
+    fn test_sbc_hl_hl() {
+        let ram = &Ram::new(&mut []);
+        let z80 = &mut Z80::new();
+        z80.set_hl(0x1234);
+        unset_c_flag(&mut z80.f);
+
+        z80.sbc_hl_hl(ram);
+
+        assert_eq!(0x0000, z80.hl());
+        assert_eq!(true, z_flag(&z80.f));
+        assert_eq!(false, c_flag(&z80.f));
+    }
+}
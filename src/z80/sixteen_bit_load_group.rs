@@ -45,42 +45,42 @@ impl Z80 {
     ///
     /// Upon the execution of an LD HL, 5000h instruction, the HL register pair
     /// contains 5000h.
-    pub fn ld_bc_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let cn = self.fetch_next_opcode(mem);
+    pub fn ld_bc_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let cn = self.fetch_next_byte(mem);
         self.c.set_value(cn);
 
-        let bn = self.fetch_next_opcode(mem);
+        let bn = self.fetch_next_byte(mem);
         self.b.set_value(bn);
 
         // T states
         10
     }
 
-    pub fn ld_de_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let en = self.fetch_next_opcode(mem);
+    pub fn ld_de_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let en = self.fetch_next_byte(mem);
         self.e.set_value(en);
 
-        let dn = self.fetch_next_opcode(mem);
+        let dn = self.fetch_next_byte(mem);
         self.d.set_value(dn);
 
         // T states
         10
     }
 
-    pub fn ld_hl_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let ln = self.fetch_next_opcode(mem);
+    pub fn ld_hl_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let ln = self.fetch_next_byte(mem);
         self.l.set_value(ln);
 
-        let hn = self.fetch_next_opcode(mem);
+        let hn = self.fetch_next_byte(mem);
         self.h.set_value(hn);
 
         // T states
         10
     }
 
-    pub fn ld_sp_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_sp_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         self.stack_pointer = ((high_n as u16) << 8) | low_n as u16;
 
@@ -123,9 +123,9 @@ impl Z80 {
     ///
     /// Upon the execution of an LD IX, 45A2h instruction, the index register
     /// contains integer 45A2h.
-    pub fn ld_ix_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ix_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         self.ix = ((high_n as u16) << 8) | low_n as u16;
 
@@ -168,9 +168,9 @@ impl Z80 {
     ///
     /// Upon the execution of an LD IY, 7733h instruction, Index Register IY
     /// contains the integer 7733h.
-    pub fn ld_iy_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_iy_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         self.iy = ((high_n as u16) << 8) | low_n as u16;
 
@@ -216,13 +216,16 @@ impl Z80 {
     /// If address 4545h contains 37h and address 4546h contains A1h, then upon
     /// the execution of an LD HL, (4545h) instruction, the HL register pair
     /// contains A137h.
-    pub fn ld_hl_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let nl = self.fetch_next_opcode(mem);
-        let nh = self.fetch_next_opcode(mem);
+    pub fn ld_hl_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let nl = self.fetch_next_byte(mem);
+        let nh = self.fetch_next_byte(mem);
 
         let address = ((nh as u16) << 8) | nl as u16;
-        self.l.set_value(mem.read(address));
-        self.h.set_value(mem.read(address.wrapping_add(1)));
+        let data = self.read_memory(mem, address);
+        self.l.set_value(data);
+        let data = self.read_memory(mem, address.wrapping_add(1));
+        self.h.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         16
@@ -276,49 +279,59 @@ impl Z80 {
     /// If Address 2130h contains 65h and address 2131h contains 78h, then upon
     /// the execution of an LD BC, (2130h) instruction, the BC register pair
     /// contains 7865h.
-    pub fn ld_ddbc_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ddbc_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        self.c.set_value(mem.read(address));
-        self.b.set_value(mem.read(address.wrapping_add(1)));
+        let data = self.read_memory(mem, address);
+        self.c.set_value(data);
+        let data = self.read_memory(mem, address.wrapping_add(1));
+        self.b.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_ddde_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ddde_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        self.e.set_value(mem.read(address));
-        self.d.set_value(mem.read(address.wrapping_add(1)));
+        let data = self.read_memory(mem, address);
+        self.e.set_value(data);
+        let data = self.read_memory(mem, address.wrapping_add(1));
+        self.d.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_ddhl_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ddhl_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        self.l.set_value(mem.read(address));
-        self.h.set_value(mem.read(address.wrapping_add(1)));
+        let data = self.read_memory(mem, address);
+        self.l.set_value(data);
+        let data = self.read_memory(mem, address.wrapping_add(1));
+        self.h.set_value(data);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_ddsp_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ddsp_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        let val_low = mem.read(address);
-        let val_high = mem.read(address.wrapping_add(1));
+        let val_low = self.read_memory(mem, address);
+        let val_high = self.read_memory(mem, address.wrapping_add(1));
+        self.memptr = address.wrapping_add(1);
 
         self.stack_pointer = ((val_high as u16) << 8) | val_low as u16;
 
@@ -364,13 +377,14 @@ impl Z80 {
     /// If address 6666h contains 92h, and address 6667h contains DAh, then upon
     /// the execution of an LD IX, (6666h) instruction, Index Register IX
     /// contains DA92h.
-    pub fn ld_ix_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_ix_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        let val_low = mem.read(address);
-        let val_high = mem.read(address.wrapping_add(1));
+        let val_low = self.read_memory(mem, address);
+        let val_high = self.read_memory(mem, address.wrapping_add(1));
+        self.memptr = address.wrapping_add(1);
 
         self.ix = ((val_high as u16) << 8) | val_low as u16;
 
@@ -416,13 +430,14 @@ impl Z80 {
     /// If address 6666h contains 92h, and address 6667h contains DAh, then upon
     /// the execution of an LD IY, (6666h) instruction, Index Register IY
     /// contains DA92h.
-    pub fn ld_iy_mem_nn(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_iy_mem_nn(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
-        let val_low = mem.read(address);
-        let val_high = mem.read(address + 1);
+        let val_low = self.read_memory(mem, address);
+        let val_high = self.read_memory(mem, address.wrapping_add(1));
+        self.memptr = address.wrapping_add(1);
 
         self.iy = ((val_high as u16) << 8) | val_low as u16;
 
@@ -468,14 +483,15 @@ impl Z80 {
     /// If register pair HL contains 483Ah, then upon the execution of an LD
     /// (B2291 – 1), HL instruction, address B229h contains 3Ah and address
     /// B22Ah contains 48h.
-    pub fn ld_mem_nn_hl(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_hl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
 
-        mem.write(address, self.l.value());
-        mem.write(address.wrapping_add(1), self.h.value());
+        self.write_memory(mem, address, self.l.value());
+        self.write_memory(mem, address.wrapping_add(1), self.h.value());
+        self.memptr = address.wrapping_add(1);
 
         // T states
         16
@@ -529,56 +545,59 @@ impl Z80 {
     /// If register pair BC contains the number 4644h, the instruction LD
     /// (1000h), BC results in 44h in memory location 1000h, and 46h in memory
     /// location 1001h.
-
-    pub fn ld_mem_nn_ddbc(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_ddbc(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
 
-        mem.write(address, self.c.value());
-        mem.write(address.wrapping_add(1), self.b.value());
+        self.write_memory(mem, address, self.c.value());
+        self.write_memory(mem, address.wrapping_add(1), self.b.value());
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_mem_nn_ddde(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_ddde(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
 
-        mem.write(address, self.e.value());
-        mem.write(address.wrapping_add(1), self.d.value());
+        self.write_memory(mem, address, self.e.value());
+        self.write_memory(mem, address.wrapping_add(1), self.d.value());
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_mem_nn_ddhl(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_ddhl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
 
-        mem.write(address, self.l.value());
-        mem.write(address.wrapping_add(1), self.h.value());
+        self.write_memory(mem, address, self.l.value());
+        self.write_memory(mem, address.wrapping_add(1), self.h.value());
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
     }
 
-    pub fn ld_mem_nn_ddsp(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let low_n = self.fetch_next_opcode(mem);
-        let high_n = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_ddsp(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let low_n = self.fetch_next_byte(mem);
+        let high_n = self.fetch_next_byte(mem);
 
         let address = ((high_n as u16) << 8) | low_n as u16;
 
         let sp_low: u8 = self.stack_pointer as u8;
         let sp_high: u8 = (self.stack_pointer >> 8) as u8;
-        mem.write(address, sp_low);
-        mem.write(address.wrapping_add(1), sp_high);
+        self.write_memory(mem, address, sp_low);
+        self.write_memory(mem, address.wrapping_add(1), sp_high);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
@@ -621,16 +640,17 @@ impl Z80 {
     /// If Index Register IX contains 5A30h, then upon the execution of an LD
     /// (4392h), IX instruction, memory location 4392h contains number 30h and
     /// location 4393h contains 5Ah.
-    pub fn ld_mem_nn_ix(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let address_low = self.fetch_next_opcode(mem);
-        let address_high = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_ix(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address_low = self.fetch_next_byte(mem);
+        let address_high = self.fetch_next_byte(mem);
 
         let address = ((address_high as u16) << 8) | address_low as u16;
 
         let ix_low: u8 = self.ix as u8;
         let ix_high: u8 = (self.ix >> 8) as u8;
-        mem.write(address, ix_low);
-        mem.write(address.wrapping_add(1), ix_high);
+        self.write_memory(mem, address, ix_low);
+        self.write_memory(mem, address.wrapping_add(1), ix_high);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
@@ -673,16 +693,17 @@ impl Z80 {
     /// If Index Register IY contains 4174h, then upon the execution of an LD
     /// (8838h), IY instruction, memory location 8838h contains number 74h and
     /// location 8839h contains 41h.
-    pub fn ld_mem_nn_iy(&mut self, mem: &mut dyn Z80Memory) -> u8 {
-        let address_low = self.fetch_next_opcode(mem);
-        let address_high = self.fetch_next_opcode(mem);
+    pub fn ld_mem_nn_iy(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        let address_low = self.fetch_next_byte(mem);
+        let address_high = self.fetch_next_byte(mem);
 
         let address = ((address_high as u16) << 8) | address_low as u16;
 
         let iy_low: u8 = self.iy as u8;
         let iy_high: u8 = (self.iy >> 8) as u8;
-        mem.write(address, iy_low);
-        mem.write(address.wrapping_add(1), iy_high);
+        self.write_memory(mem, address, iy_low);
+        self.write_memory(mem, address.wrapping_add(1), iy_high);
+        self.memptr = address.wrapping_add(1);
 
         // T states
         20
@@ -720,7 +741,8 @@ impl Z80 {
     ///
     /// If the register pair HL contains 442Eh, then upon the execution of an LD
     /// SP, HL instruction, the Stack Pointer also contains 442Eh.
-    pub fn ld_sp_hl(&mut self) -> u8 {
+    pub fn ld_sp_hl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
         self.stack_pointer = ((self.h.value() as u16) << 8) | self.l.value() as u16;
 
         // T states
@@ -750,7 +772,8 @@ impl Z80 {
     /// ### Example
     /// If Index Register IX contains 98DAh, then upon the execution of an LD
     /// SP, IX instruction, the Stack Pointer also contains 98DAh.
-    pub fn ld_sp_ix(&mut self) -> u8 {
+    pub fn ld_sp_ix(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
         self.stack_pointer = self.ix;
 
         // T states
@@ -780,7 +803,8 @@ impl Z80 {
     /// ### Example
     /// If Index Register IY contains A227h, then upon the execution of an LD
     /// SP, IY instruction, the Stack Pointer also contains A227h.
-    pub fn ld_sp_iy(&mut self) -> u8 {
+    pub fn ld_sp_iy(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 2);
         self.stack_pointer = self.iy;
 
         // T states
@@ -824,46 +848,49 @@ impl Z80 {
     /// 1007h, then upon the execution of a PUSH AF instruction, memory address
     /// 1006h contains 22h, memory address 1005h contains 33h, and the Stack
     /// Pointer contains 1005h.
-
-    pub fn push_qqbc(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_qqbc(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.b.value());
+        self.write_memory(mem, self.stack_pointer, self.b.value());
 
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.c.value());
+        self.write_memory(mem, self.stack_pointer, self.c.value());
 
         // T states
         11
     }
 
-    pub fn push_qqde(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_qqde(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.d.value());
+        self.write_memory(mem, self.stack_pointer, self.d.value());
 
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.e.value());
+        self.write_memory(mem, self.stack_pointer, self.e.value());
 
         // T states
         11
     }
 
-    pub fn push_qqhl(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_qqhl(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.h.value());
+        self.write_memory(mem, self.stack_pointer, self.h.value());
 
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.l.value());
+        self.write_memory(mem, self.stack_pointer, self.l.value());
 
         // T states
         11
     }
 
-    pub fn push_qqaf(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_qqaf(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.a.value());
+        self.write_memory(mem, self.stack_pointer, self.a.value());
 
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, self.f.value());
+        self.write_memory(mem, self.stack_pointer, self.f.value());
 
         // T states
         11
@@ -898,14 +925,15 @@ impl Z80 {
     /// 1007h, then upon the execution of a PUSH IX instruction, memory address
     /// 1006h contains 22h, memory address 1005h contains 33h, and the Stack
     /// Pointer contains 1005h.
-    pub fn push_ix(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_ix(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         let ix_high: u8 = (self.ix >> 8) as u8;
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, ix_high);
+        self.write_memory(mem, self.stack_pointer, ix_high);
 
         let ix_low: u8 = self.ix as u8;
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, ix_low);
+        self.write_memory(mem, self.stack_pointer, ix_low);
 
         // T states
         15
@@ -940,14 +968,15 @@ impl Z80 {
     /// 1007h, then upon the execution of a PUSH IY instruction, memory address
     /// 1006h contains 22h, memory address 1005h contains 33h, and the Stack
     /// Pointer contains 1005h.
-    pub fn push_iy(&mut self, mem: &mut dyn Z80Memory) -> u8 {
+    pub fn push_iy(&mut self, mem: &mut (impl Z80Memory + ?Sized)) -> u8 {
+        self.internal_cycles(mem, 1);
         let iy_high: u8 = (self.iy >> 8) as u8;
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, iy_high);
+        self.write_memory(mem, self.stack_pointer, iy_high);
 
         let iy_low: u8 = self.iy as u8;
         self.stack_pointer = self.stack_pointer.wrapping_sub(1);
-        mem.write(self.stack_pointer, iy_low);
+        self.write_memory(mem, self.stack_pointer, iy_low);
 
         // T states
         15
@@ -990,44 +1019,52 @@ impl Z80 {
     /// and location 1001h contains 33h, the instruction POP HL results in
     /// register pair HL containing 3355h, and the Stack Pointer containing
     /// 1002h.
-    pub fn pop_qqbc(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.c.set_value(mem.read(self.stack_pointer));
+    pub fn pop_qqbc(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.c.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        self.b.set_value(mem.read(self.stack_pointer));
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.b.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         // T states
         10
     }
 
-    pub fn pop_qqde(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.e.set_value(mem.read(self.stack_pointer));
+    pub fn pop_qqde(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.e.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        self.d.set_value(mem.read(self.stack_pointer));
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.d.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         // T states
         10
     }
 
-    pub fn pop_qqhl(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.l.set_value(mem.read(self.stack_pointer));
+    pub fn pop_qqhl(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.l.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        self.h.set_value(mem.read(self.stack_pointer));
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.h.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         // T states
         10
     }
 
-    pub fn pop_qqaf(&mut self, mem: &dyn Z80Memory) -> u8 {
-        self.f.set_value(mem.read(self.stack_pointer));
+    pub fn pop_qqaf(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.f.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        self.a.set_value(mem.read(self.stack_pointer));
+        let data = self.read_memory(mem, self.stack_pointer);
+        self.a.set_value(data);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         // T states
@@ -1063,11 +1100,11 @@ impl Z80 {
     /// If the Stack Pointer contains 1000h, memory location 1000h contains 55h,
     /// and location 1001h contains 33h, the instruction POP IX results in Index
     /// Register IX containing 3355h, and the Stack Pointer containing 1002h.
-    pub fn pop_ix(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let ix_low = mem.read(self.stack_pointer);
+    pub fn pop_ix(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let ix_low = self.read_memory(mem, self.stack_pointer);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        let ix_high = mem.read(self.stack_pointer);
+        let ix_high = self.read_memory(mem, self.stack_pointer);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         self.ix = ((ix_high as u16) << 8) | ix_low as u16;
@@ -1105,11 +1142,11 @@ impl Z80 {
     /// If the Stack Pointer contains 1000h, memory location 1000h contains 55h,
     /// and location 1001h contains 33h, the instruction POP IY results in Index
     /// Register IY containing 3355h, and the Stack Pointer containing 1002h.
-    pub fn pop_iy(&mut self, mem: &dyn Z80Memory) -> u8 {
-        let iy_low = mem.read(self.stack_pointer);
+    pub fn pop_iy(&mut self, mem: &(impl Z80Memory + ?Sized)) -> u8 {
+        let iy_low = self.read_memory(mem, self.stack_pointer);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
-        let iy_high = mem.read(self.stack_pointer);
+        let iy_high = self.read_memory(mem, self.stack_pointer);
         self.stack_pointer = self.stack_pointer.wrapping_add(1);
 
         self.iy = ((iy_high as u16) << 8) | iy_low as u16;
@@ -1119,6 +1156,7 @@ impl Z80 {
     }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::z80::{tests::Ram, Z80Memory, Z80};
 
@@ -1209,6 +1247,7 @@ mod tests {
 
         assert_eq!(0xF0, z80.h.value());
         assert_eq!(0x0F, z80.l.value());
+        assert_eq!(0x0004, z80.memptr);
     }
 
     #[test]
@@ -1305,6 +1344,7 @@ mod tests {
 
         assert_eq!(z80.l.value(), ram.read(3));
         assert_eq!(z80.h.value(), ram.read(4));
+        assert_eq!(0x0004, z80.memptr);
     }
 
     #[test]
@@ -1399,10 +1439,11 @@ mod tests {
 
     #[test]
     fn test_ld_sp_hl() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.set_hl(0x442E);
 
-        let t_states = z80.ld_sp_hl();
+        let t_states = z80.ld_sp_hl(ram);
         assert_eq!(6, t_states);
 
         assert_eq!(0x442E, z80.stack_pointer);
@@ -1410,10 +1451,11 @@ mod tests {
 
     #[test]
     fn test_ld_sp_ix() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.iy = 0x98DA;
 
-        let t_states = z80.ld_sp_ix();
+        let t_states = z80.ld_sp_ix(ram);
         assert_eq!(10, t_states);
 
         assert_eq!(z80.ix, z80.stack_pointer);
@@ -1421,10 +1463,11 @@ mod tests {
 
     #[test]
     fn test_ld_sp_iy() {
+        let ram = &Ram::new(&mut []);
         let z80 = &mut Z80::new();
         z80.iy = 0xA227;
 
-        let t_states = z80.ld_sp_iy();
+        let t_states = z80.ld_sp_iy(ram);
         assert_eq!(10, t_states);
 
         assert_eq!(z80.iy, z80.stack_pointer);
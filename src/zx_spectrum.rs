@@ -1,4 +1,4 @@
-use crate::z80::{Z80Memory, Z80};
+use crate::z80::{CpuVariant, ExecutionMode, MachineCycle, Z80Io, Z80Memory, Z80};
 
 static ROM_48: &[u8; 0x4000] = include_bytes!("48.rom");
 // static ROM_128_0: [u8] = include_bytes!("128-0.rom");
@@ -6,11 +6,11 @@ static ROM_48: &[u8; 0x4000] = include_bytes!("48.rom");
 
 struct ZxSpectrumMemory {
     rom: &'static [u8; 0x4000],
-    ram: [u8; 0x8000],
+    ram: [u8; 0xC000],
 }
 
 impl ZxSpectrumMemory {
-    fn new(rom: &'static [u8; 0x4000], ram: [u8; 0x8000]) -> ZxSpectrumMemory {
+    fn new(rom: &'static [u8; 0x4000], ram: [u8; 0xC000]) -> ZxSpectrumMemory {
         ZxSpectrumMemory { rom, ram }
     }
 }
@@ -25,6 +25,20 @@ impl Z80Memory for ZxSpectrumMemory {
         }
     }
 
+    fn machine_cycle(&self, cycle: MachineCycle, t_state: u64) -> u8 {
+        match cycle {
+            MachineCycle::OpcodeFetch(address)
+            | MachineCycle::MemoryRead(address)
+            | MachineCycle::MemoryWrite(address)
+            | MachineCycle::Internal(address)
+                if is_contended(address) =>
+            {
+                contention_delay(t_state)
+            }
+            _ => 0,
+        }
+    }
+
     fn write(&mut self, address: u16, data: u8) {
         let index = address as usize;
         if index >= self.rom.len() {
@@ -33,18 +47,145 @@ impl Z80Memory for ZxSpectrumMemory {
     }
 }
 
+/// The ULA responds to every even port. Reads report no keys pressed and
+/// writes latch the border colour from the low three bits.
+struct ZxSpectrumIo {
+    border_colour: u8,
+}
+
+impl ZxSpectrumIo {
+    fn new() -> ZxSpectrumIo {
+        ZxSpectrumIo { border_colour: 0 }
+    }
+}
+
+impl Z80Io for ZxSpectrumIo {
+    fn read_port(&mut self, _port: u16) -> u8 {
+        0xFF
+    }
+
+    fn write_port(&mut self, port: u16, data: u8) {
+        if port & 0x0001 == 0 {
+            self.border_colour = data & 0x07;
+        }
+    }
+
+    /// Port accesses are held off by the ULA when the high byte of the port
+    /// addresses contended memory, when the ULA itself is addressed, or both.
+    /// The delay is the total over each T state of the access, less the four
+    /// T states the access takes anyway.
+    fn machine_cycle(&mut self, cycle: MachineCycle, t_state: u64) -> u8 {
+        let port = cycle.address();
+        let pattern: &[u64] = if port & 0x0001 == 0 {
+            &[1, 3]
+        } else if is_contended(port) {
+            &[1, 1, 1, 1]
+        } else {
+            &[4]
+        };
+
+        let mut t = t_state;
+        for (index, &length) in pattern.iter().enumerate() {
+            // Only the high byte contends the first T state of a ULA access
+            if index > 0 || is_contended(port) {
+                t += contention_delay(t) as u64;
+            }
+            t += length;
+        }
+        (t - t_state - 4) as u8
+    }
+}
+
+/// The RAM the ULA reads the screen from, which the processor must share.
+fn is_contended(address: u16) -> bool {
+    (0x4000..0x8000).contains(&address)
+}
+
+/// The first T state at which the ULA fetches the top line of the screen.
+const CONTENTION_START: u64 = 14335;
+
+/// The ULA holds off the processor for the remainder of each eight T state
+/// fetch during the 128 T states of each of the 192 screen lines it draws.
+fn contention_delay(t_state: u64) -> u8 {
+    let t = t_state % T_STATES_PER_FRAME;
+    if !(CONTENTION_START..CONTENTION_START + 192 * 224).contains(&t) {
+        return 0;
+    }
+    let line_t = (t - CONTENTION_START) % 224;
+    if line_t < 128 {
+        [6, 5, 4, 3, 2, 1, 0, 0][line_t as usize % 8]
+    } else {
+        0
+    }
+}
+
+/// The length of a 48K Spectrum frame, each of which starts with the ULA
+/// raising the 50 Hz interrupt.
+pub const T_STATES_PER_FRAME: u64 = 69888;
+
+/// How long the ULA holds the INT line low at the start of a frame.
+const INTERRUPT_T_STATES: u64 = 32;
+
 pub struct ZxSpectrum {
     memory: ZxSpectrumMemory,
+    io: ZxSpectrumIo,
     processor: Z80,
 }
 
 impl ZxSpectrum {
     pub fn new() -> ZxSpectrum {
+        ZxSpectrum::with_cpu_variant(CpuVariant::ZilogNmos)
+    }
+
+    /// A Spectrum fitted with a different Z80, as found in later machines
+    /// and clones.
+    pub fn with_cpu_variant(variant: CpuVariant) -> ZxSpectrum {
+        let mut processor = Z80::with_variant(variant);
+        processor.set_execution_mode(ExecutionMode::MachineCycle);
         ZxSpectrum {
-            memory: ZxSpectrumMemory::new(ROM_48, [0; 0x8000]),
-            processor: Z80::new(),
+            memory: ZxSpectrumMemory::new(ROM_48, [0; 0xC000]),
+            io: ZxSpectrumIo::new(),
+            processor,
         }
     }
+
+    /// Executes the next instruction, returning the number of T states it
+    /// took.
+    pub fn step(&mut self) -> u8 {
+        self.processor
+            .process_next_instruction(&mut self.memory, &mut self.io)
+    }
+
+    /// Runs one frame, raising the frame interrupt at its start, and returns
+    /// the number of T states run. Any overshoot of the last instruction is
+    /// taken off the next frame, so frames stay in step with the clock.
+    pub fn run_frame(&mut self) -> u64 {
+        let memory = &mut self.memory;
+        let io = &mut self.io;
+        let processor = &mut self.processor;
+
+        processor.request_interrupt(0xFF);
+        let mut t_states = processor
+            .run_until(memory, io, |z80| z80.frame_t_states() >= INTERRUPT_T_STATES)
+            .expect("invalid opcodes run as NOPs");
+        processor.release_interrupt();
+        t_states += processor
+            .run_until(memory, io, |z80| z80.frame_t_states() >= T_STATES_PER_FRAME)
+            .expect("invalid opcodes run as NOPs");
+        processor.end_frame(T_STATES_PER_FRAME);
+
+        t_states
+    }
+
+    pub fn border_colour(&self) -> u8 {
+        self.io.border_colour
+    }
+}
+
+impl Default for ZxSpectrum {
+    fn default() -> Self {
+        ZxSpectrum::new()
+    }
 }
 
 #[cfg(test)]
@@ -57,5 +198,82 @@ mod tests {
 
         assert_eq!(ROM_48, computer.memory.rom);
         assert!(computer.memory.ram.iter().all(|&b| b == 0));
+        assert_eq!(0, computer.border_colour());
+        assert_eq!(CpuVariant::ZilogNmos, computer.processor.variant());
+        assert_eq!(
+            ExecutionMode::MachineCycle,
+            computer.processor.execution_mode()
+        );
+    }
+
+    #[test]
+    fn test_run_frame() {
+        let computer = &mut ZxSpectrum::new();
+
+        let mut total = 0;
+        let mut overshoot = 0;
+        for frame in 1..=10 {
+            let t_states = computer.run_frame();
+            total += t_states;
+
+            // The last instruction runs on into the next frame, which is
+            // that much shorter
+            let carried = overshoot;
+            overshoot = computer.processor.frame_t_states();
+            assert_eq!(T_STATES_PER_FRAME - carried + overshoot, t_states);
+            assert_eq!(frame * T_STATES_PER_FRAME + overshoot, total);
+            assert_eq!(total, computer.processor.t_states());
+        }
+    }
+
+    #[test]
+    fn test_border_colour() {
+        let io = &mut ZxSpectrumIo::new();
+
+        io.write_port(0x01FE, 0b11111010);
+        assert_eq!(0b010, io.border_colour);
+
+        io.write_port(0x00FF, 0b00000101);
+        assert_eq!(0b010, io.border_colour);
+    }
+
+    #[test]
+    fn test_contention_delay() {
+        assert_eq!(0, contention_delay(CONTENTION_START - 1));
+        assert_eq!(6, contention_delay(CONTENTION_START));
+        assert_eq!(1, contention_delay(CONTENTION_START + 5));
+        assert_eq!(0, contention_delay(CONTENTION_START + 7));
+        assert_eq!(6, contention_delay(CONTENTION_START + 8));
+        assert_eq!(0, contention_delay(CONTENTION_START + 128));
+        assert_eq!(5, contention_delay(CONTENTION_START + 225));
+        assert_eq!(0, contention_delay(CONTENTION_START + 192 * 224));
+        assert_eq!(6, contention_delay(T_STATES_PER_FRAME + CONTENTION_START));
+    }
+
+    #[test]
+    fn test_memory_contention() {
+        let memory = &ZxSpectrumMemory::new(ROM_48, [0; 0xC000]);
+
+        let read =
+            |address| memory.machine_cycle(MachineCycle::MemoryRead(address), CONTENTION_START);
+        assert_eq!(0, read(0x3FFF));
+        assert_eq!(6, read(0x4000));
+        assert_eq!(6, read(0x7FFF));
+        assert_eq!(0, read(0x8000));
+    }
+
+    #[test]
+    fn test_io_contention() {
+        let io = &mut ZxSpectrumIo::new();
+        let mut write = |port| io.machine_cycle(MachineCycle::PortWrite(port), CONTENTION_START);
+
+        // N:1 C:3 is held off after the first T state
+        assert_eq!(5, write(0x00FE));
+        // C:1 C:3 is held off before the first
+        assert_eq!(6, write(0x40FE));
+        // N:4 is never held off
+        assert_eq!(0, write(0x00FF));
+        // C:1 C:1 C:1 C:1 is held off before every other T state
+        assert_eq!(12, write(0x40FF));
     }
 }